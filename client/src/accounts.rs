@@ -0,0 +1,44 @@
+use anchor_lang::{error::ErrorCode, prelude::*, ZeroCopy};
+use std::mem::size_of;
+
+pub use anchor_lending_example::{
+    protocol::{Admin, Bank, Pool},
+    user::User,
+};
+
+/// Decodes a zero-copy program account from raw account data
+/// The discriminator must match the requested account type
+pub fn decode_account<T: ZeroCopy>(data: &[u8]) -> Result<T> {
+    let discriminator = data
+        .get(..8)
+        .ok_or(ErrorCode::AccountDiscriminatorNotFound)?;
+    require!(
+        discriminator == T::DISCRIMINATOR,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    let body = data
+        .get(8..8 + size_of::<T>())
+        .ok_or(ErrorCode::AccountDidNotDeserialize)?;
+    Ok(bytemuck::pod_read_unaligned(body))
+}
+
+/// Decodes the admin account
+pub fn decode_admin(data: &[u8]) -> Result<Admin> {
+    decode_account(data)
+}
+
+/// Decodes a pool account
+pub fn decode_pool(data: &[u8]) -> Result<Pool> {
+    decode_account(data)
+}
+
+/// Decodes a bank account
+pub fn decode_bank(data: &[u8]) -> Result<Bank> {
+    decode_account(data)
+}
+
+/// Decodes a user account
+pub fn decode_user(data: &[u8]) -> Result<User> {
+    decode_account(data)
+}
@@ -0,0 +1,463 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, sysvar},
+    system_program, InstructionData,
+};
+use anchor_lending_example::{accounts, instruction, protocol::Bank, user::User};
+use anchor_spl::token::spl_token::native_mint;
+
+use crate::{
+    pda::{
+        find_admin_address, find_bank_address, find_bank_token_account_address,
+        find_native_token_account_address, find_pool_address, find_user_address,
+    },
+    PROGRAM_ID,
+};
+
+/// Read-only metas for the banks of every open position plus any extra bank
+/// These are the remaining accounts `BankInterface::load` resolves banks from
+pub fn bank_account_metas(pool_id: u8, user: &User, extra_bank_ids: &[u8]) -> Vec<AccountMeta> {
+    let mut bank_ids = user.open_bank_ids();
+    for bank_id in extra_bank_ids {
+        if !bank_ids.contains(bank_id) {
+            bank_ids.push(*bank_id);
+        }
+    }
+
+    bank_ids
+        .into_iter()
+        .map(|bank_id| AccountMeta::new_readonly(find_bank_address(pool_id, bank_id).0, false))
+        .collect()
+}
+
+/// Builds an instruction from its accounts, data and remaining accounts
+fn build_instruction(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining_accounts);
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: metas,
+        data: data.data(),
+    }
+}
+
+/// Create instruction to initialize a new user account
+pub fn initialize_user(authority: &Pubkey, pool_id: u8, user_id: u16) -> Instruction {
+    build_instruction(
+        accounts::InitializeUser {
+            user: find_user_address(pool_id, user_id, authority).0,
+            authority: *authority,
+            pool: find_pool_address(pool_id).0,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeUser { pool_id, user_id },
+        vec![],
+    )
+}
+
+/// Create instruction to grow a user account created before the pending authority
+/// was appended to the layout
+pub fn migrate_user(payer: &Pubkey, user_account_key: &Pubkey) -> Instruction {
+    build_instruction(
+        accounts::MigrateUser {
+            user_account: *user_account_key,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateUser {},
+        vec![],
+    )
+}
+
+/// Create instruction to deposit tokens into a bank
+/// With `all` set, the amount is ignored and the whole liability is repaid
+pub fn deposit(
+    user: &Pubkey,
+    user_account_key: &Pubkey,
+    bank: &Bank,
+    user_token_account: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+    all: bool,
+) -> Instruction {
+    let bank_key = find_bank_address(bank.pool_id, bank.bank_id).0;
+    build_instruction(
+        accounts::Deposit {
+            user: *user,
+            user_token_account: *user_token_account,
+            bank_token_account: find_bank_token_account_address(&bank_key).0,
+            user_account: *user_account_key,
+            bank: bank_key,
+            pool: find_pool_address(bank.pool_id).0,
+            admin: find_admin_address().0,
+            mint: bank.mint,
+            token_program: *token_program,
+        },
+        instruction::Deposit { amount, all },
+        vec![],
+    )
+}
+
+/// Create instruction to withdraw tokens from a bank
+/// Appends the banks of every open position and the withdrawn bank for the health check
+/// With `all` set, the amount is ignored and the whole collateral is withdrawn
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw(
+    user: &Pubkey,
+    user_account_key: &Pubkey,
+    user_account: &User,
+    bank: &Bank,
+    user_token_account: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+    all: bool,
+) -> Instruction {
+    let bank_key = find_bank_address(bank.pool_id, bank.bank_id).0;
+    build_instruction(
+        accounts::Withdraw {
+            user: *user,
+            user_token_account: *user_token_account,
+            bank_token_account: find_bank_token_account_address(&bank_key).0,
+            user_account: *user_account_key,
+            bank: bank_key,
+            pool: find_pool_address(bank.pool_id).0,
+            admin: find_admin_address().0,
+            mint: bank.mint,
+            token_program: *token_program,
+        },
+        instruction::Withdraw { amount, all },
+        bank_account_metas(bank.pool_id, user_account, &[bank.bank_id]),
+    )
+}
+
+/// Create instruction to deposit native SOL into a wrapped SOL bank
+pub fn deposit_native(
+    user: &Pubkey,
+    user_account_key: &Pubkey,
+    bank: &Bank,
+    amount: u64,
+) -> Instruction {
+    let bank_key = find_bank_address(bank.pool_id, bank.bank_id).0;
+    build_instruction(
+        accounts::DepositNative {
+            user: *user,
+            temporary_token_account: find_native_token_account_address(user_account_key).0,
+            bank_token_account: find_bank_token_account_address(&bank_key).0,
+            user_account: *user_account_key,
+            bank: bank_key,
+            pool: find_pool_address(bank.pool_id).0,
+            admin: find_admin_address().0,
+            mint: bank.mint,
+            token_program: native_token_program(&bank.mint),
+            system_program: system_program::ID,
+        },
+        instruction::DepositNative { amount },
+        vec![],
+    )
+}
+
+/// Create instruction to withdraw native SOL from a wrapped SOL bank
+/// Appends the banks of every open position and the withdrawn bank for the health check
+pub fn withdraw_native(
+    user: &Pubkey,
+    user_account_key: &Pubkey,
+    user_account: &User,
+    bank: &Bank,
+    amount: u64,
+) -> Instruction {
+    let bank_key = find_bank_address(bank.pool_id, bank.bank_id).0;
+    build_instruction(
+        accounts::WithdrawNative {
+            user: *user,
+            temporary_token_account: find_native_token_account_address(user_account_key).0,
+            bank_token_account: find_bank_token_account_address(&bank_key).0,
+            user_account: *user_account_key,
+            bank: bank_key,
+            pool: find_pool_address(bank.pool_id).0,
+            admin: find_admin_address().0,
+            mint: bank.mint,
+            token_program: native_token_program(&bank.mint),
+            system_program: system_program::ID,
+        },
+        instruction::WithdrawNative { amount },
+        bank_account_metas(bank.pool_id, user_account, &[bank.bank_id]),
+    )
+}
+
+/// Token accounts and programs a liquidator uses to repay and receive collateral
+pub struct LiquidatorAccounts {
+    /// Liquidator's wallet
+    pub liquidator: Pubkey,
+    /// Liquidator's token account receiving collateral
+    pub collateral_token_account: Pubkey,
+    /// Liquidator's token account repaying the liability
+    pub liability_token_account: Pubkey,
+    /// Token program of the collateral mint
+    pub collateral_token_program: Pubkey,
+    /// Token program of the liability mint
+    pub liability_token_program: Pubkey,
+}
+
+/// Create instruction to liquidate an unhealthy position
+/// Appends the liability token program when it differs and the banks of every open position
+pub fn liquidate(
+    liquidator: &LiquidatorAccounts,
+    user_account_key: &Pubkey,
+    user_account: &User,
+    collateral_bank: &Bank,
+    liability_bank: &Bank,
+    liability_amount: u64,
+) -> Instruction {
+    let collateral_bank_key = find_bank_address(collateral_bank.pool_id, collateral_bank.bank_id).0;
+    let liability_bank_key = find_bank_address(liability_bank.pool_id, liability_bank.bank_id).0;
+
+    let mut remaining_accounts = vec![];
+    if liquidator.liability_token_program != liquidator.collateral_token_program {
+        remaining_accounts.push(AccountMeta::new_readonly(
+            liquidator.liability_token_program,
+            false,
+        ));
+    }
+    remaining_accounts.extend(bank_account_metas(user_account.pool_id, user_account, &[]));
+
+    build_instruction(
+        accounts::Liquidate {
+            liquidator: liquidator.liquidator,
+            liquidator_collateral_token: liquidator.collateral_token_account,
+            liquidator_liability_token: liquidator.liability_token_account,
+            user_account: *user_account_key,
+            collateral_bank: collateral_bank_key,
+            liability_bank: liability_bank_key,
+            bank_collateral_token: find_bank_token_account_address(&collateral_bank_key).0,
+            bank_liability_token: find_bank_token_account_address(&liability_bank_key).0,
+            pool: find_pool_address(collateral_bank.pool_id).0,
+            collateral_mint: collateral_bank.mint,
+            liability_mint: liability_bank.mint,
+            admin: find_admin_address().0,
+            token_program: liquidator.collateral_token_program,
+        },
+        instruction::Liquidate {
+            amount: liability_amount,
+        },
+        remaining_accounts,
+    )
+}
+
+/// Create instruction to settle a user position in a sunset bank after its deadline
+pub fn force_settle(
+    caller: &Pubkey,
+    user_account_key: &Pubkey,
+    bank: &Bank,
+    user_token_account: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let bank_key = find_bank_address(bank.pool_id, bank.bank_id).0;
+    build_instruction(
+        accounts::ForceSettle {
+            caller: *caller,
+            bank: bank_key,
+            bank_token_account: find_bank_token_account_address(&bank_key).0,
+            user_account: *user_account_key,
+            user_token_account: *user_token_account,
+            mint: bank.mint,
+            token_program: *token_program,
+        },
+        instruction::ForceSettle {},
+        vec![],
+    )
+}
+
+/// Create instruction to settle a liability in a sunset bank against the user's collateral
+/// Appends the liability token program when it differs from the collateral token program
+pub fn force_settle_liability(
+    caller: &LiquidatorAccounts,
+    user_account_key: &Pubkey,
+    collateral_bank: &Bank,
+    liability_bank: &Bank,
+) -> Instruction {
+    let collateral_bank_key = find_bank_address(collateral_bank.pool_id, collateral_bank.bank_id).0;
+    let liability_bank_key = find_bank_address(liability_bank.pool_id, liability_bank.bank_id).0;
+
+    let mut remaining_accounts = vec![];
+    if caller.liability_token_program != caller.collateral_token_program {
+        remaining_accounts.push(AccountMeta::new_readonly(
+            caller.liability_token_program,
+            false,
+        ));
+    }
+
+    build_instruction(
+        accounts::ForceSettleLiability {
+            caller: caller.liquidator,
+            caller_liability_token: caller.liability_token_account,
+            caller_collateral_token: caller.collateral_token_account,
+            user_account: *user_account_key,
+            bank: liability_bank_key,
+            collateral_bank: collateral_bank_key,
+            bank_token_account: find_bank_token_account_address(&liability_bank_key).0,
+            bank_collateral_token: find_bank_token_account_address(&collateral_bank_key).0,
+            pool: find_pool_address(liability_bank.pool_id).0,
+            mint: liability_bank.mint,
+            collateral_mint: collateral_bank.mint,
+            token_program: caller.collateral_token_program,
+        },
+        instruction::ForceSettleLiability {},
+        remaining_accounts,
+    )
+}
+
+/// Create instruction to flash borrow tokens from a bank vault
+/// A `flash_repay` of the amount plus the bank's fee must follow in the same transaction
+pub fn flash_borrow(
+    user: &Pubkey,
+    bank: &Bank,
+    user_token_account: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let bank_key = find_bank_address(bank.pool_id, bank.bank_id).0;
+    build_instruction(
+        accounts::FlashBorrow {
+            user: *user,
+            user_token_account: *user_token_account,
+            bank_token_account: find_bank_token_account_address(&bank_key).0,
+            bank: bank_key,
+            pool: find_pool_address(bank.pool_id).0,
+            admin: find_admin_address().0,
+            mint: bank.mint,
+            token_program: *token_program,
+            instructions: sysvar::instructions::ID,
+        },
+        instruction::FlashBorrow { amount },
+        vec![],
+    )
+}
+
+/// Create instruction to repay a flash loan, `amount` includes the bank's fee
+pub fn flash_repay(
+    user: &Pubkey,
+    bank: &Bank,
+    user_token_account: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let bank_key = find_bank_address(bank.pool_id, bank.bank_id).0;
+    build_instruction(
+        accounts::FlashRepay {
+            user: *user,
+            user_token_account: *user_token_account,
+            bank_token_account: find_bank_token_account_address(&bank_key).0,
+            bank: bank_key,
+            mint: bank.mint,
+            token_program: *token_program,
+        },
+        instruction::FlashRepay { amount },
+        vec![],
+    )
+}
+
+/// Create instruction to put a user account in flashloan mode
+/// An `end_flashloan` for the same user account must follow in the same transaction
+pub fn begin_flashloan(user_account_key: &Pubkey, authority: &Pubkey) -> Instruction {
+    build_instruction(
+        accounts::BeginFlashloan {
+            user_account: *user_account_key,
+            authority: *authority,
+            instructions: sysvar::instructions::ID,
+        },
+        instruction::BeginFlashloan {},
+        vec![],
+    )
+}
+
+/// Create instruction to leave flashloan mode
+/// Appends the banks of every open position plus the banks of positions opened during
+/// the flashloan, since `user_account` is read before the transaction runs
+pub fn end_flashloan(
+    user_account_key: &Pubkey,
+    user_account: &User,
+    authority: &Pubkey,
+    extra_bank_ids: &[u8],
+) -> Instruction {
+    build_instruction(
+        accounts::EndFlashloan {
+            user_account: *user_account_key,
+            authority: *authority,
+        },
+        instruction::EndFlashloan {},
+        bank_account_metas(user_account.pool_id, user_account, extra_bank_ids),
+    )
+}
+
+/// Create instruction to query the health of a user position
+/// Appends the banks of every open position, the result is read from simulation return data
+pub fn get_user_health(user_account_key: &Pubkey, user_account: &User) -> Instruction {
+    build_instruction(
+        accounts::GetUserHealth {
+            user_account: *user_account_key,
+        },
+        instruction::GetUserHealth {},
+        bank_account_metas(user_account.pool_id, user_account, &[]),
+    )
+}
+
+/// Create instruction to query the largest collateral withdrawal from a bank
+/// Appends the banks of every open position and the queried bank
+pub fn get_max_withdraw(
+    user_account_key: &Pubkey,
+    user_account: &User,
+    bank: &Bank,
+) -> Instruction {
+    build_instruction(
+        accounts::GetMaxWithdraw {
+            user_account: *user_account_key,
+            bank: find_bank_address(bank.pool_id, bank.bank_id).0,
+        },
+        instruction::GetMaxWithdraw {},
+        bank_account_metas(bank.pool_id, user_account, &[bank.bank_id]),
+    )
+}
+
+/// Create instruction to query the price of a bank's token at which a position becomes
+/// liquidatable
+/// Appends the banks of every open position and the queried bank
+pub fn get_liquidation_price(
+    user_account_key: &Pubkey,
+    user_account: &User,
+    bank: &Bank,
+) -> Instruction {
+    build_instruction(
+        accounts::GetLiquidationPrice {
+            user_account: *user_account_key,
+            bank: find_bank_address(bank.pool_id, bank.bank_id).0,
+        },
+        instruction::GetLiquidationPrice {},
+        bank_account_metas(bank.pool_id, user_account, &[bank.bank_id]),
+    )
+}
+
+/// Create instruction to query the largest borrow from a bank
+/// Appends the banks of every open position and the queried bank
+pub fn get_max_borrow(user_account_key: &Pubkey, user_account: &User, bank: &Bank) -> Instruction {
+    build_instruction(
+        accounts::GetMaxBorrow {
+            user_account: *user_account_key,
+            bank: find_bank_address(bank.pool_id, bank.bank_id).0,
+        },
+        instruction::GetMaxBorrow {},
+        bank_account_metas(bank.pool_id, user_account, &[bank.bank_id]),
+    )
+}
+
+/// Token program owning a native mint
+fn native_token_program(mint: &Pubkey) -> Pubkey {
+    if *mint == native_mint::ID {
+        anchor_spl::token::ID
+    } else {
+        anchor_spl::token_2022::ID
+    }
+}
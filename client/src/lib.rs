@@ -0,0 +1,17 @@
+//! Client helpers for the lending program
+//!
+//! Derives program addresses, builds instructions with every bank account
+//! `BankInterface::load` expects, and decodes program accounts. Health, max
+//! withdraw and borrow amounts and liquidation prices are computed with the
+//! same `health` module the program uses.
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+
+pub use accounts::*;
+pub use instructions::*;
+pub use pda::*;
+
+pub use anchor_lending_example::controller::health;
+pub use anchor_lending_example::ID as PROGRAM_ID;
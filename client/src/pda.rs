@@ -0,0 +1,53 @@
+use anchor_lang::prelude::Pubkey;
+
+pub use anchor_lending_example::protocol::{ADMIN_SEED, BANK_SEED, POOL_SEED};
+
+use crate::PROGRAM_ID;
+
+/// Seed of the token account holding a bank's tokens
+pub const TOKEN_ACCOUNT_SEED: &[u8] = b"token_account";
+/// Seed of user accounts
+pub const USER_SEED: &[u8] = b"user";
+/// Seed of the temporary wrapped SOL account used by native transfers
+pub const NATIVE_TOKEN_ACCOUNT_SEED: &[u8] = b"native_token_account";
+
+/// Derive the admin PDA address and bump
+pub fn find_admin_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ADMIN_SEED], &PROGRAM_ID)
+}
+
+/// Derive the pool PDA address and bump
+pub fn find_pool_address(pool_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_SEED, &[pool_id]], &PROGRAM_ID)
+}
+
+/// Derive the bank PDA address and bump
+pub fn find_bank_address(pool_id: u8, bank_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BANK_SEED, &[pool_id], &[bank_id]], &PROGRAM_ID)
+}
+
+/// Derive the bank token account PDA address and bump
+pub fn find_bank_token_account_address(bank: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TOKEN_ACCOUNT_SEED, bank.as_ref()], &PROGRAM_ID)
+}
+
+/// Derive the user account PDA address and bump
+pub fn find_user_address(pool_id: u8, user_id: u16, authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            USER_SEED,
+            &pool_id.to_le_bytes(),
+            &user_id.to_le_bytes(),
+            authority.as_ref(),
+        ],
+        &PROGRAM_ID,
+    )
+}
+
+/// Derive the temporary wrapped SOL account PDA address and bump
+pub fn find_native_token_account_address(user_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[NATIVE_TOKEN_ACCOUNT_SEED, user_account.as_ref()],
+        &PROGRAM_ID,
+    )
+}
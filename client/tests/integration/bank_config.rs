@@ -0,0 +1,135 @@
+use anchor_lending_example::{
+    protocol::{AdminError, AdminRole, BankError, UpdateBankConfigParams},
+    user::UserError,
+};
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::fixtures::{custom_error, TestContext, ONE};
+
+/// Stricter weights than the default test bank config
+const UPDATED_CONFIG: UpdateBankConfigParams = UpdateBankConfigParams {
+    initial_asset_weight: 50,
+    maintenance_asset_weight: 60,
+    initial_liability_weight: 150,
+    maintenance_liability_weight: 140,
+};
+
+/// Test: Update Bank Weights As The Pool Authority
+/// Flow:
+/// 1. List a bank with the default weights
+/// 2. Update its weights signed by the pool authority
+/// Expected: The bank stores the new weights
+#[tokio::test]
+async fn pool_authority_updates_bank_config() {
+    let mut test = TestContext::new().await;
+    let bank = test.initialize_bank(&anchor_spl::token::ID, 1).await;
+
+    let authority = test.payer();
+    test.update_bank_config(&authority, &bank, UPDATED_CONFIG)
+        .await
+        .unwrap();
+
+    let state = test.fetch_bank(&bank).await;
+    assert_eq!(state.initial_asset_weight, 50);
+    assert_eq!(state.maintenance_asset_weight, 60);
+    assert_eq!(state.initial_liability_weight, 150);
+    assert_eq!(state.maintenance_liability_weight, 140);
+}
+
+/// Test: Update Bank Weights As A Risk Manager
+/// Flow:
+/// 1. Deposit 100 collateral tokens valued at 1 USD each
+/// 2. Grant the risk manager role to a new signer
+/// 3. Lower the collateral bank's asset weights as the risk manager
+/// 4. Borrow 60 liability tokens
+/// Expected: The update succeeds and the borrow fails, since the collateral
+/// now only supports 50 USD of weighted value
+#[tokio::test]
+async fn risk_manager_updates_bank_config() {
+    let mut test = TestContext::new().await;
+    let (collateral, liability) = test.initialize_market(1_000 * ONE).await;
+    let user = test.create_user(0).await;
+    let wallet = user.wallet.pubkey();
+    let collateral_account = test.fund(&collateral, &wallet, 100 * ONE).await;
+    let liability_account = test.fund(&liability, &wallet, 0).await;
+    test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap();
+
+    let risk_manager = Keypair::new();
+    test.grant_role(&risk_manager.pubkey(), AdminRole::RiskManager)
+        .await;
+    test.update_bank_config(&risk_manager, &collateral, UPDATED_CONFIG)
+        .await
+        .unwrap();
+    assert_eq!(test.fetch_bank(&collateral).await.initial_asset_weight, 50);
+
+    let error = test
+        .withdraw(&user, &liability, &liability_account, 60 * ONE)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        Some(UserError::InsufficientCollateral.into())
+    );
+}
+
+/// Test: Update Bank Weights Without Authority
+/// Flow:
+/// 1. List a bank with the default weights
+/// 2. Update its weights signed by a key that is neither the pool authority
+///    nor a risk manager
+/// Expected: The update fails as unauthorized and the weights are unchanged
+#[tokio::test]
+async fn unauthorized_update_bank_config_fails() {
+    let mut test = TestContext::new().await;
+    let bank = test.initialize_bank(&anchor_spl::token::ID, 1).await;
+
+    let error = test
+        .update_bank_config(&Keypair::new(), &bank, UPDATED_CONFIG)
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(error), Some(AdminError::Unauthorized.into()));
+    assert_eq!(test.fetch_bank(&bank).await.initial_asset_weight, 80);
+}
+
+/// Test: Update Bank Weights To Invalid Values
+/// Flow:
+/// 1. List a bank with the default weights
+/// 2. Update it with an asset weight above 100, a liability weight below 100,
+///    and maintenance weights stricter than initial weights
+/// Expected: Every update fails with invalid weights and the weights are unchanged
+#[tokio::test]
+async fn update_bank_config_with_invalid_weights_fails() {
+    let mut test = TestContext::new().await;
+    let bank = test.initialize_bank(&anchor_spl::token::ID, 1).await;
+    let authority = test.payer();
+
+    let configs = [
+        UpdateBankConfigParams {
+            maintenance_asset_weight: 101,
+            ..UPDATED_CONFIG
+        },
+        UpdateBankConfigParams {
+            initial_liability_weight: 99,
+            maintenance_liability_weight: 99,
+            ..UPDATED_CONFIG
+        },
+        UpdateBankConfigParams {
+            initial_asset_weight: 70,
+            ..UPDATED_CONFIG
+        },
+        UpdateBankConfigParams {
+            maintenance_liability_weight: 160,
+            ..UPDATED_CONFIG
+        },
+    ];
+    for params in configs {
+        let error = test
+            .update_bank_config(&authority, &bank, params)
+            .await
+            .unwrap_err();
+        assert_eq!(custom_error(error), Some(BankError::InvalidWeights.into()));
+    }
+    assert_eq!(test.fetch_bank(&bank).await.initial_asset_weight, 80);
+}
@@ -0,0 +1,121 @@
+use anchor_lending_example::user::UserError;
+use solana_sdk::signature::Signer;
+
+use crate::fixtures::{custom_error, TestContext, ONE};
+
+/// Test: Withdraw To Borrow
+/// Flow:
+/// 1. Deposit 100 collateral tokens
+/// 2. Withdraw 60 liability tokens from an empty position
+/// Expected: A 60 token liability is opened and tokens are received
+#[tokio::test]
+async fn withdraw_from_empty_position_borrows() {
+    let mut test = TestContext::new().await;
+    let (collateral, liability) = test.initialize_market(1_000 * ONE).await;
+    let user = test.create_user(0).await;
+    let wallet = user.wallet.pubkey();
+    let collateral_account = test.fund(&collateral, &wallet, 100 * ONE).await;
+    let liability_account = test.fund(&liability, &wallet, 0).await;
+
+    test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap();
+    test.withdraw(&user, &liability, &liability_account, 60 * ONE)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test.fetch_user_balance(&user, &liability).await,
+        -((60 * ONE) as i64)
+    );
+    assert_eq!(test.fetch_token_amount(&liability_account).await, 60 * ONE);
+
+    let bank = test.fetch_bank(&liability).await;
+    assert_eq!(bank.total_deposits, 1_000 * ONE);
+    assert_eq!(bank.total_liabilities, 60 * ONE);
+}
+
+/// Test: Borrow Beyond Initial Margin
+/// Flow:
+/// 1. Deposit 100 collateral tokens, 80 USD of initial weighted collateral
+/// 2. Borrow 70 liability tokens, 84 USD of initial weighted liability
+/// Expected: Transaction fails with insufficient collateral and no liability is opened
+#[tokio::test]
+async fn borrow_beyond_initial_margin_fails() {
+    let mut test = TestContext::new().await;
+    let (collateral, liability) = test.initialize_market(1_000 * ONE).await;
+    let user = test.create_user(0).await;
+    let wallet = user.wallet.pubkey();
+    let collateral_account = test.fund(&collateral, &wallet, 100 * ONE).await;
+    let liability_account = test.fund(&liability, &wallet, 0).await;
+
+    test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap();
+    let error = test
+        .withdraw(&user, &liability, &liability_account, 70 * ONE)
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        custom_error(error),
+        Some(UserError::InsufficientCollateral.into())
+    );
+    assert_eq!(test.fetch_user_balance(&user, &liability).await, 0);
+    assert_eq!(test.fetch_token_amount(&liability_account).await, 0);
+}
+
+/// Test: Borrow Without Collateral
+/// Flow:
+/// 1. Withdraw from an empty position with no deposits
+/// Expected: Transaction fails with insufficient collateral
+#[tokio::test]
+async fn borrow_without_collateral_fails() {
+    let mut test = TestContext::new().await;
+    let (_, liability) = test.initialize_market(1_000 * ONE).await;
+    let user = test.create_user(0).await;
+    let liability_account = test.fund(&liability, &user.wallet.pubkey(), 0).await;
+
+    let error = test
+        .withdraw(&user, &liability, &liability_account, ONE)
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        custom_error(error),
+        Some(UserError::InsufficientCollateral.into())
+    );
+}
+
+/// Test: Borrow From Bank 0 Without Collateral
+/// Flow:
+/// 1. A lender supplies 1,000 tokens to bank 0, the first bank listed in the pool
+/// 2. Withdraw 100 tokens from an empty bank 0 position with no deposits
+/// Expected: Transaction fails with insufficient collateral and the vault is untouched
+#[tokio::test]
+async fn borrow_from_first_bank_without_collateral_fails() {
+    let mut test = TestContext::new().await;
+    let bank = test.initialize_bank(&anchor_spl::token::ID, 1).await;
+    assert_eq!(bank.bank_id, 0);
+
+    let lender = test.create_user(1).await;
+    let lender_account = test.fund(&bank, &lender.wallet.pubkey(), 1_000 * ONE).await;
+    test.deposit(&lender, &bank, &lender_account, 1_000 * ONE)
+        .await
+        .unwrap();
+
+    let user = test.create_user(0).await;
+    let token_account = test.fund(&bank, &user.wallet.pubkey(), 0).await;
+    let error = test
+        .withdraw(&user, &bank, &token_account, 100 * ONE)
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        custom_error(error),
+        Some(UserError::InsufficientCollateral.into())
+    );
+    assert_eq!(test.fetch_user_balance(&user, &bank).await, 0);
+    assert_eq!(test.fetch_token_amount(&token_account).await, 0);
+    assert_eq!(test.fetch_bank(&bank).await.total_liabilities, 0);
+}
@@ -0,0 +1,162 @@
+use anchor_lending_example::user::UserError;
+use solana_sdk::signature::Signer;
+
+use crate::fixtures::{custom_error, TestContext, BANK_CONFIG, ONE};
+
+/// Transfer fee charged by the fee-bearing liability mint, in basis points
+const TRANSFER_FEE_BPS: u16 = 100;
+
+/// Test: Withdraw All Collateral
+/// Flow:
+/// 1. Deposit 100 collateral tokens
+/// 2. Withdraw with the all flag
+/// Expected: The whole deposit is returned and the position slot is freed
+#[tokio::test]
+async fn withdraw_all_clears_collateral() {
+    let mut test = TestContext::new().await;
+    let (collateral, _) = test.initialize_market(1_000 * ONE).await;
+    let user = test.create_user(0).await;
+    let collateral_account = test
+        .fund(&collateral, &user.wallet.pubkey(), 100 * ONE)
+        .await;
+    test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap();
+
+    test.withdraw_all(&user, &collateral, &collateral_account)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test.fetch_token_amount(&collateral_account).await,
+        100 * ONE
+    );
+    assert_eq!(test.fetch_user(&user).await.position_count(), 0);
+    assert_eq!(test.fetch_bank(&collateral).await.total_deposits, 0);
+}
+
+/// Test: Repay All Liabilities
+/// Flow:
+/// 1. Deposit 100 collateral tokens and borrow 60 liability tokens
+/// 2. Fund the borrower with extra liability tokens and repay with the all flag
+/// Expected: Exactly the liability is transferred and the position slot is freed
+#[tokio::test]
+async fn repay_all_clears_liability() {
+    let mut test = TestContext::new().await;
+    let (collateral, liability) = test.initialize_market(1_000 * ONE).await;
+    let user = test.create_user(0).await;
+    let wallet = user.wallet.pubkey();
+    let collateral_account = test.fund(&collateral, &wallet, 100 * ONE).await;
+    let liability_account = test.fund(&liability, &wallet, 0).await;
+    test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap();
+    test.withdraw(&user, &liability, &liability_account, 60 * ONE)
+        .await
+        .unwrap();
+    test.mint_to(&liability, &liability_account, 5 * ONE).await;
+
+    test.repay_all(&user, &liability, &liability_account)
+        .await
+        .unwrap();
+
+    assert_eq!(test.fetch_token_amount(&liability_account).await, 5 * ONE);
+    assert_eq!(test.fetch_user_balance(&user, &liability).await, 0);
+    assert_eq!(test.fetch_user(&user).await.position_count(), 1);
+    assert_eq!(test.fetch_bank(&liability).await.total_liabilities, 0);
+}
+
+/// Test: Repay All With A Transfer Fee
+/// Flow:
+/// 1. List a liability mint charging a 1% transfer fee and supply 1,000 tokens
+/// 2. Deposit 100 collateral tokens and borrow 60 liability tokens
+/// 3. Fund the borrower with extra liability tokens and repay with the all flag
+/// Expected: The borrower sends the liability grossed up by the fee, the vault receives
+/// exactly the liability and the position slot is freed
+#[tokio::test]
+async fn repay_all_with_transfer_fee_clears_liability() {
+    let mut test = TestContext::new().await;
+    let collateral = test.initialize_bank(&anchor_spl::token::ID, 1).await;
+    let fee_mint = test.create_transfer_fee_mint(TRANSFER_FEE_BPS).await;
+    let liability = test
+        .list_mint(&fee_mint, &anchor_spl::token_2022::ID, BANK_CONFIG, 1)
+        .await;
+
+    let lender = test.create_user(1).await;
+    let lender_account = test
+        .fund(&liability, &lender.wallet.pubkey(), 1_000 * ONE)
+        .await;
+    test.deposit(&lender, &liability, &lender_account, 1_000 * ONE)
+        .await
+        .unwrap();
+
+    let user = test.create_user(0).await;
+    let wallet = user.wallet.pubkey();
+    let collateral_account = test.fund(&collateral, &wallet, 100 * ONE).await;
+    let liability_account = test.fund(&liability, &wallet, 0).await;
+    test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap();
+    test.withdraw(&user, &liability, &liability_account, 60 * ONE)
+        .await
+        .unwrap();
+    test.mint_to(&liability, &liability_account, 5 * ONE).await;
+
+    let owed = test
+        .fetch_user_balance(&user, &liability)
+        .await
+        .unsigned_abs();
+    let held = test.fetch_token_amount(&liability_account).await;
+    test.repay_all(&user, &liability, &liability_account)
+        .await
+        .unwrap();
+
+    let gross = (owed * 10_000).div_ceil(10_000 - TRANSFER_FEE_BPS as u64);
+    assert_eq!(
+        test.fetch_token_amount(&liability_account).await,
+        held - gross
+    );
+    assert_eq!(test.fetch_user_balance(&user, &liability).await, 0);
+    assert_eq!(test.fetch_user(&user).await.position_count(), 1);
+    assert_eq!(test.fetch_bank(&liability).await.total_liabilities, 0);
+}
+
+/// Test: Clear Without A Matching Position
+/// Flow:
+/// 1. Deposit 50 collateral tokens
+/// 2. Repay all in the collateral bank, then withdraw all in the untouched liability bank
+/// Expected: Both fail with no position to clear
+#[tokio::test]
+async fn clear_without_position_fails() {
+    let mut test = TestContext::new().await;
+    let (collateral, liability) = test.initialize_market(1_000 * ONE).await;
+    let user = test.create_user(0).await;
+    let wallet = user.wallet.pubkey();
+    let collateral_account = test.fund(&collateral, &wallet, 100 * ONE).await;
+    let liability_account = test.fund(&liability, &wallet, 0).await;
+    test.deposit(&user, &collateral, &collateral_account, 50 * ONE)
+        .await
+        .unwrap();
+
+    let error = test
+        .repay_all(&user, &collateral, &collateral_account)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        Some(UserError::NoPositionToClear.into())
+    );
+
+    let error = test
+        .withdraw_all(&user, &liability, &liability_account)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        Some(UserError::NoPositionToClear.into())
+    );
+    assert_eq!(
+        test.fetch_user_balance(&user, &collateral).await,
+        50 * ONE as i64
+    );
+}
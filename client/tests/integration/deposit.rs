@@ -0,0 +1,50 @@
+use anchor_lang::prelude::Pubkey;
+use solana_sdk::signature::Signer;
+
+use crate::fixtures::{TestContext, ONE};
+
+/// Deposits into a fresh bank and checks user, bank and vault balances
+async fn assert_deposit(token_program: Pubkey) {
+    let mut test = TestContext::new().await;
+    let bank = test.initialize_bank(&token_program, 1).await;
+    let user = test.create_user(0).await;
+    let token_account = test.fund(&bank, &user.wallet.pubkey(), 100 * ONE).await;
+
+    test.deposit(&user, &bank, &token_account, 40 * ONE)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test.fetch_user_balance(&user, &bank).await,
+        (40 * ONE) as i64
+    );
+    assert_eq!(test.fetch_bank(&bank).await.total_deposits, 40 * ONE);
+    assert_eq!(test.fetch_token_amount(&token_account).await, 60 * ONE);
+
+    let vault = lending_client::find_bank_token_account_address(&bank.key).0;
+    assert_eq!(test.fetch_token_amount(&vault).await, 40 * ONE);
+}
+
+#[tokio::test]
+async fn deposit_spl_token() {
+    assert_deposit(anchor_spl::token::ID).await;
+}
+
+#[tokio::test]
+async fn deposit_token_2022() {
+    assert_deposit(anchor_spl::token_2022::ID).await;
+}
+
+#[tokio::test]
+async fn deposit_more_than_balance_fails() {
+    let mut test = TestContext::new().await;
+    let bank = test.initialize_bank(&anchor_spl::token::ID, 1).await;
+    let user = test.create_user(0).await;
+    let token_account = test.fund(&bank, &user.wallet.pubkey(), 10 * ONE).await;
+
+    assert!(test
+        .deposit(&user, &bank, &token_account, 20 * ONE)
+        .await
+        .is_err());
+    assert_eq!(test.fetch_user_balance(&user, &bank).await, 0);
+}
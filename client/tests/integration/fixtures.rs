@@ -0,0 +1,852 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{entrypoint::ProgramResult, instruction::Instruction, system_instruction},
+    system_program, InstructionData,
+};
+use anchor_lending_example::{
+    accounts, instruction,
+    protocol::{
+        AdminRole, BankConfigParams, BankStatus, PoolConfigParams, PoolStatus,
+        UpdateBankConfigParams, UpdatePoolParams, UpdatePriceParams,
+    },
+    user::UserHealth,
+};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        transfer_fee::instruction::initialize_transfer_fee_config, transfer_hook,
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    state::{Account as AccountState, Mint as MintState},
+};
+use lending_client::{
+    decode_admin, decode_bank, decode_pool, decode_user, find_admin_address, find_bank_address,
+    find_bank_token_account_address, find_pool_address, find_user_address, Bank, User, PROGRAM_ID,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::AccountSharedData,
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+use crate::hook_program::{
+    self, find_counter_address, find_validation_address, hook_account_metas, validation_data,
+    HOOK_PROGRAM_ID,
+};
+
+/// Default weights used for test banks
+pub const BANK_CONFIG: BankConfigParams = BankConfigParams {
+    initial_asset_weight: 80,
+    maintenance_asset_weight: 90,
+    initial_liability_weight: 120,
+    maintenance_liability_weight: 110,
+    allowed_extensions: 0,
+    flash_loan_fee: 0,
+};
+
+/// Decimals of every test mint
+pub const DECIMALS: u8 = 6;
+
+/// One whole token in native units
+pub const ONE: u64 = 1_000_000;
+
+/// Entrypoint wrapper giving the program account infos with a single lifetime
+fn process_instruction<'a, 'b, 'c, 'd>(
+    program_id: &'a Pubkey,
+    accounts: &'b [AccountInfo<'c>],
+    data: &'d [u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    anchor_lending_example::entry(program_id, accounts, data)
+}
+
+/// A listed bank and the mint it holds
+#[derive(Clone, Copy)]
+pub struct TestBank {
+    pub bank_id: u8,
+    pub key: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+}
+
+/// A user with a wallet, a lending account and a token account per mint
+pub struct TestUser {
+    pub wallet: Keypair,
+    pub account: Pubkey,
+}
+
+/// In-process bank with the lending program and both token programs loaded
+pub struct TestContext {
+    pub context: ProgramTestContext,
+    pub pool_id: u8,
+}
+
+impl TestContext {
+    /// Starts a bank, initializes the admin and a pool administered by the payer
+    pub async fn new() -> Self {
+        let mut program_test = ProgramTest::new(
+            "anchor_lending_example",
+            PROGRAM_ID,
+            processor!(process_instruction),
+        );
+        program_test.add_program(
+            "transfer_hook",
+            HOOK_PROGRAM_ID,
+            processor!(hook_program::process_instruction),
+        );
+        program_test.prefer_bpf(false);
+
+        let mut test = Self {
+            context: program_test.start_with_context().await,
+            pool_id: 0,
+        };
+        test.initialize_admin().await;
+        test.initialize_pool().await;
+        test
+    }
+
+    /// Gets a copy of the payer, which is the admin and pool authority
+    pub fn payer(&self) -> Keypair {
+        self.context.payer.insecure_clone()
+    }
+
+    /// Sends instructions in one transaction signed by the payer and any extra signers
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> std::result::Result<(), BanksClientError> {
+        let payer = self.payer();
+        let blockhash = self.context.get_new_latest_blockhash().await?;
+
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    /// Initializes the admin account with the payer as authority
+    async fn initialize_admin(&mut self) {
+        let ix = build_instruction(
+            accounts::Initialize {
+                admin: find_admin_address().0,
+                authority: self.context.payer.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::Initialize {},
+        );
+        self.process(&[ix], &[]).await.unwrap();
+    }
+
+    /// Initializes the next pool with the payer as pool authority and makes it the
+    /// pool later banks and users are created in
+    pub async fn create_pool(&mut self) -> u8 {
+        let data = self.fetch_data(&find_admin_address().0).await;
+        self.pool_id = decode_admin(&data).unwrap().pool_count;
+        self.initialize_pool().await;
+        self.pool_id
+    }
+
+    /// Initializes the current pool with the payer as pool authority
+    async fn initialize_pool(&mut self) {
+        let authority = self.context.payer.pubkey();
+        let ix = build_instruction(
+            accounts::InitializePool {
+                admin: find_admin_address().0,
+                pool: find_pool_address(self.pool_id).0,
+                authority,
+                pool_authority: authority,
+                system_program: system_program::ID,
+            },
+            instruction::InitializePool {
+                params: PoolConfigParams {
+                    name: [0; 32],
+                    close_factor: 50,
+                    max_liquidation_bonus: 5,
+                    max_positions: 16,
+                },
+            },
+        );
+        self.process(&[ix], &[]).await.unwrap();
+    }
+
+    /// Creates a mint without extensions owned by the given token program
+    pub async fn create_mint(&mut self, token_program: &Pubkey) -> Pubkey {
+        self.create_extension_mint(token_program, Keypair::new(), &[], Vec::new())
+            .await
+    }
+
+    /// Creates a Token-2022 mint charging `basis_points` on every transfer
+    pub async fn create_transfer_fee_mint(&mut self, basis_points: u16) -> Pubkey {
+        let mint = Keypair::new();
+        let init_ix = initialize_transfer_fee_config(
+            &spl_token_2022::ID,
+            &mint.pubkey(),
+            None,
+            None,
+            basis_points,
+            u64::MAX,
+        )
+        .unwrap();
+        self.create_extension_mint(
+            &spl_token_2022::ID,
+            mint,
+            &[ExtensionType::TransferFeeConfig],
+            vec![init_ix],
+        )
+        .await
+    }
+
+    /// Creates a Token-2022 mint whose transfers run the test hook program, along
+    /// with its validation account and transfer counter
+    pub async fn create_transfer_hook_mint(&mut self) -> Pubkey {
+        let mint = Keypair::new();
+        let init_ix = transfer_hook::instruction::initialize(
+            &spl_token_2022::ID,
+            &mint.pubkey(),
+            None,
+            Some(HOOK_PROGRAM_ID),
+        )
+        .unwrap();
+        let mint = self
+            .create_extension_mint(
+                &spl_token_2022::ID,
+                mint,
+                &[ExtensionType::TransferHook],
+                vec![init_ix],
+            )
+            .await;
+
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        for (address, data) in [
+            (find_validation_address(&mint), validation_data()),
+            (find_counter_address(&mint), vec![0; 8]),
+        ] {
+            let mut account = AccountSharedData::new(
+                rent.minimum_balance(data.len()),
+                data.len(),
+                &HOOK_PROGRAM_ID,
+            );
+            account.set_data_from_slice(&data);
+            self.context.set_account(&address, &account);
+        }
+        mint
+    }
+
+    /// Creates a mint sized for `extensions`, running their initialization
+    /// instructions before the mint itself is initialized
+    pub async fn create_extension_mint(
+        &mut self,
+        token_program: &Pubkey,
+        mint: Keypair,
+        extensions: &[ExtensionType],
+        extension_instructions: Vec<Instruction>,
+    ) -> Pubkey {
+        let payer = self.context.payer.pubkey();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let space = ExtensionType::try_calculate_account_len::<MintState>(extensions).unwrap();
+
+        let mut instructions = vec![system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(space),
+            space as u64,
+            token_program,
+        )];
+        instructions.extend(extension_instructions);
+        instructions.push(
+            spl_token_2022::instruction::initialize_mint2(
+                token_program,
+                &mint.pubkey(),
+                &payer,
+                None,
+                DECIMALS,
+            )
+            .unwrap(),
+        );
+        self.process(&instructions, &[&mint]).await.unwrap();
+        mint.pubkey()
+    }
+
+    /// Creates a token account for a mint
+    pub async fn create_token_account(
+        &mut self,
+        mint: &Pubkey,
+        owner: &Pubkey,
+        token_program: &Pubkey,
+    ) -> Pubkey {
+        let account = Keypair::new();
+        let payer = self.context.payer.pubkey();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+
+        // Token accounts carry the extensions their mint requires, such as withheld fees
+        let mint_data = self.fetch_data(mint).await;
+        let mint_extensions = StateWithExtensions::<MintState>::unpack(&mint_data)
+            .unwrap()
+            .get_extension_types()
+            .unwrap();
+        let space = ExtensionType::try_calculate_account_len::<AccountState>(
+            &ExtensionType::get_required_init_account_extensions(&mint_extensions),
+        )
+        .unwrap();
+
+        let instructions = [
+            system_instruction::create_account(
+                &payer,
+                &account.pubkey(),
+                rent.minimum_balance(space),
+                space as u64,
+                token_program,
+            ),
+            spl_token_2022::instruction::initialize_account3(
+                token_program,
+                &account.pubkey(),
+                mint,
+                owner,
+            )
+            .unwrap(),
+        ];
+        self.process(&instructions, &[&account]).await.unwrap();
+        account.pubkey()
+    }
+
+    /// Mints tokens to a token account, the payer is every mint's authority
+    pub async fn mint_to(&mut self, bank: &TestBank, destination: &Pubkey, amount: u64) {
+        let ix = spl_token_2022::instruction::mint_to(
+            &bank.token_program,
+            &bank.mint,
+            destination,
+            &self.context.payer.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap();
+        self.process(&[ix], &[]).await.unwrap();
+    }
+
+    /// Lists a new mint, activates the bank and prices it at `price` USD
+    pub async fn initialize_bank(&mut self, token_program: &Pubkey, price: u64) -> TestBank {
+        let mint = self.create_mint(token_program).await;
+        self.list_mint(&mint, token_program, BANK_CONFIG, price)
+            .await
+    }
+
+    /// Lists an existing mint with the given bank config, activates the bank and
+    /// prices it at `price` USD
+    pub async fn list_mint(
+        &mut self,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+        params: BankConfigParams,
+        price: u64,
+    ) -> TestBank {
+        let bank = self
+            .send_initialize_bank(mint, token_program, params)
+            .await
+            .unwrap();
+        let activate_ix = build_instruction(
+            accounts::UpdateBankStatus {
+                admin: find_admin_address().0,
+                pool: find_pool_address(self.pool_id).0,
+                authority: self.context.payer.pubkey(),
+                bank: bank.key,
+            },
+            instruction::UpdateBankStatus {
+                new_status: BankStatus::Active as u8,
+            },
+        );
+        self.process(&[activate_ix], &[]).await.unwrap();
+        self.set_price(&bank, price).await;
+        bank
+    }
+
+    /// Sends a bank listing for a mint signed by the payer, the bank stays inactive
+    pub async fn send_initialize_bank(
+        &mut self,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+        params: BankConfigParams,
+    ) -> std::result::Result<TestBank, BanksClientError> {
+        let bank_id = self.fetch_pool_bank_count().await;
+        let bank_key = find_bank_address(self.pool_id, bank_id).0;
+
+        let ix = build_instruction(
+            accounts::InitializeBank {
+                admin: find_admin_address().0,
+                pool: find_pool_address(self.pool_id).0,
+                bank: bank_key,
+                mint: *mint,
+                token_account: find_bank_token_account_address(&bank_key).0,
+                authority: self.context.payer.pubkey(),
+                token_program: *token_program,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeBank {
+                pool_id: self.pool_id,
+                params,
+            },
+        );
+        self.process(&[ix], &[]).await?;
+
+        Ok(TestBank {
+            bank_id,
+            key: bank_key,
+            mint: *mint,
+            token_program: *token_program,
+        })
+    }
+
+    /// Lists a collateral and a liability bank, both priced at 1 USD, and has a
+    /// lender supply `liquidity` to the liability bank
+    pub async fn initialize_market(&mut self, liquidity: u64) -> (TestBank, TestBank) {
+        let collateral = self.initialize_bank(&anchor_spl::token::ID, 1).await;
+        let liability = self.initialize_bank(&anchor_spl::token_2022::ID, 1).await;
+
+        let lender = self.create_user(u16::MAX).await;
+        let lender_account = self
+            .fund(&liability, &lender.wallet.pubkey(), liquidity)
+            .await;
+        self.deposit(&lender, &liability, &lender_account, liquidity)
+            .await
+            .unwrap();
+
+        (collateral, liability)
+    }
+
+    /// Sets a bank's price in whole USD with a zero exponent
+    pub async fn set_price(&mut self, bank: &TestBank, price: u64) {
+        let ix = build_instruction(
+            accounts::UpdatePrice {
+                admin: find_admin_address().0,
+                bank: bank.key,
+                authority: self.context.payer.pubkey(),
+            },
+            instruction::UpdatePrice {
+                params: UpdatePriceParams {
+                    ema_price: price,
+                    ema_conf: 0,
+                    price,
+                    conf: 0,
+                    exponent: 0,
+                    publish_time: 0,
+                },
+            },
+        );
+        self.process(&[ix], &[]).await.unwrap();
+    }
+
+    /// Sets the share of a liability a single liquidation may repay, as a percentage
+    pub async fn set_close_factor(&mut self, close_factor: u8) {
+        self.update_pool(|params| params.close_factor = close_factor)
+            .await;
+    }
+
+    /// Sets the operational status of the current pool
+    pub async fn set_pool_status(&mut self, status: PoolStatus) {
+        self.update_pool(|params| params.status = status as u8)
+            .await;
+    }
+
+    /// Updates the current pool, keeping every setting `update` leaves unchanged
+    async fn update_pool(&mut self, update: impl FnOnce(&mut UpdatePoolParams)) {
+        let data = self.fetch_data(&find_pool_address(self.pool_id).0).await;
+        let pool = decode_pool(&data).unwrap();
+        let mut params = UpdatePoolParams {
+            status: pool.status,
+            close_factor: pool.close_factor,
+            max_liquidation_bonus: pool.max_liquidation_bonus,
+            max_positions: pool.max_positions,
+        };
+        update(&mut params);
+
+        let ix = build_instruction(
+            accounts::UpdatePool {
+                pool: find_pool_address(self.pool_id).0,
+                authority: self.context.payer.pubkey(),
+            },
+            instruction::UpdatePool {
+                pool_id: self.pool_id,
+                params,
+            },
+        );
+        self.process(&[ix], &[]).await.unwrap();
+    }
+
+    /// Replaces the protocol-wide pause flags, see `PauseFlag`
+    pub async fn set_protocol_pause(&mut self, flags: u8) {
+        let ix = build_instruction(
+            accounts::SetProtocolPause {
+                admin: find_admin_address().0,
+                authority: self.context.payer.pubkey(),
+            },
+            instruction::SetProtocolPause { flags },
+        );
+        self.process(&[ix], &[]).await.unwrap();
+    }
+
+    /// Replaces the pause flags of the current pool, see `PauseFlag`
+    pub async fn set_pool_pause(&mut self, flags: u8) {
+        let ix = build_instruction(
+            accounts::SetPoolPause {
+                admin: find_admin_address().0,
+                pool: find_pool_address(self.pool_id).0,
+                authority: self.context.payer.pubkey(),
+            },
+            instruction::SetPoolPause {
+                pool_id: self.pool_id,
+                flags,
+            },
+        );
+        self.process(&[ix], &[]).await.unwrap();
+    }
+
+    /// Sets a bank's flash loan fee in basis points
+    pub async fn set_flash_loan_fee(&mut self, bank: &TestBank, fee: u16) {
+        let ix = build_instruction(
+            accounts::UpdateFlashLoanFee {
+                admin: find_admin_address().0,
+                pool: find_pool_address(self.pool_id).0,
+                authority: self.context.payer.pubkey(),
+                bank: bank.key,
+            },
+            instruction::UpdateFlashLoanFee { fee },
+        );
+        self.process(&[ix], &[]).await.unwrap();
+    }
+
+    /// Grants an admin role to `grantee`
+    pub async fn grant_role(&mut self, grantee: &Pubkey, role: AdminRole) {
+        let ix = build_instruction(
+            accounts::GrantRole {
+                admin: find_admin_address().0,
+                authority: self.context.payer.pubkey(),
+                grantee: *grantee,
+            },
+            instruction::GrantRole { role: role as u8 },
+        );
+        self.process(&[ix], &[]).await.unwrap();
+    }
+
+    /// Updates a bank's risk weights, signed by `authority`
+    pub async fn update_bank_config(
+        &mut self,
+        authority: &Keypair,
+        bank: &TestBank,
+        params: UpdateBankConfigParams,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = build_instruction(
+            accounts::UpdateBankConfig {
+                admin: find_admin_address().0,
+                pool: find_pool_address(self.pool_id).0,
+                authority: authority.pubkey(),
+                bank: bank.key,
+            },
+            instruction::UpdateBankConfig { params },
+        );
+        self.process(&[ix], &[authority]).await
+    }
+
+    /// Moves a bank into sunset with a deadline `seconds` from now
+    pub async fn sunset_bank(&mut self, bank: &TestBank, seconds: i64) {
+        let now = self.fetch_clock().await.unix_timestamp;
+        let ix = build_instruction(
+            accounts::SunsetBank {
+                admin: find_admin_address().0,
+                pool: find_pool_address(self.pool_id).0,
+                authority: self.context.payer.pubkey(),
+                bank: bank.key,
+            },
+            instruction::SunsetBank {
+                deadline: now + seconds,
+            },
+        );
+        self.process(&[ix], &[]).await.unwrap();
+    }
+
+    /// Moves the cluster clock forward by `seconds`
+    pub async fn advance_clock(&mut self, seconds: i64) {
+        let mut clock = self.fetch_clock().await;
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    /// Creates a funded wallet and its lending account
+    pub async fn create_user(&mut self, user_id: u16) -> TestUser {
+        let wallet = Keypair::new();
+        let fund_ix = system_instruction::transfer(
+            &self.context.payer.pubkey(),
+            &wallet.pubkey(),
+            1_000_000_000,
+        );
+        let init_ix = lending_client::initialize_user(&wallet.pubkey(), self.pool_id, user_id);
+        self.process(&[fund_ix, init_ix], &[&wallet]).await.unwrap();
+
+        TestUser {
+            account: find_user_address(self.pool_id, user_id, &wallet.pubkey()).0,
+            wallet,
+        }
+    }
+
+    /// Creates a token account for a wallet holding `amount` of a bank's mint
+    pub async fn fund(&mut self, bank: &TestBank, owner: &Pubkey, amount: u64) -> Pubkey {
+        let account = self
+            .create_token_account(&bank.mint, owner, &bank.token_program)
+            .await;
+        if amount > 0 {
+            self.mint_to(bank, &account, amount).await;
+        }
+        account
+    }
+
+    /// Deposits tokens from a user's token account into a bank
+    pub async fn deposit(
+        &mut self,
+        user: &TestUser,
+        bank: &TestBank,
+        token_account: &Pubkey,
+        amount: u64,
+    ) -> std::result::Result<(), BanksClientError> {
+        self.send_deposit(user, bank, token_account, amount, false)
+            .await
+    }
+
+    /// Repays a user's whole liability in a bank from their token account
+    pub async fn repay_all(
+        &mut self,
+        user: &TestUser,
+        bank: &TestBank,
+        token_account: &Pubkey,
+    ) -> std::result::Result<(), BanksClientError> {
+        self.send_deposit(user, bank, token_account, 0, true).await
+    }
+
+    /// Withdraws tokens from a bank to a user's token account, borrowing past the deposit
+    pub async fn withdraw(
+        &mut self,
+        user: &TestUser,
+        bank: &TestBank,
+        token_account: &Pubkey,
+        amount: u64,
+    ) -> std::result::Result<(), BanksClientError> {
+        self.send_withdraw(user, bank, token_account, amount, false)
+            .await
+    }
+
+    /// Withdraws a user's whole collateral in a bank to their token account
+    pub async fn withdraw_all(
+        &mut self,
+        user: &TestUser,
+        bank: &TestBank,
+        token_account: &Pubkey,
+    ) -> std::result::Result<(), BanksClientError> {
+        self.send_withdraw(user, bank, token_account, 0, true).await
+    }
+
+    /// Sends a deposit signed by the user
+    async fn send_deposit(
+        &mut self,
+        user: &TestUser,
+        bank: &TestBank,
+        token_account: &Pubkey,
+        amount: u64,
+        all: bool,
+    ) -> std::result::Result<(), BanksClientError> {
+        let bank_state = self.fetch_bank(bank).await;
+        let mut ix = lending_client::deposit(
+            &user.wallet.pubkey(),
+            &user.account,
+            &bank_state,
+            token_account,
+            &bank.token_program,
+            amount,
+            all,
+        );
+        ix.accounts
+            .extend(self.transfer_hook_metas(&bank.mint).await);
+        self.process(&[ix], &[&user.wallet]).await
+    }
+
+    /// Sends a withdrawal signed by the user
+    async fn send_withdraw(
+        &mut self,
+        user: &TestUser,
+        bank: &TestBank,
+        token_account: &Pubkey,
+        amount: u64,
+        all: bool,
+    ) -> std::result::Result<(), BanksClientError> {
+        let bank_state = self.fetch_bank(bank).await;
+        let user_state = self.fetch_user(user).await;
+        let mut ix = lending_client::withdraw(
+            &user.wallet.pubkey(),
+            &user.account,
+            &user_state,
+            &bank_state,
+            token_account,
+            &bank.token_program,
+            amount,
+            all,
+        );
+        ix.accounts
+            .extend(self.transfer_hook_metas(&bank.mint).await);
+        self.process(&[ix], &[&user.wallet]).await
+    }
+
+    /// Simulates a health query for a user and decodes the return data
+    pub async fn fetch_user_health(&mut self, user: &TestUser) -> UserHealth {
+        let user_state = self.fetch_user(user).await;
+        let ix = lending_client::get_user_health(&user.account, &user_state);
+        let data = self.simulate_return_data(ix).await;
+        UserHealth::try_from_slice(&data).unwrap()
+    }
+
+    /// Simulates a max withdraw query for a user and bank
+    pub async fn fetch_max_withdraw(&mut self, user: &TestUser, bank: &TestBank) -> u64 {
+        let user_state = self.fetch_user(user).await;
+        let bank_state = self.fetch_bank(bank).await;
+        let ix = lending_client::get_max_withdraw(&user.account, &user_state, &bank_state);
+        let data = self.simulate_return_data(ix).await;
+        u64::try_from_slice(&data).unwrap()
+    }
+
+    /// Simulates a max borrow query for a user and bank
+    pub async fn fetch_max_borrow(&mut self, user: &TestUser, bank: &TestBank) -> u64 {
+        let user_state = self.fetch_user(user).await;
+        let bank_state = self.fetch_bank(bank).await;
+        let ix = lending_client::get_max_borrow(&user.account, &user_state, &bank_state);
+        let data = self.simulate_return_data(ix).await;
+        u64::try_from_slice(&data).unwrap()
+    }
+
+    /// Simulates a liquidation price query for a user and bank
+    pub async fn fetch_liquidation_price(
+        &mut self,
+        user: &TestUser,
+        bank: &TestBank,
+    ) -> Option<u64> {
+        let user_state = self.fetch_user(user).await;
+        let bank_state = self.fetch_bank(bank).await;
+        let ix = lending_client::get_liquidation_price(&user.account, &user_state, &bank_state);
+        let data = self.simulate_return_data(ix).await;
+        Option::<u64>::try_from_slice(&data).unwrap()
+    }
+
+    /// Simulates an instruction signed by the payer and reads the program's return data
+    async fn simulate_return_data(&mut self, ix: Instruction) -> Vec<u8> {
+        let payer = self.payer();
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let transaction =
+            Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+
+        let simulation = self
+            .context
+            .banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+        let return_data = simulation
+            .simulation_details
+            .and_then(|details| details.return_data)
+            .expect("no return data");
+        assert_eq!(return_data.program_id, PROGRAM_ID);
+        return_data.data
+    }
+
+    /// Reads the remaining accounts transfers of a mint need for its transfer hook,
+    /// mints without a hook need none
+    async fn transfer_hook_metas(&mut self, mint: &Pubkey) -> Vec<AccountMeta> {
+        let data = self.fetch_data(mint).await;
+        let mint_state = StateWithExtensions::<MintState>::unpack(&data).unwrap();
+        match transfer_hook::get_program_id(&mint_state) {
+            Some(_) => hook_account_metas(mint),
+            None => Vec::new(),
+        }
+    }
+
+    /// Reads the number of transfers the test hook program has seen for a mint
+    pub async fn fetch_hook_transfers(&mut self, mint: &Pubkey) -> u64 {
+        let data = self.fetch_data(&find_counter_address(mint)).await;
+        u64::from_le_bytes(data[..8].try_into().unwrap())
+    }
+
+    /// Reads raw account data
+    async fn fetch_data(&mut self, address: &Pubkey) -> Vec<u8> {
+        self.context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .expect("account not found")
+            .data
+    }
+
+    /// Reads the number of banks listed in the pool, which is the next bank ID
+    async fn fetch_pool_bank_count(&mut self) -> u8 {
+        let data = self.fetch_data(&find_pool_address(self.pool_id).0).await;
+        decode_pool(&data).unwrap().bank_count
+    }
+
+    /// Reads the cluster clock
+    async fn fetch_clock(&mut self) -> Clock {
+        self.context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+    }
+
+    /// Reads a bank account
+    pub async fn fetch_bank(&mut self, bank: &TestBank) -> Bank {
+        let data = self.fetch_data(&bank.key).await;
+        decode_bank(&data).unwrap()
+    }
+
+    /// Reads a user account
+    pub async fn fetch_user(&mut self, user: &TestUser) -> User {
+        let data = self.fetch_data(&user.account).await;
+        decode_user(&data).unwrap()
+    }
+
+    /// Reads the signed balance of a user's position, liabilities are negative
+    pub async fn fetch_user_balance(&mut self, user: &TestUser, bank: &TestBank) -> i64 {
+        self.fetch_user(user)
+            .await
+            .find_balance_by_bank_id(bank.bank_id)
+    }
+
+    /// Reads the amount held by a token account
+    pub async fn fetch_token_amount(&mut self, address: &Pubkey) -> u64 {
+        let data = self.fetch_data(address).await;
+        StateWithExtensions::<AccountState>::unpack(&data)
+            .unwrap()
+            .base
+            .amount
+    }
+}
+
+/// Builds a lending program instruction without remaining accounts
+fn build_instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Extracts the custom program error code of a failed transaction
+pub fn custom_error(error: BanksClientError) -> Option<u32> {
+    match error.unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => Some(code),
+        _ => None,
+    }
+}
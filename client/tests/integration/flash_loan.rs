@@ -0,0 +1,168 @@
+use anchor_lang::{prelude::Pubkey, solana_program::instruction::Instruction};
+use anchor_lending_example::protocol::BankError;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::fixtures::{custom_error, TestBank, TestContext, ONE};
+
+/// Fee charged by the flash loan bank, 0.3%
+const FEE: u16 = 30;
+
+/// Market whose liability bank holds 1,000 tokens of liquidity and charges a
+/// flash loan fee, and a borrower holding tokens to pay the fee with
+struct FlashLoanSetup {
+    test: TestContext,
+    bank: TestBank,
+    borrower: Keypair,
+    token_account: Pubkey,
+}
+
+impl FlashLoanSetup {
+    async fn new() -> Self {
+        let mut test = TestContext::new().await;
+        let (_, bank) = test.initialize_market(1_000 * ONE).await;
+        test.set_flash_loan_fee(&bank, FEE).await;
+
+        let borrower = Keypair::new();
+        let token_account = test.fund(&bank, &borrower.pubkey(), 10 * ONE).await;
+
+        Self {
+            test,
+            bank,
+            borrower,
+            token_account,
+        }
+    }
+
+    async fn borrow_ix(&mut self, amount: u64) -> Instruction {
+        let bank = self.test.fetch_bank(&self.bank).await;
+        lending_client::flash_borrow(
+            &self.borrower.pubkey(),
+            &bank,
+            &self.token_account,
+            &self.bank.token_program,
+            amount,
+        )
+    }
+
+    async fn repay_ix(&mut self, amount: u64) -> Instruction {
+        let bank = self.test.fetch_bank(&self.bank).await;
+        lending_client::flash_repay(
+            &self.borrower.pubkey(),
+            &bank,
+            &self.token_account,
+            &self.bank.token_program,
+            amount,
+        )
+    }
+
+    async fn vault_amount(&mut self) -> u64 {
+        let vault = lending_client::find_bank_token_account_address(&self.bank.key).0;
+        self.test.fetch_token_amount(&vault).await
+    }
+}
+
+/// Test: Flash Borrow And Repay
+/// Flow:
+/// 1. Flash borrow 500 tokens and repay 500 tokens plus the 0.3% fee in one transaction
+/// Expected: The vault keeps the fee, the bank accrues it and no loan is left outstanding
+#[tokio::test]
+async fn flash_loan_repaid_with_fee() {
+    let mut setup = FlashLoanSetup::new().await;
+    let amount = 500 * ONE;
+    let fee = amount * FEE as u64 / 10_000;
+
+    let borrow_ix = setup.borrow_ix(amount).await;
+    let repay_ix = setup.repay_ix(amount + fee).await;
+    let borrower = setup.borrower.insecure_clone();
+    setup
+        .test
+        .process(&[borrow_ix, repay_ix], &[&borrower])
+        .await
+        .unwrap();
+
+    assert_eq!(setup.vault_amount().await, 1_000 * ONE + fee);
+    assert_eq!(
+        setup.test.fetch_token_amount(&setup.token_account).await,
+        10 * ONE - fee
+    );
+    let bank = setup.test.fetch_bank(&setup.bank).await;
+    assert_eq!(bank.flash_loan_fees, fee);
+    assert_eq!(bank.flash_loan_outstanding, 0);
+    assert_eq!(bank.total_deposits, 1_000 * ONE);
+}
+
+/// Test: Flash Borrow Without Repayment
+/// Flow:
+/// 1. Flash borrow 500 tokens alone
+/// 2. Flash borrow 500 tokens followed by a repayment missing the fee
+/// Expected: Both fail with the repayment missing and the vault is untouched
+#[tokio::test]
+async fn flash_borrow_without_matching_repay_fails() {
+    let mut setup = FlashLoanSetup::new().await;
+    let amount = 500 * ONE;
+    let borrower = setup.borrower.insecure_clone();
+
+    let borrow_ix = setup.borrow_ix(amount).await;
+    let error = setup
+        .test
+        .process(std::slice::from_ref(&borrow_ix), &[&borrower])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        Some(BankError::FlashRepayMissing.into())
+    );
+
+    let repay_ix = setup.repay_ix(amount).await;
+    let error = setup
+        .test
+        .process(&[borrow_ix, repay_ix], &[&borrower])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        Some(BankError::FlashRepayMissing.into())
+    );
+    assert_eq!(setup.vault_amount().await, 1_000 * ONE);
+}
+
+/// Test: Two Flash Loans Against One Repayment
+/// Flow:
+/// 1. Flash borrow twice from the same bank before a single repayment
+/// Expected: The second borrow fails with a flash loan already active
+#[tokio::test]
+async fn second_flash_borrow_before_repay_fails() {
+    let mut setup = FlashLoanSetup::new().await;
+    let amount = 100 * ONE;
+    let fee = amount * FEE as u64 / 10_000;
+    let borrower = setup.borrower.insecure_clone();
+
+    let borrow_ix = setup.borrow_ix(amount).await;
+    let repay_ix = setup.repay_ix(amount + fee).await;
+    let error = setup
+        .test
+        .process(&[borrow_ix.clone(), borrow_ix, repay_ix], &[&borrower])
+        .await
+        .unwrap_err();
+
+    assert_eq!(custom_error(error), Some(BankError::FlashLoanActive.into()));
+}
+
+/// Test: Flash Repay Without A Loan
+/// Flow:
+/// 1. Repay a flash loan that was never borrowed
+/// Expected: Transaction fails with no flash loan outstanding
+#[tokio::test]
+async fn flash_repay_without_borrow_fails() {
+    let mut setup = FlashLoanSetup::new().await;
+    let borrower = setup.borrower.insecure_clone();
+
+    let repay_ix = setup.repay_ix(ONE).await;
+    let error = setup
+        .test
+        .process(&[repay_ix], &[&borrower])
+        .await
+        .unwrap_err();
+
+    assert_eq!(custom_error(error), Some(BankError::NoFlashLoan.into()));
+}
@@ -0,0 +1,212 @@
+use anchor_lang::{prelude::Pubkey, solana_program::instruction::Instruction};
+use anchor_lending_example::user::UserError;
+use solana_sdk::signature::Signer;
+
+use crate::fixtures::{custom_error, TestBank, TestContext, TestUser, ONE};
+
+/// Market with a user holding 100 collateral tokens deposited and an empty
+/// liability token account
+struct FlashloanModeSetup {
+    test: TestContext,
+    collateral: TestBank,
+    liability: TestBank,
+    user: TestUser,
+    liability_account: Pubkey,
+}
+
+impl FlashloanModeSetup {
+    async fn new() -> Self {
+        let mut test = TestContext::new().await;
+        let (collateral, liability) = test.initialize_market(1_000 * ONE).await;
+        let user = test.create_user(0).await;
+        let wallet = user.wallet.pubkey();
+        let collateral_account = test.fund(&collateral, &wallet, 100 * ONE).await;
+        let liability_account = test.fund(&liability, &wallet, 0).await;
+        test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+            .await
+            .unwrap();
+
+        Self {
+            test,
+            collateral,
+            liability,
+            user,
+            liability_account,
+        }
+    }
+
+    fn begin_ix(&self) -> Instruction {
+        lending_client::begin_flashloan(&self.user.account, &self.user.wallet.pubkey())
+    }
+
+    /// Ends the flashloan, valuing the liability bank opened during it
+    async fn end_ix(&mut self) -> Instruction {
+        let user_state = self.test.fetch_user(&self.user).await;
+        lending_client::end_flashloan(
+            &self.user.account,
+            &user_state,
+            &self.user.wallet.pubkey(),
+            &[self.liability.bank_id],
+        )
+    }
+
+    async fn borrow_ix(&mut self, amount: u64) -> Instruction {
+        let user_state = self.test.fetch_user(&self.user).await;
+        let bank = self.test.fetch_bank(&self.liability).await;
+        lending_client::withdraw(
+            &self.user.wallet.pubkey(),
+            &self.user.account,
+            &user_state,
+            &bank,
+            &self.liability_account,
+            &self.liability.token_program,
+            amount,
+            false,
+        )
+    }
+
+    async fn repay_ix(&mut self, amount: u64) -> Instruction {
+        let bank = self.test.fetch_bank(&self.liability).await;
+        lending_client::deposit(
+            &self.user.wallet.pubkey(),
+            &self.user.account,
+            &bank,
+            &self.liability_account,
+            &self.liability.token_program,
+            amount,
+            false,
+        )
+    }
+
+    async fn process(
+        &mut self,
+        instructions: &[Instruction],
+    ) -> Result<(), solana_program_test::BanksClientError> {
+        let wallet = self.user.wallet.insecure_clone();
+        self.test.process(instructions, &[&wallet]).await
+    }
+}
+
+/// Test: Unhealthy Step Inside Flashloan Mode
+/// Flow:
+/// 1. Begin flashloan mode and borrow 200 liability tokens against 100 collateral tokens
+/// 2. Repay 150 tokens and end flashloan mode in the same transaction
+/// Expected: The intermediate borrow is allowed, the final 50 token liability is kept
+/// and the user account leaves flashloan mode
+#[tokio::test]
+async fn flashloan_mode_defers_health_check() {
+    let mut setup = FlashloanModeSetup::new().await;
+
+    // Borrowing 200 tokens outside flashloan mode is rejected
+    let borrow_ix = setup.borrow_ix(200 * ONE).await;
+    let error = setup
+        .process(std::slice::from_ref(&borrow_ix))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        Some(UserError::InsufficientCollateral.into())
+    );
+
+    let begin_ix = setup.begin_ix();
+    let repay_ix = setup.repay_ix(150 * ONE).await;
+    let end_ix = setup.end_ix().await;
+    setup
+        .process(&[begin_ix, borrow_ix, repay_ix, end_ix])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        setup
+            .test
+            .fetch_user_balance(&setup.user, &setup.liability)
+            .await,
+        -50 * ONE as i64
+    );
+    assert_eq!(
+        setup
+            .test
+            .fetch_token_amount(&setup.liability_account)
+            .await,
+        50 * ONE
+    );
+    assert!(!setup.test.fetch_user(&setup.user).await.in_flashloan());
+    assert_eq!(
+        setup
+            .test
+            .fetch_user_balance(&setup.user, &setup.collateral)
+            .await,
+        100 * ONE as i64
+    );
+}
+
+/// Test: Unhealthy End Of Flashloan Mode
+/// Flow:
+/// 1. Begin flashloan mode, borrow 200 liability tokens and end flashloan mode
+/// Expected: Ending fails the initial health check and the transaction is reverted
+#[tokio::test]
+async fn flashloan_mode_unhealthy_end_fails() {
+    let mut setup = FlashloanModeSetup::new().await;
+
+    let begin_ix = setup.begin_ix();
+    let borrow_ix = setup.borrow_ix(200 * ONE).await;
+    let end_ix = setup.end_ix().await;
+    let error = setup
+        .process(&[begin_ix, borrow_ix, end_ix])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        Some(UserError::InsufficientCollateral.into())
+    );
+
+    assert_eq!(
+        setup
+            .test
+            .fetch_user_balance(&setup.user, &setup.liability)
+            .await,
+        0
+    );
+    assert!(!setup.test.fetch_user(&setup.user).await.in_flashloan());
+}
+
+/// Test: Unbalanced Flashloan Mode Instructions
+/// Flow:
+/// 1. Begin flashloan mode without ending it
+/// 2. Begin flashloan mode ended by another user account
+/// 3. End flashloan mode without beginning it
+/// Expected: Beginning fails with the end missing and ending fails outside flashloan mode
+#[tokio::test]
+async fn flashloan_mode_requires_begin_and_end() {
+    let mut setup = FlashloanModeSetup::new().await;
+
+    let begin_ix = setup.begin_ix();
+    let error = setup
+        .process(std::slice::from_ref(&begin_ix))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        Some(UserError::EndFlashloanMissing.into())
+    );
+
+    let other = setup.test.create_user(1).await;
+    let other_state = setup.test.fetch_user(&other).await;
+    let other_end_ix =
+        lending_client::end_flashloan(&other.account, &other_state, &other.wallet.pubkey(), &[]);
+    let other_wallet = other.wallet.insecure_clone();
+    let user_wallet = setup.user.wallet.insecure_clone();
+    let error = setup
+        .test
+        .process(&[begin_ix, other_end_ix], &[&user_wallet, &other_wallet])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        Some(UserError::EndFlashloanMissing.into())
+    );
+
+    let end_ix = setup.end_ix().await;
+    let error = setup.process(&[end_ix]).await.unwrap_err();
+    assert_eq!(custom_error(error), Some(UserError::NotInFlashloan.into()));
+}
@@ -0,0 +1,222 @@
+use anchor_lending_example::user::UserError;
+use solana_sdk::signature::Signer;
+
+use crate::fixtures::{custom_error, TestContext, ONE};
+
+/// Test: Health Of A Position Without Liabilities
+/// Flow:
+/// 1. Deposit 100 collateral tokens priced at 1 USD
+/// 2. Query the position health
+/// Expected: Collateral is valued with each weight and the ratio is unbounded
+#[tokio::test]
+async fn reports_health_without_liabilities() {
+    let mut test = TestContext::new().await;
+    let (collateral, _) = test.initialize_market(1_000 * ONE).await;
+    let user = test.create_user(0).await;
+    let collateral_account = test
+        .fund(&collateral, &user.wallet.pubkey(), 100 * ONE)
+        .await;
+    test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap();
+
+    let health = test.fetch_user_health(&user).await;
+
+    assert_eq!(health.total_collateral, 100 * ONE as u128);
+    assert_eq!(health.weighted_collateral, 80 * ONE as u128);
+    assert_eq!(health.maintenance_collateral, 90 * ONE as u128);
+    assert_eq!(health.total_liability, 0);
+    assert_eq!(health.health_ratio, u64::MAX);
+    assert!(!health.liquidatable);
+}
+
+/// Test: Health Of A Borrowing Position
+/// Flow:
+/// 1. Deposit 100 collateral tokens and borrow 60 liability tokens, both at 1 USD
+/// 2. Query the position health
+/// 3. Raise the liability price to 2 USD and query again
+/// Expected: Values follow the weights, and the position turns liquidatable
+/// once maintenance liabilities exceed maintenance collateral
+#[tokio::test]
+async fn reports_health_of_borrowing_position() {
+    let mut test = TestContext::new().await;
+    let (collateral, liability) = test.initialize_market(1_000 * ONE).await;
+    let user = test.create_user(0).await;
+    let wallet = user.wallet.pubkey();
+    let collateral_account = test.fund(&collateral, &wallet, 100 * ONE).await;
+    let liability_account = test.fund(&liability, &wallet, 0).await;
+    test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap();
+    test.withdraw(&user, &liability, &liability_account, 60 * ONE)
+        .await
+        .unwrap();
+
+    let health = test.fetch_user_health(&user).await;
+
+    assert_eq!(health.total_collateral, 100 * ONE as u128);
+    assert_eq!(health.total_liability, 60 * ONE as u128);
+    assert_eq!(health.weighted_collateral, 80 * ONE as u128);
+    assert_eq!(health.weighted_liability, 72 * ONE as u128);
+    assert_eq!(health.maintenance_collateral, 90 * ONE as u128);
+    assert_eq!(health.maintenance_liability, 66 * ONE as u128);
+    // 90 / 66 in basis points
+    assert_eq!(health.health_ratio, 13_636);
+    assert!(!health.liquidatable);
+
+    test.set_price(&liability, 2).await;
+    let health = test.fetch_user_health(&user).await;
+
+    assert_eq!(health.maintenance_liability, 132 * ONE as u128);
+    // 90 / 132 in basis points
+    assert_eq!(health.health_ratio, 6_818);
+    assert!(health.liquidatable);
+}
+
+/// Test: Max Withdraw And Borrow Follow Initial Weights
+/// Flow:
+/// 1. Deposit 100 collateral tokens and borrow 30 liability tokens, both at 1 USD
+/// 2. Query the max withdraw and max borrow amounts
+/// 3. Borrow the max borrow amount, then one more token
+/// Expected: The queried borrow succeeds and a further borrow fails
+#[tokio::test]
+async fn max_amounts_follow_initial_weights() {
+    let mut test = TestContext::new().await;
+    let (collateral, liability) = test.initialize_market(1_000 * ONE).await;
+    let user = test.create_user(0).await;
+    let wallet = user.wallet.pubkey();
+    let collateral_account = test.fund(&collateral, &wallet, 100 * ONE).await;
+    let liability_account = test.fund(&liability, &wallet, 0).await;
+    test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap();
+    test.withdraw(&user, &liability, &liability_account, 30 * ONE)
+        .await
+        .unwrap();
+
+    // 80 USD initial collateral against 36 USD initial liability leaves 44 USD free
+    let max_withdraw = test.fetch_max_withdraw(&user, &collateral).await;
+    assert_eq!(max_withdraw, 55 * ONE);
+    let max_borrow = test.fetch_max_borrow(&user, &liability).await;
+    assert_eq!(max_borrow, 44 * ONE * 100 / 120);
+
+    // Nothing can be borrowed from the collateral bank beyond withdrawing it all
+    assert_eq!(test.fetch_max_borrow(&user, &collateral).await, 0);
+    // No collateral is held in the liability bank
+    assert_eq!(test.fetch_max_withdraw(&user, &liability).await, 0);
+
+    test.withdraw(&user, &liability, &liability_account, max_borrow)
+        .await
+        .unwrap();
+    assert_eq!(
+        custom_error(
+            test.withdraw(&user, &liability, &liability_account, ONE)
+                .await
+                .unwrap_err()
+        ),
+        Some(UserError::InsufficientCollateral.into())
+    );
+}
+
+/// Test: Max Withdraw Of An Unencumbered Deposit
+/// Flow:
+/// 1. Deposit 100 collateral tokens without borrowing
+/// 2. Query and withdraw the max withdraw amount
+/// Expected: The whole deposit is withdrawable
+#[tokio::test]
+async fn max_withdraw_of_unencumbered_deposit() {
+    let mut test = TestContext::new().await;
+    let (collateral, _) = test.initialize_market(1_000 * ONE).await;
+    let user = test.create_user(0).await;
+    let collateral_account = test
+        .fund(&collateral, &user.wallet.pubkey(), 100 * ONE)
+        .await;
+    test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap();
+
+    let max_withdraw = test.fetch_max_withdraw(&user, &collateral).await;
+    assert_eq!(max_withdraw, 100 * ONE);
+
+    test.withdraw(&user, &collateral, &collateral_account, max_withdraw)
+        .await
+        .unwrap();
+    assert_eq!(test.fetch_user_balance(&user, &collateral).await, 0);
+}
+
+/// Test: Max Borrow Capped By Liquidity
+/// Flow:
+/// 1. Supply 10 liability tokens of liquidity
+/// 2. Deposit 100 collateral tokens and query the max borrow
+/// Expected: Max borrow is the bank's liquidity rather than the collateral's capacity
+#[tokio::test]
+async fn max_borrow_capped_by_liquidity() {
+    let mut test = TestContext::new().await;
+    let (collateral, liability) = test.initialize_market(10 * ONE).await;
+    let user = test.create_user(0).await;
+    let collateral_account = test
+        .fund(&collateral, &user.wallet.pubkey(), 100 * ONE)
+        .await;
+    test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap();
+
+    assert_eq!(test.fetch_max_borrow(&user, &liability).await, 10 * ONE);
+}
+
+/// Test: Liquidation Prices Of A Borrowing Position
+/// Flow:
+/// 1. Deposit 55 collateral tokens at 4 USD and borrow 90 liability tokens at 1 USD
+/// 2. Query the liquidation price of both tokens
+/// 3. Raise the liability price past its liquidation price
+/// Expected: 198 USD maintenance collateral against 99 USD maintenance liability
+/// gives a liquidation price of 2 USD for either token, and the position turns
+/// liquidatable past it
+#[tokio::test]
+async fn reports_liquidation_prices() {
+    let mut test = TestContext::new().await;
+    let (collateral, liability) = test.initialize_market(1_000 * ONE).await;
+    test.set_price(&collateral, 4).await;
+    let user = test.create_user(0).await;
+    let wallet = user.wallet.pubkey();
+    let collateral_account = test.fund(&collateral, &wallet, 55 * ONE).await;
+    let liability_account = test.fund(&liability, &wallet, 0).await;
+    test.deposit(&user, &collateral, &collateral_account, 55 * ONE)
+        .await
+        .unwrap();
+    test.withdraw(&user, &liability, &liability_account, 90 * ONE)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test.fetch_liquidation_price(&user, &collateral).await,
+        Some(2)
+    );
+    assert_eq!(
+        test.fetch_liquidation_price(&user, &liability).await,
+        Some(2)
+    );
+
+    test.set_price(&liability, 3).await;
+    assert!(test.fetch_user_health(&user).await.liquidatable);
+}
+
+/// Test: No Liquidation Price Without Liabilities
+/// Flow:
+/// 1. Deposit 100 collateral tokens without borrowing
+/// 2. Query the liquidation price of the collateral
+/// Expected: No price makes the position liquidatable
+#[tokio::test]
+async fn no_liquidation_price_without_liabilities() {
+    let mut test = TestContext::new().await;
+    let (collateral, _) = test.initialize_market(1_000 * ONE).await;
+    let user = test.create_user(0).await;
+    let collateral_account = test
+        .fund(&collateral, &user.wallet.pubkey(), 100 * ONE)
+        .await;
+    test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap();
+
+    assert_eq!(test.fetch_liquidation_price(&user, &collateral).await, None);
+}
@@ -0,0 +1,93 @@
+//! Minimal transfer hook program counting the transfers of each hooked mint
+//!
+//! Every mint gets a validation account listing one extra account, a counter PDA
+//! the hook increments, so a transfer only succeeds when the lending program
+//! forwards the extra account metas to Token-2022.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{entrypoint::ProgramResult, instruction::AccountMeta},
+};
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::{
+    get_extra_account_metas_address,
+    instruction::{ExecuteInstruction, TransferHookInstruction},
+};
+
+/// Address the hook program is registered at
+pub const HOOK_PROGRAM_ID: Pubkey = Pubkey::new_from_array([7; 32]);
+
+/// Seed of the per-mint transfer counter
+const COUNTER_SEED: &[u8] = b"counter";
+
+/// Finds the transfer counter of a hooked mint
+pub fn find_counter_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[COUNTER_SEED, mint.as_ref()], &HOOK_PROGRAM_ID).0
+}
+
+/// Finds the account listing the extra account metas of a hooked mint
+pub fn find_validation_address(mint: &Pubkey) -> Pubkey {
+    get_extra_account_metas_address(mint, &HOOK_PROGRAM_ID)
+}
+
+/// Builds the validation account data requiring the mint's counter on every transfer
+pub fn validation_data() -> Vec<u8> {
+    let counter = ExtraAccountMeta::new_with_seeds(
+        &[
+            Seed::Literal {
+                bytes: COUNTER_SEED.to_vec(),
+            },
+            // Index 1 of the execute accounts is the mint
+            Seed::AccountKey { index: 1 },
+        ],
+        false,
+        true,
+    )
+    .unwrap();
+
+    let mut data = vec![0; ExtraAccountMetaList::size_of(1).unwrap()];
+    ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &[counter]).unwrap();
+    data
+}
+
+/// Remaining accounts a lending instruction needs to transfer a hooked mint
+pub fn hook_account_metas(mint: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(find_counter_address(mint), false),
+        AccountMeta::new_readonly(HOOK_PROGRAM_ID, false),
+        AccountMeta::new_readonly(find_validation_address(mint), false),
+    ]
+}
+
+/// Checks the execute accounts against the mint's validation account and
+/// increments the mint's transfer counter
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let TransferHookInstruction::Execute { .. } = TransferHookInstruction::unpack(data)? else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    // Accounts are source, mint, destination, authority, validation, then the extra metas
+    let [_, mint, _, _, validation, counter, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if *validation.key != get_extra_account_metas_address(mint.key, program_id) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    ExtraAccountMetaList::check_account_infos::<ExecuteInstruction>(
+        accounts,
+        data,
+        program_id,
+        &validation.try_borrow_data()?,
+    )?;
+
+    let mut counter_data = counter.try_borrow_mut_data()?;
+    let count = u64::from_le_bytes(counter_data[..8].try_into().unwrap());
+    counter_data[..8].copy_from_slice(&(count + 1).to_le_bytes());
+    Ok(())
+}
@@ -0,0 +1,283 @@
+use anchor_lending_example::user::UserError;
+use lending_client::LiquidatorAccounts;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::fixtures::{custom_error, TestBank, TestContext, TestUser, ONE};
+
+/// Borrower with 200 collateral tokens and 60 borrowed liability tokens, and a
+/// liquidator holding liability tokens to repay with
+struct LiquidationSetup {
+    test: TestContext,
+    collateral: TestBank,
+    liability: TestBank,
+    borrower: TestUser,
+    liquidator: Keypair,
+    liquidator_collateral_account: anchor_lang::prelude::Pubkey,
+    liquidator_liability_account: anchor_lang::prelude::Pubkey,
+}
+
+impl LiquidationSetup {
+    async fn new() -> Self {
+        let mut test = TestContext::new().await;
+        let (collateral, liability) = test.initialize_market(1_000 * ONE).await;
+
+        let borrower = test.create_user(0).await;
+        let wallet = borrower.wallet.pubkey();
+        let collateral_account = test.fund(&collateral, &wallet, 200 * ONE).await;
+        let liability_account = test.fund(&liability, &wallet, 0).await;
+        test.deposit(&borrower, &collateral, &collateral_account, 200 * ONE)
+            .await
+            .unwrap();
+        test.withdraw(&borrower, &liability, &liability_account, 60 * ONE)
+            .await
+            .unwrap();
+
+        let liquidator = Keypair::new();
+        let liquidator_collateral_account = test.fund(&collateral, &liquidator.pubkey(), 0).await;
+        let liquidator_liability_account =
+            test.fund(&liability, &liquidator.pubkey(), 100 * ONE).await;
+
+        Self {
+            test,
+            collateral,
+            liability,
+            borrower,
+            liquidator,
+            liquidator_collateral_account,
+            liquidator_liability_account,
+        }
+    }
+
+    /// Repays `amount` of the borrower's liability in exchange for collateral
+    async fn liquidate(
+        &mut self,
+        amount: u64,
+    ) -> std::result::Result<(), solana_program_test::BanksClientError> {
+        let (collateral, liability) = (self.collateral, self.liability);
+        let liability_token_account = self.liquidator_liability_account;
+        self.liquidate_banks(&collateral, &liability, &liability_token_account, amount)
+            .await
+    }
+
+    /// Repays `amount` in `liability` from the liquidator's token account in exchange
+    /// for the borrower's collateral in `collateral`
+    async fn liquidate_banks(
+        &mut self,
+        collateral: &TestBank,
+        liability: &TestBank,
+        liability_token_account: &anchor_lang::prelude::Pubkey,
+        amount: u64,
+    ) -> std::result::Result<(), solana_program_test::BanksClientError> {
+        let user = self.test.fetch_user(&self.borrower).await;
+        let collateral_bank = self.test.fetch_bank(collateral).await;
+        let liability_bank = self.test.fetch_bank(liability).await;
+
+        let ix = lending_client::liquidate(
+            &LiquidatorAccounts {
+                liquidator: self.liquidator.pubkey(),
+                collateral_token_account: self.liquidator_collateral_account,
+                liability_token_account: *liability_token_account,
+                collateral_token_program: collateral.token_program,
+                liability_token_program: liability.token_program,
+            },
+            &self.borrower.account,
+            &user,
+            &collateral_bank,
+            &liability_bank,
+            amount,
+        );
+        let liquidator = self.liquidator.insecure_clone();
+        self.test.process(&[ix], &[&liquidator]).await
+    }
+}
+
+/// Test: Liquidate Healthy Position
+/// Flow:
+/// 1. Borrow 60 liability tokens against 200 collateral tokens
+/// 2. Attempt to liquidate with prices unchanged
+/// Expected: Transaction fails with position healthy error
+#[tokio::test]
+async fn liquidate_healthy_position_fails() {
+    let mut setup = LiquidationSetup::new().await;
+
+    let error = setup.liquidate(10 * ONE).await.unwrap_err();
+
+    assert_eq!(custom_error(error), Some(UserError::PositionHealthy.into()));
+    assert_eq!(
+        setup
+            .test
+            .fetch_user_balance(&setup.borrower, &setup.liability)
+            .await,
+        -((60 * ONE) as i64)
+    );
+}
+
+/// Test: Liquidate Beyond Close Factor
+/// Flow:
+/// 1. Triple the liability token price, making the position liquidatable
+/// 2. Attempt to repay 40 of the 60 token liability with a 50% close factor
+/// Expected: Transaction fails with close factor exceeded error
+#[tokio::test]
+async fn liquidate_beyond_close_factor_fails() {
+    let mut setup = LiquidationSetup::new().await;
+    let liability = setup.liability;
+    setup.test.set_price(&liability, 3).await;
+
+    let error = setup.liquidate(40 * ONE).await.unwrap_err();
+
+    assert_eq!(
+        custom_error(error),
+        Some(UserError::CloseFactorExceeded.into())
+    );
+}
+
+/// Test: Liquidate Unhealthy Position
+/// Flow:
+/// 1. Triple the liability token price, 180 USD maintenance collateral against 198 USD
+/// 2. Repay 30 liability tokens, worth 90 USD
+/// Expected: Liability halves, the health ratio rises from 91% to 96% and the
+/// liquidator receives 90 USD of collateral plus the bonus
+#[tokio::test]
+async fn liquidate_unhealthy_position() {
+    let mut setup = LiquidationSetup::new().await;
+    let liability = setup.liability;
+    setup.test.set_price(&liability, 3).await;
+
+    setup.liquidate(30 * ONE).await.unwrap();
+
+    // 90 USD of collateral at a 5% discount
+    let seized = 90 * ONE * 100 / 95;
+    let test = &mut setup.test;
+    assert_eq!(
+        test.fetch_user_balance(&setup.borrower, &setup.liability)
+            .await,
+        -((30 * ONE) as i64)
+    );
+    assert_eq!(
+        test.fetch_user_balance(&setup.borrower, &setup.collateral)
+            .await,
+        (200 * ONE - seized) as i64
+    );
+    assert_eq!(
+        test.fetch_token_amount(&setup.liquidator_collateral_account)
+            .await,
+        seized
+    );
+    assert_eq!(
+        test.fetch_token_amount(&setup.liquidator_liability_account)
+            .await,
+        70 * ONE
+    );
+}
+
+/// Test: Liquidate Position Below Bonus Coverage
+/// Flow:
+/// 1. Quadruple the liability token price, 180 USD maintenance collateral against 264 USD
+/// 2. Repay 30 liability tokens, worth 120 USD
+/// Expected: Transaction fails since seizing collateral with the bonus lowers the
+/// health ratio from 68% to 50%
+#[tokio::test]
+async fn liquidate_lowering_health_ratio_fails() {
+    let mut setup = LiquidationSetup::new().await;
+    let liability = setup.liability;
+    setup.test.set_price(&liability, 4).await;
+
+    let error = setup.liquidate(30 * ONE).await.unwrap_err();
+
+    assert_eq!(
+        custom_error(error),
+        Some(UserError::LiquidationHealthNotImproved.into())
+    );
+    assert_eq!(
+        setup
+            .test
+            .fetch_user_balance(&setup.borrower, &setup.liability)
+            .await,
+        -((60 * ONE) as i64)
+    );
+}
+
+/// Test: Liquidate Whole Liability
+/// Flow:
+/// 1. Raise the close factor to 100% and triple the liability token price
+/// 2. Repay the whole 60 token liability, worth 180 USD
+/// Expected: The liability is closed even though the collateral left puts the
+/// position above the liquidation target
+#[tokio::test]
+async fn liquidate_whole_liability() {
+    let mut setup = LiquidationSetup::new().await;
+    let liability = setup.liability;
+    setup.test.set_close_factor(100).await;
+    setup.test.set_price(&liability, 3).await;
+
+    setup.liquidate(60 * ONE).await.unwrap();
+
+    // 180 USD of collateral at a 5% discount
+    let seized = 180 * ONE * 100 / 95;
+    let test = &mut setup.test;
+    assert_eq!(
+        test.fetch_user_balance(&setup.borrower, &setup.liability)
+            .await,
+        0
+    );
+    assert_eq!(
+        test.fetch_user_balance(&setup.borrower, &setup.collateral)
+            .await,
+        (200 * ONE - seized) as i64
+    );
+    assert_eq!(test.fetch_bank(&setup.liability).await.total_liabilities, 0);
+}
+
+/// Test: Liquidate Against A Collateral Position
+/// Flow:
+/// 1. Deposit 50 tokens into a second collateral bank and quadruple the liability token price
+/// 2. Attempt to "repay" 10 tokens of the second collateral bank in exchange for collateral
+/// Expected: Transaction fails with no liability to repay and no balance changes
+#[tokio::test]
+async fn liquidate_collateral_position_fails() {
+    let mut setup = LiquidationSetup::new().await;
+    let test = &mut setup.test;
+    let second_collateral = test.initialize_bank(&anchor_spl::token::ID, 1).await;
+    let wallet = setup.borrower.wallet.pubkey();
+    let borrower_account = test.fund(&second_collateral, &wallet, 50 * ONE).await;
+    test.deposit(
+        &setup.borrower,
+        &second_collateral,
+        &borrower_account,
+        50 * ONE,
+    )
+    .await
+    .unwrap();
+    let liquidator_account = test
+        .fund(&second_collateral, &setup.liquidator.pubkey(), 100 * ONE)
+        .await;
+    let liability = setup.liability;
+    test.set_price(&liability, 4).await;
+
+    let collateral = setup.collateral;
+    let error = setup
+        .liquidate_banks(
+            &collateral,
+            &second_collateral,
+            &liquidator_account,
+            10 * ONE,
+        )
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        custom_error(error),
+        Some(UserError::NoLiabilityToRepay.into())
+    );
+    let test = &mut setup.test;
+    assert_eq!(
+        test.fetch_user_balance(&setup.borrower, &second_collateral)
+            .await,
+        (50 * ONE) as i64
+    );
+    assert_eq!(
+        test.fetch_user_balance(&setup.borrower, &setup.collateral)
+            .await,
+        (200 * ONE) as i64
+    );
+}
@@ -0,0 +1,21 @@
+//! Integration tests running the lending program in an in-process bank
+//!
+//! The program is registered as a native processor, so these tests exercise
+//! the instruction handlers, account constraints and token CPIs without
+//! building the BPF binary first.
+
+mod bank_config;
+mod borrow;
+mod clear;
+mod deposit;
+mod fixtures;
+mod flash_loan;
+mod flashloan_mode;
+mod health;
+mod hook_program;
+mod liquidate;
+mod migrate;
+mod pause;
+mod pool;
+mod sunset;
+mod transfer_hook;
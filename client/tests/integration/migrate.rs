@@ -0,0 +1,68 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lending_example::user::{User, UserError};
+use solana_sdk::{account::AccountSharedData, signature::Signer};
+
+use crate::fixtures::{custom_error, TestContext, ONE};
+
+/// Test: Migrate A Legacy User Account
+/// Flow:
+/// 1. Deposit 40 tokens, then shrink the user account to the layout without a
+///    pending authority
+/// 2. Migrate the account, then deposit again
+/// 3. Migrate the account a second time
+/// Expected: The account grows to the current size with its balances in place and
+/// no pending authority, it keeps working and cannot be migrated twice
+#[tokio::test]
+async fn migrate_legacy_user_account() {
+    let mut test = TestContext::new().await;
+    let bank = test.initialize_bank(&anchor_spl::token::ID, 1).await;
+    let user = test.create_user(0).await;
+    let token_account = test.fund(&bank, &user.wallet.pubkey(), 100 * ONE).await;
+    test.deposit(&user, &bank, &token_account, 40 * ONE)
+        .await
+        .unwrap();
+
+    let mut account = test
+        .context
+        .banks_client
+        .get_account(user.account)
+        .await
+        .unwrap()
+        .unwrap();
+    let rent = test.context.banks_client.get_rent().await.unwrap();
+    account.data.truncate(User::LEGACY_LEN);
+    account.lamports = rent.minimum_balance(User::LEGACY_LEN);
+    test.context
+        .set_account(&user.account, &AccountSharedData::from(account));
+
+    let payer = test.payer();
+    let ix = lending_client::migrate_user(&payer.pubkey(), &user.account);
+    test.process(std::slice::from_ref(&ix), &[]).await.unwrap();
+
+    let migrated = test
+        .context
+        .banks_client
+        .get_account(user.account)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(migrated.data.len(), User::LEN);
+    assert_eq!(migrated.lamports, rent.minimum_balance(User::LEN));
+
+    let user_state = test.fetch_user(&user).await;
+    assert_eq!(user_state.pending_authority, Pubkey::default());
+    assert_eq!(
+        user_state.find_balance_by_bank_id(bank.bank_id),
+        (40 * ONE) as i64
+    );
+    test.deposit(&user, &bank, &token_account, 10 * ONE)
+        .await
+        .unwrap();
+    assert_eq!(
+        test.fetch_user_balance(&user, &bank).await,
+        (50 * ONE) as i64
+    );
+
+    let error = test.process(&[ix], &[]).await.unwrap_err();
+    assert_eq!(custom_error(error), Some(UserError::AlreadyMigrated.into()));
+}
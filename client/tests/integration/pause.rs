@@ -0,0 +1,114 @@
+use anchor_lending_example::{protocol::PauseFlag, user::UserError};
+use solana_sdk::signature::Signer;
+
+use crate::fixtures::{custom_error, TestBank, TestContext, TestUser, ONE};
+
+/// Borrower with 100 collateral tokens deposited and 60 liability tokens borrowed
+struct PauseSetup {
+    test: TestContext,
+    collateral: TestBank,
+    liability: TestBank,
+    user: TestUser,
+    collateral_account: anchor_lang::prelude::Pubkey,
+    liability_account: anchor_lang::prelude::Pubkey,
+}
+
+impl PauseSetup {
+    async fn new() -> Self {
+        let mut test = TestContext::new().await;
+        let (collateral, liability) = test.initialize_market(1_000 * ONE).await;
+        let user = test.create_user(0).await;
+        let wallet = user.wallet.pubkey();
+        let collateral_account = test.fund(&collateral, &wallet, 200 * ONE).await;
+        let liability_account = test.fund(&liability, &wallet, 0).await;
+        test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+            .await
+            .unwrap();
+        test.withdraw(&user, &liability, &liability_account, 60 * ONE)
+            .await
+            .unwrap();
+
+        Self {
+            test,
+            collateral,
+            liability,
+            user,
+            collateral_account,
+            liability_account,
+        }
+    }
+
+    /// Deposits fail with `error` while a partial repayment and a repay all succeed
+    async fn assert_only_repayments_accepted(&mut self, error: UserError) {
+        let test = &mut self.test;
+        let deposit_error = test
+            .deposit(
+                &self.user,
+                &self.collateral,
+                &self.collateral_account,
+                100 * ONE,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(custom_error(deposit_error), Some(error.into()));
+
+        test.deposit(
+            &self.user,
+            &self.liability,
+            &self.liability_account,
+            20 * ONE,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            test.fetch_user_balance(&self.user, &self.liability).await,
+            -((40 * ONE) as i64)
+        );
+        test.repay_all(&self.user, &self.liability, &self.liability_account)
+            .await
+            .unwrap();
+        assert_eq!(
+            test.fetch_user_balance(&self.user, &self.liability).await,
+            0
+        );
+        assert_eq!(
+            test.fetch_user_balance(&self.user, &self.collateral).await,
+            (100 * ONE) as i64
+        );
+    }
+}
+
+/// Test: Repay While Protocol Deposits Are Paused
+/// Flow:
+/// 1. Deposit 100 collateral tokens and borrow 60 liability tokens
+/// 2. Pause deposits protocol-wide
+/// 3. Deposit collateral, then repay 20 tokens and the rest with the all flag
+/// Expected: The deposit fails with protocol paused while both repayments succeed
+#[tokio::test]
+async fn repay_while_protocol_deposits_paused() {
+    let mut setup = PauseSetup::new().await;
+    setup
+        .test
+        .set_protocol_pause(PauseFlag::Deposit as u8)
+        .await;
+
+    setup
+        .assert_only_repayments_accepted(UserError::ProtocolPaused)
+        .await;
+}
+
+/// Test: Repay While Pool Deposits Are Paused
+/// Flow:
+/// 1. Deposit 100 collateral tokens and borrow 60 liability tokens
+/// 2. Pause deposits in the pool
+/// 3. Deposit collateral, then repay 20 tokens and the rest with the all flag
+/// Expected: The deposit fails with pool paused while both repayments succeed
+#[tokio::test]
+async fn repay_while_pool_deposits_paused() {
+    let mut setup = PauseSetup::new().await;
+    setup.test.set_pool_pause(PauseFlag::Deposit as u8).await;
+
+    setup
+        .assert_only_repayments_accepted(UserError::PoolPaused)
+        .await;
+}
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::AccountMeta;
+use anchor_lending_example::{
+    protocol::{BankConfigParams, BankError, PoolStatus},
+    user::UserError,
+};
+use solana_sdk::signature::Signer;
+
+use crate::fixtures::{custom_error, TestContext, BANK_CONFIG, ONE};
+
+/// Test: Value A Position Against Another Pool's Bank
+/// Flow:
+/// 1. Deposit 100 collateral tokens into bank 0 of pool 0
+/// 2. List bank 0 of pool 1 at 1,000 USD
+/// 3. Borrow 500 liability tokens, passing pool 1's bank 0 ahead of pool 0's
+/// Expected: The foreign bank is ignored, the collateral is valued at 1 USD and the
+/// borrow fails with insufficient collateral
+#[tokio::test]
+async fn foreign_pool_bank_is_ignored_in_health_checks() {
+    let mut test = TestContext::new().await;
+    let (collateral, liability) = test.initialize_market(1_000 * ONE).await;
+    let user = test.create_user(0).await;
+    let wallet = user.wallet.pubkey();
+    let collateral_account = test.fund(&collateral, &wallet, 100 * ONE).await;
+    let liability_account = test.fund(&liability, &wallet, 0).await;
+    test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap();
+
+    test.create_pool().await;
+    let foreign = test.initialize_bank(&anchor_spl::token::ID, 1_000).await;
+    assert_eq!(foreign.bank_id, collateral.bank_id);
+    test.pool_id = 0;
+
+    let user_state = test.fetch_user(&user).await;
+    let bank_state = test.fetch_bank(&liability).await;
+    let mut ix = lending_client::withdraw(
+        &wallet,
+        &user.account,
+        &user_state,
+        &bank_state,
+        &liability_account,
+        &liability.token_program,
+        500 * ONE,
+        false,
+    );
+    let position = ix
+        .accounts
+        .iter()
+        .position(|meta| meta.pubkey == collateral.key)
+        .unwrap();
+    ix.accounts
+        .insert(position, AccountMeta::new_readonly(foreign.key, false));
+
+    let error = test.process(&[ix], &[&user.wallet]).await.unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        Some(UserError::InsufficientCollateral.into())
+    );
+    assert_eq!(test.fetch_user_balance(&user, &liability).await, 0);
+    assert_eq!(test.fetch_token_amount(&liability_account).await, 0);
+}
+
+/// Test: List A Bank With Invalid Weights
+/// Flow:
+/// 1. List banks with an asset weight above 100, a liability weight below 100,
+///    and maintenance weights stricter than initial weights
+/// Expected: Every listing fails with invalid weights
+#[tokio::test]
+async fn initialize_bank_with_invalid_weights_fails() {
+    let mut test = TestContext::new().await;
+    let mint = test.create_mint(&anchor_spl::token::ID).await;
+
+    let configs = [
+        BankConfigParams {
+            maintenance_asset_weight: 255,
+            ..BANK_CONFIG
+        },
+        BankConfigParams {
+            initial_liability_weight: 99,
+            maintenance_liability_weight: 99,
+            ..BANK_CONFIG
+        },
+        BankConfigParams {
+            initial_asset_weight: 95,
+            ..BANK_CONFIG
+        },
+        BankConfigParams {
+            maintenance_liability_weight: 130,
+            ..BANK_CONFIG
+        },
+    ];
+    for params in configs {
+        let error = test
+            .send_initialize_bank(&mint, &anchor_spl::token::ID, params)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(custom_error(error), Some(BankError::InvalidWeights.into()));
+    }
+}
+
+/// Test: Repay In An Inactive Pool
+/// Flow:
+/// 1. Deposit 100 collateral tokens and borrow 60 liability tokens
+/// 2. Deactivate the pool
+/// 3. Deposit collateral, then repay 20 tokens and the rest with the all flag
+/// Expected: The deposit fails with pool inactive while both repayments succeed
+#[tokio::test]
+async fn repay_in_inactive_pool() {
+    let mut test = TestContext::new().await;
+    let (collateral, liability) = test.initialize_market(1_000 * ONE).await;
+    let user = test.create_user(0).await;
+    let wallet = user.wallet.pubkey();
+    let collateral_account = test.fund(&collateral, &wallet, 200 * ONE).await;
+    let liability_account = test.fund(&liability, &wallet, 0).await;
+    test.deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap();
+    test.withdraw(&user, &liability, &liability_account, 60 * ONE)
+        .await
+        .unwrap();
+
+    test.set_pool_status(PoolStatus::Inactive).await;
+
+    let error = test
+        .deposit(&user, &collateral, &collateral_account, 100 * ONE)
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(error), Some(UserError::PoolInactive.into()));
+
+    test.deposit(&user, &liability, &liability_account, 20 * ONE)
+        .await
+        .unwrap();
+    assert_eq!(
+        test.fetch_user_balance(&user, &liability).await,
+        -((40 * ONE) as i64)
+    );
+    test.repay_all(&user, &liability, &liability_account)
+        .await
+        .unwrap();
+    assert_eq!(test.fetch_user_balance(&user, &liability).await, 0);
+    assert_eq!(test.fetch_bank(&liability).await.total_liabilities, 0);
+}
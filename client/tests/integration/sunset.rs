@@ -0,0 +1,262 @@
+use anchor_lending_example::protocol::BankError;
+use lending_client::LiquidatorAccounts;
+use solana_program_test::BanksClientError;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::fixtures::{custom_error, TestBank, TestContext, TestUser, ONE};
+
+/// Seconds between sunsetting the liability bank and its settlement deadline
+const SUNSET_PERIOD: i64 = 3_600;
+
+/// Lender with 1,000 liability tokens deposited, a borrower with 200 collateral tokens
+/// and 60 borrowed liability tokens, and the liability bank in sunset
+struct SunsetSetup {
+    test: TestContext,
+    collateral: TestBank,
+    liability: TestBank,
+    lender: TestUser,
+    lender_account: anchor_lang::prelude::Pubkey,
+    borrower: TestUser,
+    borrower_account: anchor_lang::prelude::Pubkey,
+    keeper: Keypair,
+    keeper_collateral_account: anchor_lang::prelude::Pubkey,
+    keeper_liability_account: anchor_lang::prelude::Pubkey,
+}
+
+impl SunsetSetup {
+    async fn new() -> Self {
+        let mut test = TestContext::new().await;
+        let collateral = test.initialize_bank(&anchor_spl::token::ID, 1).await;
+        let liability = test.initialize_bank(&anchor_spl::token_2022::ID, 1).await;
+
+        let lender = test.create_user(1).await;
+        let lender_account = test
+            .fund(&liability, &lender.wallet.pubkey(), 1_000 * ONE)
+            .await;
+        test.deposit(&lender, &liability, &lender_account, 1_000 * ONE)
+            .await
+            .unwrap();
+
+        let borrower = test.create_user(0).await;
+        let wallet = borrower.wallet.pubkey();
+        let collateral_account = test.fund(&collateral, &wallet, 200 * ONE).await;
+        let borrower_account = test.fund(&liability, &wallet, 0).await;
+        test.deposit(&borrower, &collateral, &collateral_account, 200 * ONE)
+            .await
+            .unwrap();
+        test.withdraw(&borrower, &liability, &borrower_account, 60 * ONE)
+            .await
+            .unwrap();
+
+        let keeper = Keypair::new();
+        let keeper_collateral_account = test.fund(&collateral, &keeper.pubkey(), 0).await;
+        let keeper_liability_account = test.fund(&liability, &keeper.pubkey(), 100 * ONE).await;
+
+        test.sunset_bank(&liability, SUNSET_PERIOD).await;
+
+        Self {
+            test,
+            collateral,
+            liability,
+            lender,
+            lender_account,
+            borrower,
+            borrower_account,
+            keeper,
+            keeper_collateral_account,
+            keeper_liability_account,
+        }
+    }
+
+    /// Settles a user account's position in the sunset bank, paying collateral to `token_account`
+    async fn force_settle(
+        &mut self,
+        user_account: &anchor_lang::prelude::Pubkey,
+        token_account: &anchor_lang::prelude::Pubkey,
+    ) -> std::result::Result<(), BanksClientError> {
+        let bank = self.test.fetch_bank(&self.liability).await;
+        let ix = lending_client::force_settle(
+            &self.keeper.pubkey(),
+            user_account,
+            &bank,
+            token_account,
+            &self.liability.token_program,
+        );
+        let keeper = self.keeper.insecure_clone();
+        self.test.process(&[ix], &[&keeper]).await
+    }
+
+    /// Settles the borrower's liability against their collateral
+    async fn force_settle_liability(&mut self) -> std::result::Result<(), BanksClientError> {
+        let collateral_bank = self.test.fetch_bank(&self.collateral).await;
+        let liability_bank = self.test.fetch_bank(&self.liability).await;
+        let ix = lending_client::force_settle_liability(
+            &LiquidatorAccounts {
+                liquidator: self.keeper.pubkey(),
+                collateral_token_account: self.keeper_collateral_account,
+                liability_token_account: self.keeper_liability_account,
+                collateral_token_program: self.collateral.token_program,
+                liability_token_program: self.liability.token_program,
+            },
+            &self.borrower.account,
+            &collateral_bank,
+            &liability_bank,
+        );
+        let keeper = self.keeper.insecure_clone();
+        self.test.process(&[ix], &[&keeper]).await
+    }
+}
+
+/// Test: Force Settle With Outstanding Liabilities
+/// Flow:
+/// 1. Attempt to settle the liability before the deadline
+/// 2. Pass the deadline and attempt to pay out the lender
+/// 3. Attempt to write off the borrower's liability while they hold collateral
+/// Expected: Every attempt fails and no position changes
+#[tokio::test]
+async fn force_settle_with_outstanding_liabilities_fails() {
+    let mut setup = SunsetSetup::new().await;
+
+    let error = setup.force_settle_liability().await.unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        Some(BankError::SunsetDeadlineNotReached.into())
+    );
+
+    setup.test.advance_clock(SUNSET_PERIOD).await;
+    let (lender, lender_account) = (setup.lender.account, setup.lender_account);
+    let error = setup
+        .force_settle(&lender, &lender_account)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        Some(BankError::SunsetLiabilitiesOutstanding.into())
+    );
+
+    let (borrower, borrower_account) = (setup.borrower.account, setup.borrower_account);
+    let error = setup
+        .force_settle(&borrower, &borrower_account)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        Some(BankError::SettleAgainstCollateral.into())
+    );
+
+    let test = &mut setup.test;
+    assert_eq!(
+        test.fetch_user_balance(&setup.lender, &setup.liability)
+            .await,
+        (1_000 * ONE) as i64
+    );
+    assert_eq!(
+        test.fetch_user_balance(&setup.borrower, &setup.liability)
+            .await,
+        -((60 * ONE) as i64)
+    );
+}
+
+/// Test: Settle Liability Against Collateral
+/// Flow:
+/// 1. Pass the deadline and settle the borrower's 60 token liability
+/// 2. Pay out the lender
+/// Expected: The keeper repays 60 tokens for 60 USD of collateral at a 5% discount,
+/// both borrower slots are cleared and the lender is paid in full
+#[tokio::test]
+async fn force_settle_liability_against_collateral() {
+    let mut setup = SunsetSetup::new().await;
+    setup.test.advance_clock(SUNSET_PERIOD).await;
+
+    setup.force_settle_liability().await.unwrap();
+
+    let seized = 60 * ONE * 100 / 95;
+    let test = &mut setup.test;
+    assert_eq!(
+        test.fetch_user_balance(&setup.borrower, &setup.liability)
+            .await,
+        0
+    );
+    assert_eq!(
+        test.fetch_user_balance(&setup.borrower, &setup.collateral)
+            .await,
+        (200 * ONE - seized) as i64
+    );
+    assert_eq!(
+        test.fetch_token_amount(&setup.keeper_collateral_account)
+            .await,
+        seized
+    );
+    assert_eq!(
+        test.fetch_token_amount(&setup.keeper_liability_account)
+            .await,
+        40 * ONE
+    );
+    assert_eq!(test.fetch_bank(&setup.liability).await.total_liabilities, 0);
+
+    let (lender, lender_account) = (setup.lender.account, setup.lender_account);
+    setup.force_settle(&lender, &lender_account).await.unwrap();
+
+    let test = &mut setup.test;
+    assert_eq!(
+        test.fetch_token_amount(&setup.lender_account).await,
+        1_000 * ONE
+    );
+    let lender_state = test.fetch_user(&setup.lender).await;
+    assert_eq!(
+        lender_state.find_balance_by_bank_id(setup.liability.bank_id),
+        0
+    );
+    assert_eq!(lender_state.position_count(), 0);
+}
+
+/// Test: Settle Bad Debt
+/// Flow:
+/// 1. Raise the liability price to 10 USD, 600 USD of debt against 200 USD of collateral
+/// 2. Pass the deadline and settle the liability against all the collateral
+/// 3. Write off the remaining liability and pay out the lender
+/// Expected: 19 tokens are repaid for the 200 collateral tokens, the other 41 are
+/// written off and the lender receives what the vault holds
+#[tokio::test]
+async fn force_settle_bad_debt() {
+    let mut setup = SunsetSetup::new().await;
+    let liability = setup.liability;
+    setup.test.set_price(&liability, 10).await;
+    setup.test.advance_clock(SUNSET_PERIOD).await;
+
+    setup.force_settle_liability().await.unwrap();
+
+    let test = &mut setup.test;
+    assert_eq!(
+        test.fetch_user_balance(&setup.borrower, &setup.collateral)
+            .await,
+        0
+    );
+    assert_eq!(
+        test.fetch_user_balance(&setup.borrower, &setup.liability)
+            .await,
+        -((41 * ONE) as i64)
+    );
+    assert_eq!(
+        test.fetch_token_amount(&setup.keeper_collateral_account)
+            .await,
+        200 * ONE
+    );
+
+    let (borrower, borrower_account) = (setup.borrower.account, setup.borrower_account);
+    setup
+        .force_settle(&borrower, &borrower_account)
+        .await
+        .unwrap();
+    let (lender, lender_account) = (setup.lender.account, setup.lender_account);
+    setup.force_settle(&lender, &lender_account).await.unwrap();
+
+    let test = &mut setup.test;
+    let borrower_state = test.fetch_user(&setup.borrower).await;
+    assert_eq!(borrower_state.position_count(), 0);
+    assert_eq!(test.fetch_bank(&setup.liability).await.total_liabilities, 0);
+    assert_eq!(
+        test.fetch_token_amount(&setup.lender_account).await,
+        959 * ONE
+    );
+}
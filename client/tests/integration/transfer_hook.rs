@@ -0,0 +1,94 @@
+use anchor_lending_example::protocol::{BankConfigParams, MintExtension};
+use solana_sdk::signature::Signer;
+use spl_transfer_hook_interface::error::TransferHookError;
+
+use crate::fixtures::{custom_error, TestBank, TestContext, TestUser, BANK_CONFIG, ONE};
+
+/// Lists a mint running the test transfer hook and funds a user with 100 tokens
+async fn setup_hook_bank(
+    test: &mut TestContext,
+) -> (TestBank, TestUser, anchor_lang::prelude::Pubkey) {
+    let mint = test.create_transfer_hook_mint().await;
+    let bank = test
+        .list_mint(
+            &mint,
+            &anchor_spl::token_2022::ID,
+            BankConfigParams {
+                allowed_extensions: MintExtension::TransferHook as u16,
+                ..BANK_CONFIG
+            },
+            1,
+        )
+        .await;
+    let user = test.create_user(0).await;
+    let token_account = test.fund(&bank, &user.wallet.pubkey(), 100 * ONE).await;
+    (bank, user, token_account)
+}
+
+/// Test: Deposit And Withdraw A Transfer Hook Mint
+/// Flow:
+/// 1. List a Token-2022 mint whose transfers run the test hook program
+/// 2. Deposit 100 tokens, signed by the user
+/// 3. Withdraw 40 tokens, signed by the bank PDA
+/// Expected: Both transfers forward the hook's extra account metas, the hook runs
+/// once per transfer and balances move as without a hook
+#[tokio::test]
+async fn deposit_and_withdraw_transfer_hook_mint() {
+    let mut test = TestContext::new().await;
+    let (bank, user, token_account) = setup_hook_bank(&mut test).await;
+    let hook_transfers = test.fetch_hook_transfers(&bank.mint).await;
+
+    test.deposit(&user, &bank, &token_account, 100 * ONE)
+        .await
+        .unwrap();
+    assert_eq!(
+        test.fetch_hook_transfers(&bank.mint).await,
+        hook_transfers + 1
+    );
+
+    test.withdraw(&user, &bank, &token_account, 40 * ONE)
+        .await
+        .unwrap();
+    assert_eq!(
+        test.fetch_hook_transfers(&bank.mint).await,
+        hook_transfers + 2
+    );
+
+    assert_eq!(
+        test.fetch_user_balance(&user, &bank).await,
+        (60 * ONE) as i64
+    );
+    assert_eq!(test.fetch_token_amount(&token_account).await, 40 * ONE);
+    let vault = lending_client::find_bank_token_account_address(&bank.key).0;
+    assert_eq!(test.fetch_token_amount(&vault).await, 60 * ONE);
+}
+
+/// Test: Deposit A Transfer Hook Mint Without Its Extra Accounts
+/// Flow:
+/// 1. List a Token-2022 mint whose transfers run the test hook program
+/// 2. Deposit without appending the hook accounts
+/// Expected: The transfer fails to find the hook's validation account and nothing is credited
+#[tokio::test]
+async fn deposit_transfer_hook_mint_without_extra_accounts_fails() {
+    let mut test = TestContext::new().await;
+    let (bank, user, token_account) = setup_hook_bank(&mut test).await;
+
+    let bank_state = test.fetch_bank(&bank).await;
+    let ix = lending_client::deposit(
+        &user.wallet.pubkey(),
+        &user.account,
+        &bank_state,
+        &token_account,
+        &bank.token_program,
+        100 * ONE,
+        false,
+    );
+    let error = test.process(&[ix], &[&user.wallet]).await.unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        Some(TransferHookError::IncorrectAccount as u32)
+    );
+
+    assert_eq!(test.fetch_user_balance(&user, &bank).await, 0);
+    assert_eq!(test.fetch_token_amount(&token_account).await, 100 * ONE);
+}
@@ -0,0 +1,14 @@
+//! Runs random instruction sequences and checks the protocol invariants
+//!
+//! Run from the `fuzz` directory with `cargo hfuzz run fuzz_0` and replay a
+//! crash with `cargo hfuzz run-debug fuzz_0 <crash file>`.
+
+use honggfuzz::fuzz;
+
+fn main() {
+    loop {
+        fuzz!(|data: &[u8]| {
+            lending_fuzz::run(data);
+        });
+    }
+}
@@ -0,0 +1,394 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        entrypoint::ProgramResult, instruction::Instruction, program_pack::Pack, system_instruction,
+    },
+    system_program, InstructionData,
+};
+use anchor_lending_example::{
+    accounts, instruction,
+    protocol::{BankConfigParams, BankStatus, PoolConfigParams, UpdatePriceParams},
+};
+use anchor_spl::token_2022::spl_token_2022;
+use lending_client::{
+    decode_bank, decode_user, find_admin_address, find_bank_address,
+    find_bank_token_account_address, find_pool_address, find_user_address, Bank, User, PROGRAM_ID,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+/// Pool every sequence runs in
+pub const POOL_ID: u8 = 0;
+
+/// Number of users created before a sequence starts
+pub const USER_COUNT: usize = 3;
+
+/// Largest number of banks a sequence can list
+pub const MAX_BANKS: usize = 6;
+
+/// Decimals of every fuzzed mint
+pub const DECIMALS: u8 = 6;
+
+/// Tokens minted to every user for each listed bank, in native units
+pub const USER_TOKEN_SUPPLY: u64 = 1_000_000_000_000;
+
+/// Weights of every fuzzed bank
+const BANK_CONFIG: BankConfigParams = BankConfigParams {
+    initial_asset_weight: 80,
+    maintenance_asset_weight: 90,
+    initial_liability_weight: 120,
+    maintenance_liability_weight: 110,
+    allowed_extensions: 0,
+    flash_loan_fee: 0,
+};
+
+/// Entrypoint wrapper giving the program account infos with a single lifetime
+fn process_instruction<'a, 'b, 'c, 'd>(
+    program_id: &'a Pubkey,
+    accounts: &'b [AccountInfo<'c>],
+    data: &'d [u8],
+) -> ProgramResult {
+    // SAFETY: the account infos outlive the program invocation. Leaking a copy as
+    // the integration tests do would grow without bound over a fuzzing session.
+    let accounts: &'c [AccountInfo<'c>] = unsafe { std::mem::transmute(accounts) };
+    anchor_lending_example::entry(program_id, accounts, data)
+}
+
+/// A listed bank and the mint it holds
+#[derive(Clone, Copy, Debug)]
+pub struct FuzzBank {
+    pub bank_id: u8,
+    pub key: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+}
+
+/// A user with a wallet, a lending account and a funded token account per bank
+pub struct FuzzUser {
+    pub wallet: Keypair,
+    pub account: Pubkey,
+    pub token_accounts: Vec<Pubkey>,
+}
+
+/// In-process bank with an initialized pool, its listed banks and users
+pub struct FuzzContext {
+    pub context: ProgramTestContext,
+    pub banks: Vec<FuzzBank>,
+    pub users: Vec<FuzzUser>,
+}
+
+impl FuzzContext {
+    /// Starts a bank with the admin, pool and users initialized and no banks listed
+    pub async fn new() -> Self {
+        let mut program_test = ProgramTest::new(
+            "anchor_lending_example",
+            PROGRAM_ID,
+            processor!(process_instruction),
+        );
+        program_test.prefer_bpf(false);
+
+        let mut fuzz = Self {
+            context: program_test.start_with_context().await,
+            banks: vec![],
+            users: vec![],
+        };
+        fuzz.initialize_pool().await;
+        for user_id in 0..USER_COUNT as u16 {
+            fuzz.initialize_user(user_id).await;
+        }
+        fuzz
+    }
+
+    /// Gets the payer, which is the admin, pool authority and mint authority
+    fn payer(&self) -> Keypair {
+        self.context.payer.insecure_clone()
+    }
+
+    /// Sends instructions in one transaction signed by the payer and any extra signers
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> std::result::Result<(), BanksClientError> {
+        let payer = self.payer();
+        let blockhash = self.context.get_new_latest_blockhash().await?;
+
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    /// Initializes the admin and the pool with the payer as authority
+    async fn initialize_pool(&mut self) {
+        let authority = self.context.payer.pubkey();
+        let initialize_ix = build_instruction(
+            accounts::Initialize {
+                admin: find_admin_address().0,
+                authority,
+                system_program: system_program::ID,
+            },
+            instruction::Initialize {},
+        );
+        let pool_ix = build_instruction(
+            accounts::InitializePool {
+                admin: find_admin_address().0,
+                pool: find_pool_address(POOL_ID).0,
+                authority,
+                pool_authority: authority,
+                system_program: system_program::ID,
+            },
+            instruction::InitializePool {
+                params: PoolConfigParams {
+                    name: [0; 32],
+                    close_factor: 50,
+                    max_liquidation_bonus: 5,
+                    max_positions: 16,
+                },
+            },
+        );
+        self.process(&[initialize_ix, pool_ix], &[])
+            .await
+            .expect("failed to initialize pool");
+    }
+
+    /// Creates a funded wallet and its lending account
+    async fn initialize_user(&mut self, user_id: u16) {
+        let wallet = Keypair::new();
+        let fund_ix = system_instruction::transfer(
+            &self.context.payer.pubkey(),
+            &wallet.pubkey(),
+            1_000_000_000,
+        );
+        let init_ix = lending_client::initialize_user(&wallet.pubkey(), POOL_ID, user_id);
+        self.process(&[fund_ix, init_ix], &[&wallet])
+            .await
+            .expect("failed to initialize user");
+
+        self.users.push(FuzzUser {
+            account: find_user_address(POOL_ID, user_id, &wallet.pubkey()).0,
+            wallet,
+            token_accounts: vec![],
+        });
+    }
+
+    /// Lists a new mint as an active bank priced at `price` USD and gives every
+    /// user a funded token account for it
+    /// Returns an error when the program rejects the listing
+    pub async fn initialize_bank(
+        &mut self,
+        token_program: Pubkey,
+        price: u64,
+    ) -> std::result::Result<(), BanksClientError> {
+        let mint = self.create_mint(&token_program).await;
+        let authority = self.context.payer.pubkey();
+        let pool = find_pool_address(POOL_ID).0;
+        let bank_id = self.banks.len() as u8;
+        let bank_key = find_bank_address(POOL_ID, bank_id).0;
+
+        let initialize_ix = build_instruction(
+            accounts::InitializeBank {
+                admin: find_admin_address().0,
+                pool,
+                bank: bank_key,
+                mint,
+                token_account: find_bank_token_account_address(&bank_key).0,
+                authority,
+                token_program,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeBank {
+                pool_id: POOL_ID,
+                params: BANK_CONFIG,
+            },
+        );
+        let activate_ix = build_instruction(
+            accounts::UpdateBankStatus {
+                admin: find_admin_address().0,
+                pool,
+                authority,
+                bank: bank_key,
+            },
+            instruction::UpdateBankStatus {
+                new_status: BankStatus::Active as u8,
+            },
+        );
+        let price_ix = update_price_instruction(&authority, &bank_key, price);
+        self.process(&[initialize_ix, activate_ix, price_ix], &[])
+            .await?;
+
+        let bank = FuzzBank {
+            bank_id,
+            key: bank_key,
+            mint,
+            token_program,
+        };
+        for index in 0..self.users.len() {
+            let owner = self.users[index].wallet.pubkey();
+            let token_account = self.create_token_account(&bank, &owner).await;
+            self.users[index].token_accounts.push(token_account);
+        }
+        self.banks.push(bank);
+        Ok(())
+    }
+
+    /// Sets a bank's price in whole USD with a zero exponent
+    pub async fn update_price(
+        &mut self,
+        bank: &FuzzBank,
+        price: u64,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = update_price_instruction(&self.context.payer.pubkey(), &bank.key, price);
+        self.process(&[ix], &[]).await
+    }
+
+    /// Creates a mint without extensions owned by the given token program
+    async fn create_mint(&mut self, token_program: &Pubkey) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.context.payer.pubkey();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let space = spl_token_2022::state::Mint::LEN;
+
+        let instructions = [
+            system_instruction::create_account(
+                &payer,
+                &mint.pubkey(),
+                rent.minimum_balance(space),
+                space as u64,
+                token_program,
+            ),
+            spl_token_2022::instruction::initialize_mint2(
+                token_program,
+                &mint.pubkey(),
+                &payer,
+                None,
+                DECIMALS,
+            )
+            .unwrap(),
+        ];
+        self.process(&instructions, &[&mint])
+            .await
+            .expect("failed to create mint");
+        mint.pubkey()
+    }
+
+    /// Creates a token account holding `USER_TOKEN_SUPPLY` of a bank's mint
+    async fn create_token_account(&mut self, bank: &FuzzBank, owner: &Pubkey) -> Pubkey {
+        let account = Keypair::new();
+        let payer = self.context.payer.pubkey();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let space = spl_token_2022::state::Account::LEN;
+
+        let instructions = [
+            system_instruction::create_account(
+                &payer,
+                &account.pubkey(),
+                rent.minimum_balance(space),
+                space as u64,
+                &bank.token_program,
+            ),
+            spl_token_2022::instruction::initialize_account3(
+                &bank.token_program,
+                &account.pubkey(),
+                &bank.mint,
+                owner,
+            )
+            .unwrap(),
+            spl_token_2022::instruction::mint_to(
+                &bank.token_program,
+                &bank.mint,
+                &account.pubkey(),
+                &payer,
+                &[],
+                USER_TOKEN_SUPPLY,
+            )
+            .unwrap(),
+        ];
+        self.process(&instructions, &[&account])
+            .await
+            .expect("failed to create token account");
+        account.pubkey()
+    }
+
+    /// Reads raw account data
+    async fn fetch_data(&mut self, address: &Pubkey) -> Vec<u8> {
+        self.context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .expect("account not found")
+            .data
+    }
+
+    /// Reads a bank account
+    pub async fn fetch_bank(&mut self, bank: &FuzzBank) -> Bank {
+        let data = self.fetch_data(&bank.key).await;
+        decode_bank(&data).unwrap()
+    }
+
+    /// Reads every listed bank account
+    pub async fn fetch_banks(&mut self) -> Vec<Bank> {
+        let mut banks = Vec::with_capacity(self.banks.len());
+        for bank in self.banks.clone() {
+            banks.push(self.fetch_bank(&bank).await);
+        }
+        banks
+    }
+
+    /// Reads the lending account of a user
+    pub async fn fetch_user(&mut self, user_index: usize) -> User {
+        let address = self.users[user_index].account;
+        let data = self.fetch_data(&address).await;
+        decode_user(&data).unwrap()
+    }
+
+    /// Reads the amount held by a token account
+    pub async fn fetch_token_amount(&mut self, address: &Pubkey) -> u64 {
+        let data = self.fetch_data(address).await;
+        spl_token_2022::state::Account::unpack_from_slice(&data)
+            .unwrap()
+            .amount
+    }
+}
+
+/// Builds an instruction setting a bank's price in whole USD with a zero exponent
+fn update_price_instruction(authority: &Pubkey, bank: &Pubkey, price: u64) -> Instruction {
+    build_instruction(
+        accounts::UpdatePrice {
+            admin: find_admin_address().0,
+            bank: *bank,
+            authority: *authority,
+        },
+        instruction::UpdatePrice {
+            params: UpdatePriceParams {
+                ema_price: price,
+                ema_conf: 0,
+                price,
+                conf: 0,
+                exponent: 0,
+                publish_time: 0,
+            },
+        },
+    )
+}
+
+/// Builds a lending program instruction without remaining accounts
+fn build_instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
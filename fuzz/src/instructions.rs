@@ -0,0 +1,213 @@
+use arbitrary::Arbitrary;
+use lending_client::LiquidatorAccounts;
+use solana_sdk::signature::Signer;
+
+use crate::context::{FuzzContext, MAX_BANKS, USER_COUNT};
+use crate::invariants::{check_vault_balances, check_withdrawal_health};
+
+/// A program instruction with fuzzed arguments
+/// Users and banks are chosen by index modulo the number that exist, and prices
+/// are whole USD between 1 and 100
+#[derive(Arbitrary, Clone, Copy, Debug)]
+pub enum FuzzInstruction {
+    InitializeBank {
+        token_2022: bool,
+        price: u8,
+    },
+    UpdatePrice {
+        bank: u8,
+        price: u8,
+    },
+    Deposit {
+        user: u8,
+        bank: u8,
+        amount: u32,
+        all: bool,
+    },
+    Withdraw {
+        user: u8,
+        bank: u8,
+        amount: u32,
+        all: bool,
+    },
+    Liquidate {
+        liquidator: u8,
+        user: u8,
+        collateral_bank: u8,
+        liability_bank: u8,
+        amount: u32,
+    },
+}
+
+/// Maps a fuzzed byte to a price between 1 and 100 USD
+fn price(value: u8) -> u64 {
+    1 + value as u64 % 100
+}
+
+impl FuzzInstruction {
+    /// Sends the instruction and checks the invariants when it succeeds
+    /// Instructions needing a bank are skipped until one is listed
+    pub async fn execute(self, context: &mut FuzzContext) {
+        if context.banks.is_empty() && !matches!(self, Self::InitializeBank { .. }) {
+            return;
+        }
+
+        let bank_count = context.banks.len();
+        let succeeded = match self {
+            Self::InitializeBank {
+                token_2022,
+                price: value,
+            } => {
+                if bank_count >= MAX_BANKS {
+                    return;
+                }
+                let token_program = if token_2022 {
+                    anchor_spl::token_2022::ID
+                } else {
+                    anchor_spl::token::ID
+                };
+                context
+                    .initialize_bank(token_program, price(value))
+                    .await
+                    .is_ok()
+            }
+            Self::UpdatePrice { bank, price: value } => {
+                let bank = context.banks[bank as usize % bank_count];
+                context.update_price(&bank, price(value)).await.is_ok()
+            }
+            Self::Deposit {
+                user,
+                bank,
+                amount,
+                all,
+            } => {
+                let user = user as usize % USER_COUNT;
+                let bank = bank as usize % bank_count;
+                deposit(context, user, bank, amount as u64, all).await
+            }
+            Self::Withdraw {
+                user,
+                bank,
+                amount,
+                all,
+            } => {
+                let user = user as usize % USER_COUNT;
+                let bank = bank as usize % bank_count;
+                let succeeded = withdraw(context, user, bank, amount as u64, all).await;
+                if succeeded {
+                    check_withdrawal_health(context, user).await;
+                }
+                succeeded
+            }
+            Self::Liquidate {
+                liquidator,
+                user,
+                collateral_bank,
+                liability_bank,
+                amount,
+            } => {
+                liquidate(
+                    context,
+                    liquidator as usize % USER_COUNT,
+                    user as usize % USER_COUNT,
+                    collateral_bank as usize % bank_count,
+                    liability_bank as usize % bank_count,
+                    amount as u64,
+                )
+                .await
+            }
+        };
+
+        if succeeded {
+            check_vault_balances(context).await;
+        }
+    }
+}
+
+/// Deposits from a user's token account, or repays the whole liability with `all`
+/// Returns whether the program accepted it
+async fn deposit(
+    context: &mut FuzzContext,
+    user: usize,
+    bank: usize,
+    amount: u64,
+    all: bool,
+) -> bool {
+    let fuzz_bank = context.banks[bank];
+    let bank_state = context.fetch_bank(&fuzz_bank).await;
+    let fuzz_user = &context.users[user];
+    let ix = lending_client::deposit(
+        &fuzz_user.wallet.pubkey(),
+        &fuzz_user.account,
+        &bank_state,
+        &fuzz_user.token_accounts[bank],
+        &fuzz_bank.token_program,
+        amount,
+        all,
+    );
+    let wallet = fuzz_user.wallet.insecure_clone();
+    context.process(&[ix], &[&wallet]).await.is_ok()
+}
+
+/// Withdraws to a user's token account, borrowing past the deposit, or withdraws
+/// the whole collateral with `all`
+/// Returns whether the program accepted it
+async fn withdraw(
+    context: &mut FuzzContext,
+    user: usize,
+    bank: usize,
+    amount: u64,
+    all: bool,
+) -> bool {
+    let fuzz_bank = context.banks[bank];
+    let bank_state = context.fetch_bank(&fuzz_bank).await;
+    let user_state = context.fetch_user(user).await;
+    let fuzz_user = &context.users[user];
+    let ix = lending_client::withdraw(
+        &fuzz_user.wallet.pubkey(),
+        &fuzz_user.account,
+        &user_state,
+        &bank_state,
+        &fuzz_user.token_accounts[bank],
+        &fuzz_bank.token_program,
+        amount,
+        all,
+    );
+    let wallet = fuzz_user.wallet.insecure_clone();
+    context.process(&[ix], &[&wallet]).await.is_ok()
+}
+
+/// Repays part of a user's liability with another user's tokens in exchange for
+/// collateral, returns whether the program accepted it
+async fn liquidate(
+    context: &mut FuzzContext,
+    liquidator: usize,
+    user: usize,
+    collateral_bank: usize,
+    liability_bank: usize,
+    amount: u64,
+) -> bool {
+    let collateral = context.banks[collateral_bank];
+    let liability = context.banks[liability_bank];
+    let collateral_state = context.fetch_bank(&collateral).await;
+    let liability_state = context.fetch_bank(&liability).await;
+    let user_state = context.fetch_user(user).await;
+
+    let liquidator = &context.users[liquidator];
+    let ix = lending_client::liquidate(
+        &LiquidatorAccounts {
+            liquidator: liquidator.wallet.pubkey(),
+            collateral_token_account: liquidator.token_accounts[collateral_bank],
+            liability_token_account: liquidator.token_accounts[liability_bank],
+            collateral_token_program: collateral.token_program,
+            liability_token_program: liability.token_program,
+        },
+        &context.users[user].account,
+        &user_state,
+        &collateral_state,
+        &liability_state,
+        amount,
+    );
+    let wallet = liquidator.wallet.insecure_clone();
+    context.process(&[ix], &[&wallet]).await.is_ok()
+}
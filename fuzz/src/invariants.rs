@@ -0,0 +1,82 @@
+use anchor_lending_example::user::state::BalanceType;
+use lending_client::{find_bank_token_account_address, Bank, User};
+
+use crate::context::FuzzContext;
+
+/// Every bank vault holds at least the collateral of all users minus their liabilities
+pub async fn check_vault_balances(context: &mut FuzzContext) {
+    let mut users = Vec::with_capacity(context.users.len());
+    for user_index in 0..context.users.len() {
+        users.push(context.fetch_user(user_index).await);
+    }
+
+    for bank in context.banks.clone() {
+        let vault = find_bank_token_account_address(&bank.key).0;
+        let vault_amount = context.fetch_token_amount(&vault).await as i128;
+        let net_deposits: i128 = users
+            .iter()
+            .map(|user| user.find_balance_by_bank_id(bank.bank_id) as i128)
+            .sum();
+
+        assert!(
+            vault_amount >= net_deposits,
+            "bank {} vault holds {} against net user deposits of {}",
+            bank.bank_id,
+            vault_amount,
+            net_deposits
+        );
+    }
+}
+
+/// Values a token amount in micro USD straight from the bank's price feed, rounding down
+fn token_value(bank: &Bank, amount: u64) -> u128 {
+    let price = &bank.price_message;
+    let scale = 6 + price.exponent - bank.decimals as i32;
+    let value = amount as u128 * price.price as u128;
+    if scale >= 0 {
+        value * 10u128.pow(scale as u32)
+    } else {
+        value / 10u128.pow(scale.unsigned_abs())
+    }
+}
+
+/// Sums the initial weighted value of a user's collateral and liabilities in micro USD
+/// Banks are never sunset by the fuzzer, so the configured asset weights apply as is
+fn initial_weighted_position(banks: &[Bank], user: &User) -> (u128, u128) {
+    let mut collateral = 0;
+    let mut liability = 0;
+    for balance in user
+        .token_balances
+        .iter()
+        .filter(|token| token.balance != 0)
+    {
+        let bank = banks
+            .iter()
+            .find(|bank| bank.bank_id == balance.bank_id)
+            .expect("position in an unlisted bank");
+        let value = token_value(bank, balance.balance);
+        if balance.balance_type == BalanceType::Collateral as u8 {
+            collateral += value * bank.initial_asset_weight as u128 / 100;
+        } else {
+            liability += value * bank.initial_liability_weight as u128 / 100;
+        }
+    }
+    (collateral, liability)
+}
+
+/// A user that just withdrew has initial weighted liabilities no larger than
+/// initial weighted collateral
+/// Valued independently of the program's health code from raw balances, prices and weights
+pub async fn check_withdrawal_health(context: &mut FuzzContext, user_index: usize) {
+    let banks = context.fetch_banks().await;
+    let user = context.fetch_user(user_index).await;
+
+    let (collateral, liability) = initial_weighted_position(&banks, &user);
+    assert!(
+        liability <= collateral,
+        "user {} withdrew to {} weighted liability against {} weighted collateral",
+        user_index,
+        liability,
+        collateral
+    );
+}
@@ -0,0 +1,56 @@
+//! Fuzz harness for the lending program
+//!
+//! Random sequences of `initialize_bank`, `update_price`, `deposit`, `withdraw`
+//! and `liquidate` are decoded from fuzzer input and sent to the program in an
+//! in-process bank. Failed transactions are expected and ignored, while every
+//! successful one is followed by the checks in [`invariants`]; a violated
+//! invariant panics so the fuzzer records the input.
+
+pub mod context;
+pub mod instructions;
+pub mod invariants;
+
+use arbitrary::{Arbitrary, Unstructured};
+
+pub use context::FuzzContext;
+pub use instructions::FuzzInstruction;
+
+/// Longest instruction sequence decoded from one input
+pub const MAX_SEQUENCE_LENGTH: usize = 48;
+
+/// Decodes an instruction sequence from raw fuzzer input
+pub fn decode(data: &[u8]) -> Vec<FuzzInstruction> {
+    let mut unstructured = Unstructured::new(data);
+    let mut sequence = vec![];
+    while sequence.len() < MAX_SEQUENCE_LENGTH && !unstructured.is_empty() {
+        match FuzzInstruction::arbitrary(&mut unstructured) {
+            Ok(instruction) => sequence.push(instruction),
+            Err(_) => break,
+        }
+    }
+    sequence
+}
+
+/// Runs the instruction sequence decoded from raw fuzzer input
+/// Panics when an invariant is violated
+pub fn run(data: &[u8]) {
+    let sequence = decode(data);
+    if sequence.is_empty() {
+        return;
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to build runtime");
+    runtime.block_on(run_sequence(&sequence));
+}
+
+/// Runs an instruction sequence against a fresh pool
+/// Panics when an invariant is violated
+pub async fn run_sequence(sequence: &[FuzzInstruction]) {
+    let mut context = FuzzContext::new().await;
+    for instruction in sequence {
+        instruction.execute(&mut context).await;
+    }
+}
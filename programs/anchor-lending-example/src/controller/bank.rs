@@ -1,7 +1,7 @@
 use anchor_lang::{prelude::*, Discriminator};
 use arrayref::array_ref;
 
-use crate::controller::oracle::calculate_token_value;
+use crate::controller::health::{calculate_weighted_sum, HealthType};
 use crate::protocol::{Bank, BANK_SPACE};
 use crate::user::state::{BalanceType, TokenBalance};
 
@@ -21,19 +21,64 @@ pub enum BankError {
     WeightOverflow,
 }
 
-pub struct BankInterface<'a> {
-    pub banks: Vec<(u8, AccountLoader<'a, Bank>)>,
+/// Source of the banks a position is valued against
+pub trait BankProvider {
+    /// Checks whether a bank is available
+    fn contains(&self, bank_id: u8) -> bool;
+
+    /// Copies every available bank for the pure valuation functions in `controller::health`
+    fn snapshot(&self) -> Result<Vec<Bank>>;
 }
 
-impl<'a> BankInterface<'a> {
+/// Banks loaded from bank accounts passed to an instruction
+#[derive(Default)]
+pub struct AccountBanks<'a>(pub Vec<(u8, AccountLoader<'a, Bank>)>);
+
+impl BankProvider for AccountBanks<'_> {
+    fn contains(&self, bank_id: u8) -> bool {
+        self.0.iter().any(|(id, _)| *id == bank_id)
+    }
+
+    fn snapshot(&self) -> Result<Vec<Bank>> {
+        self.0
+            .iter()
+            .map(|(_, loader)| Ok(*loader.load()?))
+            .collect()
+    }
+}
+
+/// Banks held in memory, for off-chain valuation and tests
+#[derive(Default, Clone)]
+pub struct BankSnapshots(pub Vec<Bank>);
+
+impl BankProvider for BankSnapshots {
+    fn contains(&self, bank_id: u8) -> bool {
+        self.0.iter().any(|bank| bank.bank_id == bank_id)
+    }
+
+    fn snapshot(&self) -> Result<Vec<Bank>> {
+        Ok(self.0.clone())
+    }
+}
+
+pub struct BankInterface<P: BankProvider> {
+    pub banks: P,
+    /// Unix timestamp used for time dependent weights
+    pub timestamp: i64,
+}
+
+impl<'a> BankInterface<AccountBanks<'a>> {
     /// Load bank interface with required and optional bank accounts
-    /// @param remaining_account - List of required bank accounts
+    /// Banks of other pools are skipped, bank IDs are only unique within a pool
+    /// @param pool_id - Pool of the position being valued
     /// @param bank_ids - List of required bank IDs
+    /// @param remaining_account - List of required bank accounts
     pub fn load<'c: 'a>(
+        pool_id: u8,
         bank_ids: Vec<u8>,
         remaining_account: &'c [AccountInfo<'a>],
-    ) -> Result<BankInterface<'a>> {
-        let mut bank_interface = BankInterface { banks: vec![] };
+    ) -> Result<BankInterface<AccountBanks<'a>>> {
+        let mut banks = AccountBanks::default();
 
         // Process all accounts
         for account_info in remaining_account.iter() {
@@ -54,67 +99,58 @@ impl<'a> BankInterface<'a> {
 
             let account_loader: AccountLoader<'_, Bank> = AccountLoader::try_from(account_info)?;
             let bank = account_loader.load()?;
-            if bank_interface
-                .banks
-                .iter()
-                .any(|(id, _loader)| *id == bank.bank_id)
-            {
+            if bank.pool_id != pool_id || banks.contains(bank.bank_id) {
                 continue;
             }
-            bank_interface
-                .banks
-                .push((bank.bank_id, account_loader.clone()));
+            banks.0.push((bank.bank_id, account_loader.clone()));
         }
 
+        BankInterface::new(banks, bank_ids, Clock::get()?.unix_timestamp)
+    }
+}
+
+impl BankInterface<BankSnapshots> {
+    /// Build a bank interface from in-memory banks valued at the given timestamp
+    pub fn from_snapshots(banks: Vec<Bank>, timestamp: i64) -> Self {
+        BankInterface {
+            banks: BankSnapshots(banks),
+            timestamp,
+        }
+    }
+}
+
+impl<P: BankProvider> BankInterface<P> {
+    /// Build a bank interface from any bank source
+    /// Fails if a required bank is not available
+    pub fn new(banks: P, bank_ids: Vec<u8>, timestamp: i64) -> Result<Self> {
         // Validate that all required banks are present
         for required_id in bank_ids.iter() {
-            if !bank_interface.banks.iter().any(|(id, _)| id == required_id) {
+            if !banks.contains(*required_id) {
                 return Err(error!(BankError::MissingRequiredBanks));
             }
         }
 
-        Ok(bank_interface)
+        Ok(BankInterface { banks, timestamp })
+    }
+
+    /// Copies every available bank for the pure valuation functions in `controller::health`
+    pub fn snapshot(&self) -> Result<Vec<Bank>> {
+        self.banks.snapshot()
     }
 
     fn calculate_sum(
         &self,
         token_balances: &[TokenBalance; 16],
         required_type: BalanceType,
-        weight_selector: Option<fn(&Bank) -> u8>,
-        overflow_error: BankError,
+        health_type: HealthType,
     ) -> Result<u128> {
-        let mut total: u128 = 0;
-        for balance in token_balances.iter() {
-            if balance.balance_type != required_type as u8
-                || balance.balance == 0
-                || balance.bank_id == 0
-            {
-                continue;
-            }
-            let bank = self
-                .banks
-                .iter()
-                .find(|(id, _)| *id == balance.bank_id)
-                .ok_or(error!(BankError::BankNotFound))?
-                .1
-                .load()?;
-            let usd_value =
-                calculate_token_value(balance.balance, bank.decimals, &bank.price_message)?;
-            let final_value = if let Some(weight_fn) = weight_selector {
-                let w = weight_fn(&bank) as u128;
-                (usd_value as u128)
-                    .checked_mul(w)
-                    .ok_or(error!(BankError::WeightOverflow))?
-                    .checked_div(100)
-                    .ok_or(error!(BankError::WeightOverflow))?
-            } else {
-                usd_value as u128
-            };
-            total = total
-                .checked_add(final_value)
-                .ok_or(error!(overflow_error))?;
-        }
-        Ok(total)
+        calculate_weighted_sum(
+            &self.snapshot()?,
+            token_balances,
+            required_type,
+            health_type,
+            self.timestamp,
+        )
     }
 
     /// Calculates the total collateral value (sum of collateral balances)
@@ -136,8 +172,7 @@ impl<'a> BankInterface<'a> {
         self.calculate_sum(
             token_balances,
             BalanceType::Collateral,
-            None,
-            BankError::CollateralOverflow,
+            HealthType::Unweighted,
         )
     }
 
@@ -157,8 +192,7 @@ impl<'a> BankInterface<'a> {
         self.calculate_sum(
             token_balances,
             BalanceType::Liability,
-            None,
-            BankError::LiabilityOverflow,
+            HealthType::Unweighted,
         )
     }
 
@@ -197,6 +231,7 @@ impl<'a> BankInterface<'a> {
     }
 
     /// Calculates the total collateral value with Initial Asset Weight applied
+    /// Asset weights of sunset banks decay to zero by the sunset deadline
     /// Returns the weighted sum in USD with 6 decimal places precision
     ///
     /// # Arguments
@@ -213,12 +248,7 @@ impl<'a> BankInterface<'a> {
         &self,
         token_balances: &[TokenBalance; 16],
     ) -> Result<u128> {
-        self.calculate_sum(
-            token_balances,
-            BalanceType::Collateral,
-            Some(|b: &Bank| b.initial_asset_weight),
-            BankError::CollateralOverflow,
-        )
+        self.calculate_sum(token_balances, BalanceType::Collateral, HealthType::Initial)
     }
 
     /// Calculates the total liability value with Initial Liability Weight applied
@@ -238,12 +268,7 @@ impl<'a> BankInterface<'a> {
         &self,
         token_balances: &[TokenBalance; 16],
     ) -> Result<u128> {
-        self.calculate_sum(
-            token_balances,
-            BalanceType::Liability,
-            Some(|b: &Bank| b.initial_liability_weight),
-            BankError::LiabilityOverflow,
-        )
+        self.calculate_sum(token_balances, BalanceType::Liability, HealthType::Initial)
     }
 
     /// Calculates both weighted collateral and liability values
@@ -268,6 +293,7 @@ impl<'a> BankInterface<'a> {
     }
 
     /// Calculates the total collateral value with Maintenance Asset Weight applied
+    /// Asset weights of sunset banks decay to zero by the sunset deadline
     /// Returns the weighted sum in USD with 6 decimal places precision
     ///
     /// # Arguments
@@ -287,8 +313,7 @@ impl<'a> BankInterface<'a> {
         self.calculate_sum(
             token_balances,
             BalanceType::Collateral,
-            Some(|b: &Bank| b.maintenance_asset_weight),
-            BankError::CollateralOverflow,
+            HealthType::Maintenance,
         )
     }
 
@@ -312,8 +337,7 @@ impl<'a> BankInterface<'a> {
         self.calculate_sum(
             token_balances,
             BalanceType::Liability,
-            Some(|b: &Bank| b.maintenance_liability_weight),
-            BankError::LiabilityOverflow,
+            HealthType::Maintenance,
         )
     }
 
@@ -339,3 +363,137 @@ impl<'a> BankInterface<'a> {
         Ok((maintenance_collateral, maintenance_liability))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::test_utils::bank;
+    use crate::protocol::BankStatus;
+
+    const COLLATERAL_BANK: u8 = 1;
+    const LIABILITY_BANK: u8 = 2;
+
+    fn balance(bank_id: u8, tokens: u64, balance_type: BalanceType) -> TokenBalance {
+        TokenBalance {
+            balance: tokens * 1_000_000,
+            bank_id,
+            balance_type: balance_type as u8,
+            padding: [0; 6],
+        }
+    }
+
+    /// 100 tokens at $2 as collateral against 50 tokens at $1 borrowed
+    fn position() -> [TokenBalance; 16] {
+        let mut balances = [TokenBalance::default(); 16];
+        balances[0] = balance(COLLATERAL_BANK, 100, BalanceType::Collateral);
+        balances[1] = balance(LIABILITY_BANK, 50, BalanceType::Liability);
+        balances
+    }
+
+    fn interface() -> BankInterface<BankSnapshots> {
+        BankInterface::from_snapshots(vec![bank(COLLATERAL_BANK, 2), bank(LIABILITY_BANK, 1)], 0)
+    }
+
+    #[test]
+    fn values_position_without_weights() {
+        let interface = interface();
+        assert_eq!(
+            interface.calculate_total_value(position()).unwrap(),
+            150_000_000
+        );
+    }
+
+    #[test]
+    fn applies_initial_weights() {
+        let interface = interface();
+        assert_eq!(
+            interface
+                .calculate_total_weighted_values(position())
+                .unwrap(),
+            (160_000_000, 60_000_000)
+        );
+    }
+
+    #[test]
+    fn applies_maintenance_weights() {
+        let interface = interface();
+        assert_eq!(
+            interface
+                .calculate_total_maintenance_values(position())
+                .unwrap(),
+            (180_000_000, 55_000_000)
+        );
+    }
+
+    #[test]
+    fn decays_sunset_asset_weight() {
+        let mut sunset = bank(COLLATERAL_BANK, 2);
+        sunset.status = BankStatus::Sunset as u8;
+        sunset.status_updated_at = 0;
+        sunset.sunset_deadline = 100;
+
+        // Halfway to the deadline the 80% initial weight has decayed to 40%
+        let interface = BankInterface::from_snapshots(vec![sunset, bank(LIABILITY_BANK, 1)], 50);
+        assert_eq!(
+            interface
+                .calculate_total_weighted_values(position())
+                .unwrap(),
+            (80_000_000, 60_000_000)
+        );
+    }
+
+    #[test]
+    fn skips_empty_balances() {
+        let mut balances = position();
+        balances[1].balance = 0;
+        assert_eq!(
+            interface()
+                .calculate_total_weighted_values(balances)
+                .unwrap(),
+            (160_000_000, 0)
+        );
+    }
+
+    #[test]
+    fn values_bank_zero_positions() {
+        let mut balances = position();
+        balances[1] = balance(0, 50, BalanceType::Liability);
+        let interface =
+            BankInterface::from_snapshots(vec![bank(COLLATERAL_BANK, 2), bank(0, 1)], 0);
+        assert_eq!(
+            interface.calculate_total_weighted_values(balances).unwrap(),
+            (160_000_000, 60_000_000)
+        );
+    }
+
+    #[test]
+    fn rejects_missing_required_bank() {
+        let banks = BankSnapshots(vec![bank(COLLATERAL_BANK, 2)]);
+        let result = BankInterface::new(banks, vec![COLLATERAL_BANK, LIABILITY_BANK], 0);
+        assert_eq!(
+            result.err().unwrap(),
+            BankError::MissingRequiredBanks.into()
+        );
+    }
+
+    #[test]
+    fn rejects_balance_without_bank() {
+        let interface = BankInterface::from_snapshots(vec![bank(COLLATERAL_BANK, 2)], 0);
+        assert_eq!(
+            interface
+                .calculate_total_weighted_values(position())
+                .unwrap_err(),
+            BankError::BankNotFound.into()
+        );
+    }
+
+    #[test]
+    fn rejects_negative_net_value() {
+        let mut balances = position();
+        balances[1] = balance(LIABILITY_BANK, 500, BalanceType::Liability);
+        assert_eq!(
+            interface().calculate_total_value(balances).unwrap_err(),
+            BankError::NetValueOverflow.into()
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    state::Mint as MintState,
+};
+
+use crate::protocol::{BankError, MintExtension};
+
+/// Inspects the extensions of a mint at bank listing
+/// Returns the restricted extensions found as `MintExtension` bit flags
+///
+/// # Arguments
+///
+/// * `mint` - The mint account, owned by either the Token or Token-2022 program
+///
+/// # Returns
+///
+/// * `Result<u16>` - Restricted extension flags, or error if:
+///   - The mint data cannot be parsed
+///   - The mint carries an extension this program does not know about
+pub fn inspect_mint_extensions(mint: &AccountInfo) -> Result<u16> {
+    // Legacy token mints have no extensions
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+
+    // Unknown extension types fail to parse and are rejected
+    let extension_types = mint_state
+        .get_extension_types()
+        .map_err(|_| error!(BankError::UnsupportedMintExtension))?;
+
+    let mut flags: u16 = 0;
+    for extension_type in extension_types {
+        flags |= match extension_type {
+            ExtensionType::PermanentDelegate => MintExtension::PermanentDelegate as u16,
+            ExtensionType::NonTransferable => MintExtension::NonTransferable as u16,
+            ExtensionType::ConfidentialTransferMint
+            | ExtensionType::ConfidentialTransferFeeConfig => {
+                MintExtension::ConfidentialTransfer as u16
+            }
+            ExtensionType::TransferHook => MintExtension::TransferHook as u16,
+            ExtensionType::DefaultAccountState => MintExtension::DefaultAccountState as u16,
+            ExtensionType::MintCloseAuthority => MintExtension::MintCloseAuthority as u16,
+            // Fees are handled by the token interface, the rest only carry metadata
+            ExtensionType::TransferFeeConfig
+            | ExtensionType::InterestBearingConfig
+            | ExtensionType::MetadataPointer
+            | ExtensionType::TokenMetadata
+            | ExtensionType::GroupPointer
+            | ExtensionType::TokenGroup
+            | ExtensionType::GroupMemberPointer
+            | ExtensionType::TokenGroupMember => 0,
+            _ => return Err(error!(BankError::UnsupportedMintExtension)),
+        };
+    }
+
+    Ok(flags)
+}
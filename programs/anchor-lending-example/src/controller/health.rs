@@ -0,0 +1,576 @@
+use anchor_lang::prelude::*;
+
+use crate::controller::bank::BankError;
+use crate::controller::oracle::{calculate_token_amount, calculate_token_value};
+use crate::protocol::Bank;
+use crate::user::state::{BalanceType, TokenBalance};
+
+/// Weights applied when valuing a position
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HealthType {
+    /// Raw USD value without weights
+    Unweighted,
+    /// Initial weights, used to open or increase risk
+    Initial,
+    /// Maintenance weights, used to decide liquidation
+    Maintenance,
+}
+
+/// Gets the asset weight of a bank as a percentage
+/// Asset weights of sunset banks decay to zero by the sunset deadline
+pub fn asset_weight(bank: &Bank, health_type: HealthType, now: i64) -> u8 {
+    match health_type {
+        HealthType::Unweighted => 100,
+        HealthType::Initial => bank.asset_weight(bank.initial_asset_weight, now),
+        HealthType::Maintenance => bank.asset_weight(bank.maintenance_asset_weight, now),
+    }
+}
+
+/// Gets the liability weight of a bank as a percentage
+pub fn liability_weight(bank: &Bank, health_type: HealthType) -> u8 {
+    match health_type {
+        HealthType::Unweighted => 100,
+        HealthType::Initial => bank.initial_liability_weight,
+        HealthType::Maintenance => bank.maintenance_liability_weight,
+    }
+}
+
+/// Gets the weight applied to a balance of the given type
+fn balance_weight(bank: &Bank, balance_type: BalanceType, health_type: HealthType, now: i64) -> u8 {
+    match balance_type {
+        BalanceType::Collateral => asset_weight(bank, health_type, now),
+        BalanceType::Liability => liability_weight(bank, health_type),
+    }
+}
+
+/// Finds a bank by ID
+pub fn find_bank(banks: &[Bank], bank_id: u8) -> Result<&Bank> {
+    banks
+        .iter()
+        .find(|bank| bank.bank_id == bank_id)
+        .ok_or(error!(BankError::BankNotFound))
+}
+
+/// Calculates the weighted USD value of a token amount held in a bank
+/// Returns the value in USD with 6 decimal places precision
+pub fn calculate_weighted_value(
+    bank: &Bank,
+    amount: u64,
+    balance_type: BalanceType,
+    health_type: HealthType,
+    now: i64,
+) -> Result<u128> {
+    let usd_value = calculate_token_value(amount, bank.decimals, &bank.price_message)?;
+    if health_type == HealthType::Unweighted {
+        return Ok(usd_value as u128);
+    }
+
+    let weight = balance_weight(bank, balance_type, health_type, now) as u128;
+    (usd_value as u128)
+        .checked_mul(weight)
+        .ok_or(error!(BankError::WeightOverflow))?
+        .checked_div(100)
+        .ok_or(error!(BankError::WeightOverflow))
+}
+
+/// Calculates the weighted USD value of every balance of one type
+/// Returns the sum in USD with 6 decimal places precision
+pub fn calculate_weighted_sum(
+    banks: &[Bank],
+    token_balances: &[TokenBalance; 16],
+    balance_type: BalanceType,
+    health_type: HealthType,
+    now: i64,
+) -> Result<u128> {
+    let overflow_error = match balance_type {
+        BalanceType::Collateral => BankError::CollateralOverflow,
+        BalanceType::Liability => BankError::LiabilityOverflow,
+    };
+
+    let mut total: u128 = 0;
+    for balance in token_balances.iter() {
+        // Empty slots carry bank_id 0 too, only the balance tells them apart from bank 0
+        if balance.balance_type != balance_type as u8 || balance.balance == 0 {
+            continue;
+        }
+        let bank = find_bank(banks, balance.bank_id)?;
+        let value =
+            calculate_weighted_value(bank, balance.balance, balance_type, health_type, now)?;
+        total = total.checked_add(value).ok_or(error!(overflow_error))?;
+    }
+    Ok(total)
+}
+
+/// Calculates the weighted collateral and liability values of a position
+/// Returns (collateral, liability) in USD with 6 decimal places precision
+pub fn calculate_health(
+    banks: &[Bank],
+    token_balances: &[TokenBalance; 16],
+    health_type: HealthType,
+    now: i64,
+) -> Result<(u128, u128)> {
+    let collateral = calculate_weighted_sum(
+        banks,
+        token_balances,
+        BalanceType::Collateral,
+        health_type,
+        now,
+    )?;
+    let liability = calculate_weighted_sum(
+        banks,
+        token_balances,
+        BalanceType::Liability,
+        health_type,
+        now,
+    )?;
+    Ok((collateral, liability))
+}
+
+/// Gets the signed balance of a bank, liabilities are negative
+fn signed_balance(token_balances: &[TokenBalance; 16], bank_id: u8) -> i128 {
+    token_balances
+        .iter()
+        .find(|balance| balance.bank_id == bank_id && balance.balance != 0)
+        .map(|balance| {
+            if balance.balance_type == BalanceType::Liability as u8 {
+                -(balance.balance as i128)
+            } else {
+                balance.balance as i128
+            }
+        })
+        .unwrap_or(0)
+}
+
+/// Calculates the initial margin left after the current liabilities
+/// Returns None when liabilities already exceed the initial weighted collateral
+fn free_initial_margin(
+    banks: &[Bank],
+    token_balances: &[TokenBalance; 16],
+    now: i64,
+) -> Result<Option<u128>> {
+    let (collateral, liability) =
+        calculate_health(banks, token_balances, HealthType::Initial, now)?;
+    Ok(collateral.checked_sub(liability))
+}
+
+/// Calculates the largest amount of collateral that can be withdrawn from a bank
+/// without borrowing, keeping initial weighted collateral above liabilities
+/// Returns the amount in the bank token's native units
+pub fn max_withdraw_amount(
+    banks: &[Bank],
+    token_balances: &[TokenBalance; 16],
+    bank_id: u8,
+    now: i64,
+) -> Result<u64> {
+    let balance = signed_balance(token_balances, bank_id);
+    if balance <= 0 {
+        return Ok(0);
+    }
+    let balance = balance as u64;
+
+    // Nothing can be withdrawn from a position already below its initial requirement
+    let Some(free_margin) = free_initial_margin(banks, token_balances, now)? else {
+        return Ok(0);
+    };
+
+    let bank = find_bank(banks, bank_id)?;
+    let weight = asset_weight(bank, HealthType::Initial, now) as u128;
+    if weight == 0 {
+        // Collateral without weight does not back any liability
+        return Ok(balance);
+    }
+
+    let free_value = free_margin
+        .checked_mul(100)
+        .ok_or(error!(BankError::WeightOverflow))?
+        .checked_div(weight)
+        .ok_or(error!(BankError::WeightOverflow))?;
+    capped_token_amount(bank, free_value, balance)
+}
+
+/// Calculates the largest new liability that can be opened in a bank, up to `cap`
+/// Any collateral held in the bank is assumed withdrawn first, so a single withdrawal
+/// of `max_withdraw_amount + max_borrow_amount` is the largest the position allows
+/// Returns the amount in the bank token's native units
+pub fn max_borrow_amount(
+    banks: &[Bank],
+    token_balances: &[TokenBalance; 16],
+    bank_id: u8,
+    cap: u64,
+    now: i64,
+) -> Result<u64> {
+    let bank = find_bank(banks, bank_id)?;
+    let balance = signed_balance(token_balances, bank_id);
+
+    // Borrowing only starts once the collateral in this bank is fully withdrawn
+    let Some(mut free_margin) = free_initial_margin(banks, token_balances, now)? else {
+        return Ok(0);
+    };
+    if balance > 0 {
+        let collateral_value = calculate_weighted_value(
+            bank,
+            balance as u64,
+            BalanceType::Collateral,
+            HealthType::Initial,
+            now,
+        )?;
+        if collateral_value > free_margin {
+            return Ok(0);
+        }
+        free_margin -= collateral_value;
+    }
+
+    let weight = liability_weight(bank, HealthType::Initial) as u128;
+    if weight == 0 {
+        return Ok(0);
+    }
+    let free_value = free_margin
+        .checked_mul(100)
+        .ok_or(error!(BankError::WeightOverflow))?
+        .checked_div(weight)
+        .ok_or(error!(BankError::WeightOverflow))?;
+    capped_token_amount(bank, free_value, cap)
+}
+
+/// Converts a USD value to a token amount of at most `cap`
+/// A value covering the cap is not converted, since it may exceed what a u64 holds
+fn capped_token_amount(bank: &Bank, value: u128, cap: u64) -> Result<u64> {
+    let cap_value = calculate_token_value(cap, bank.decimals, &bank.price_message)?;
+    if value >= cap_value as u128 {
+        return Ok(cap);
+    }
+    calculate_token_amount(value, bank.decimals, &bank.price_message)
+}
+
+/// Calculates the price of a bank's token at which the position becomes liquidatable,
+/// holding every other price constant
+/// Returns the price in the bank's price feed units and exponent, or None when
+/// no price of this token a u64 feed can report makes the position liquidatable.
+/// A liability position that is liquidatable at any price returns Some(0)
+pub fn liquidation_price(
+    banks: &[Bank],
+    token_balances: &[TokenBalance; 16],
+    bank_id: u8,
+    now: i64,
+) -> Result<Option<u64>> {
+    let balance = signed_balance(token_balances, bank_id);
+    if balance == 0 {
+        return Ok(None);
+    }
+
+    let bank = find_bank(banks, bank_id)?;
+    let (collateral, liability) =
+        calculate_health(banks, token_balances, HealthType::Maintenance, now)?;
+    let balance_type = if balance > 0 {
+        BalanceType::Collateral
+    } else {
+        BalanceType::Liability
+    };
+    let position_value = calculate_weighted_value(
+        bank,
+        balance.unsigned_abs() as u64,
+        balance_type,
+        HealthType::Maintenance,
+        now,
+    )?;
+    if position_value == 0 {
+        return Ok(None);
+    }
+
+    // Value this position must reach for collateral to equal liabilities
+    let target_value = match balance_type {
+        BalanceType::Collateral => {
+            let other_collateral = collateral.saturating_sub(position_value);
+            if liability <= other_collateral {
+                return Ok(None);
+            }
+            liability - other_collateral
+        }
+        BalanceType::Liability => {
+            let other_liability = liability.saturating_sub(position_value);
+            if collateral <= other_liability {
+                return Ok(Some(0));
+            }
+            collateral - other_liability
+        }
+    };
+
+    // Value scales linearly with price
+    let price = (bank.price_message.price as u128)
+        .checked_mul(target_value)
+        .ok_or(error!(BankError::WeightOverflow))?
+        / position_value;
+    Ok(u64::try_from(price).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::test_utils::bank;
+    use crate::protocol::BankStatus;
+
+    const ONE: u64 = 1_000_000;
+    const COLLATERAL: u8 = 1;
+    const LIABILITY: u8 = 2;
+    const OTHER: u8 = 3;
+    /// Bank whose token is worth 10^-8 USD
+    const DUST: u8 = 4;
+    /// Bank whose collateral carries no weight
+    const UNWEIGHTED: u8 = 5;
+    /// Halfway through the sunset of `sunset_bank`
+    const NOW: i64 = 50;
+
+    /// Bank in sunset since 0 with a deadline at 100, halving asset weights at `NOW`
+    fn sunset_bank(bank_id: u8, price: u64) -> Bank {
+        Bank {
+            status: BankStatus::Sunset as u8,
+            status_updated_at: 0,
+            sunset_deadline: 100,
+            ..bank(bank_id, price)
+        }
+    }
+
+    /// Banks with the collateral bank priced at `collateral_price`, optionally in sunset
+    fn banks(collateral_price: u64, sunset: bool) -> Vec<Bank> {
+        let collateral = if sunset {
+            sunset_bank(COLLATERAL, collateral_price)
+        } else {
+            bank(COLLATERAL, collateral_price)
+        };
+        let mut dust = bank(DUST, 10_000_000_000);
+        dust.price_message.exponent = -18;
+        let mut unweighted = bank(UNWEIGHTED, 1);
+        unweighted.initial_asset_weight = 0;
+        unweighted.maintenance_asset_weight = 0;
+        unweighted.initial_liability_weight = 0;
+        vec![
+            collateral,
+            bank(LIABILITY, 1),
+            bank(OTHER, 1),
+            dust,
+            unweighted,
+        ]
+    }
+
+    /// Position from signed native amounts per bank, liabilities are negative
+    fn position(balances: &[(u8, i64)]) -> [TokenBalance; 16] {
+        let mut token_balances = [TokenBalance::default(); 16];
+        for (slot, (bank_id, balance)) in balances.iter().enumerate() {
+            token_balances[slot] = TokenBalance {
+                balance: balance.unsigned_abs(),
+                bank_id: *bank_id,
+                balance_type: if *balance < 0 {
+                    BalanceType::Liability as u8
+                } else {
+                    BalanceType::Collateral as u8
+                },
+                padding: [0; 6],
+            };
+        }
+        token_balances
+    }
+
+    fn tokens(amount: u64) -> i64 {
+        (amount * ONE) as i64
+    }
+
+    #[test]
+    fn max_withdraw_amounts() {
+        // (case, sunset, position, bank, amount)
+        let cases = [
+            (
+                "whole deposit without liabilities",
+                false,
+                vec![(COLLATERAL, tokens(100))],
+                COLLATERAL,
+                100 * ONE,
+            ),
+            (
+                "44 USD free margin at an 80% weight",
+                false,
+                vec![(COLLATERAL, tokens(100)), (LIABILITY, -tokens(30))],
+                COLLATERAL,
+                55 * ONE,
+            ),
+            (
+                "position below its initial requirement",
+                false,
+                vec![(COLLATERAL, tokens(100)), (LIABILITY, -tokens(70))],
+                COLLATERAL,
+                0,
+            ),
+            (
+                "no collateral in the bank",
+                false,
+                vec![(COLLATERAL, tokens(100)), (LIABILITY, -tokens(30))],
+                LIABILITY,
+                0,
+            ),
+            (
+                "28 USD free margin at a sunset weight decayed to 40%",
+                true,
+                vec![(COLLATERAL, tokens(100)), (LIABILITY, -tokens(10))],
+                COLLATERAL,
+                70 * ONE,
+            ),
+            (
+                "collateral without weight backs nothing",
+                false,
+                vec![
+                    (COLLATERAL, tokens(100)),
+                    (UNWEIGHTED, tokens(50)),
+                    (LIABILITY, -tokens(10)),
+                ],
+                UNWEIGHTED,
+                50 * ONE,
+            ),
+            (
+                "margin worth more dust tokens than a u64 holds",
+                false,
+                vec![(COLLATERAL, tokens(1_000_000)), (DUST, tokens(100))],
+                DUST,
+                100 * ONE,
+            ),
+        ];
+        for (case, sunset, balances, bank_id, amount) in cases {
+            assert_eq!(
+                max_withdraw_amount(&banks(1, sunset), &position(&balances), bank_id, NOW).unwrap(),
+                amount,
+                "{case}"
+            );
+        }
+    }
+
+    #[test]
+    fn max_borrow_amounts() {
+        // (case, sunset, position, bank, cap, amount)
+        let cases = [
+            (
+                "44 USD free margin at a 120% weight",
+                false,
+                vec![(COLLATERAL, tokens(100)), (LIABILITY, -tokens(30))],
+                LIABILITY,
+                u64::MAX,
+                44 * ONE * 100 / 120,
+            ),
+            (
+                "capped by the bank's liquidity",
+                false,
+                vec![(COLLATERAL, tokens(100)), (LIABILITY, -tokens(30))],
+                LIABILITY,
+                10 * ONE,
+                10 * ONE,
+            ),
+            (
+                "collateral in the bank worth more than the free margin",
+                false,
+                vec![(COLLATERAL, tokens(100)), (LIABILITY, -tokens(30))],
+                COLLATERAL,
+                u64::MAX,
+                0,
+            ),
+            (
+                "liabilities without weight",
+                false,
+                vec![(COLLATERAL, tokens(100))],
+                UNWEIGHTED,
+                u64::MAX,
+                0,
+            ),
+            (
+                "4 USD free margin once sunset halves the collateral weight",
+                true,
+                vec![(COLLATERAL, tokens(100)), (LIABILITY, -tokens(30))],
+                LIABILITY,
+                u64::MAX,
+                4 * ONE * 100 / 120,
+            ),
+            (
+                "margin worth more dust tokens than a u64 holds",
+                false,
+                vec![(COLLATERAL, tokens(10_000_000))],
+                DUST,
+                u64::MAX,
+                u64::MAX,
+            ),
+        ];
+        for (case, sunset, balances, bank_id, cap, amount) in cases {
+            assert_eq!(
+                max_borrow_amount(&banks(1, sunset), &position(&balances), bank_id, cap, NOW)
+                    .unwrap(),
+                amount,
+                "{case}"
+            );
+        }
+    }
+
+    #[test]
+    fn liquidation_prices() {
+        // 198 USD maintenance collateral against 99 USD maintenance liability
+        let borrowing = vec![(COLLATERAL, tokens(55)), (LIABILITY, -tokens(90))];
+        // (case, sunset, position, bank, price)
+        let cases = [
+            (
+                "collateral halving in value",
+                false,
+                borrowing.clone(),
+                COLLATERAL,
+                Some(2),
+            ),
+            (
+                "liability doubling in value",
+                false,
+                borrowing.clone(),
+                LIABILITY,
+                Some(2),
+            ),
+            (
+                "collateral weight halved by sunset",
+                true,
+                borrowing.clone(),
+                COLLATERAL,
+                Some(4),
+            ),
+            (
+                "no liabilities",
+                false,
+                vec![(COLLATERAL, tokens(55))],
+                COLLATERAL,
+                None,
+            ),
+            ("no position in the bank", false, borrowing, OTHER, None),
+            (
+                "collateral without weight",
+                false,
+                vec![(UNWEIGHTED, tokens(55)), (LIABILITY, -tokens(10))],
+                UNWEIGHTED,
+                None,
+            ),
+            (
+                "other liabilities alone exceed the collateral",
+                false,
+                vec![
+                    (COLLATERAL, tokens(55)),
+                    (LIABILITY, -tokens(10)),
+                    (OTHER, -tokens(200)),
+                ],
+                LIABILITY,
+                Some(0),
+            ),
+            (
+                "price beyond what a u64 feed reports",
+                false,
+                vec![(COLLATERAL, tokens(1_000_000_000)), (DUST, -tokens(100))],
+                DUST,
+                None,
+            ),
+        ];
+        for (case, sunset, balances, bank_id, price) in cases {
+            assert_eq!(
+                liquidation_price(&banks(4, sunset), &position(&balances), bank_id, NOW).unwrap(),
+                price,
+                "{case}"
+            );
+        }
+    }
+}
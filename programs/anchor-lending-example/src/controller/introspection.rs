@@ -0,0 +1,37 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::Instruction,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
+
+use crate::protocol::BankError;
+
+/// Gets the index of the current instruction in the transaction
+/// Fails unless the instruction is called directly by the transaction, since a CPI
+/// would let the calling program run code the introspection cannot see
+pub fn load_top_level_index(instructions: &AccountInfo) -> Result<usize> {
+    let current_index = load_current_index_checked(instructions)? as usize;
+    let current = load_instruction_at_checked(current_index, instructions)?;
+    require_keys_eq!(current.program_id, crate::ID, BankError::FlashLoanCpi);
+    Ok(current_index)
+}
+
+/// Checks whether an instruction of this program with the given discriminator follows
+/// the instruction at `current_index` and satisfies `matches`
+pub fn has_later_instruction(
+    instructions: &AccountInfo,
+    current_index: usize,
+    discriminator: &[u8],
+    matches: impl Fn(&Instruction) -> Result<bool>,
+) -> Result<bool> {
+    let mut index = current_index + 1;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions) {
+        index += 1;
+        if ix.program_id == crate::ID && ix.data.get(..8) == Some(discriminator) && matches(&ix)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
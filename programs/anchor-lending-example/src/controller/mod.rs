@@ -1,7 +1,18 @@
 pub mod bank;
+pub mod extension;
+pub mod health;
+pub mod introspection;
 pub mod oracle;
+pub mod risk;
 pub mod token;
 
+#[cfg(test)]
+mod test_utils;
+
 pub use bank::*;
+pub use extension::*;
+pub use health::*;
+pub use introspection::*;
 pub use oracle::*;
+pub use risk::*;
 pub use token::*;
@@ -11,66 +11,174 @@ use anchor_lang::prelude::*;
 ///
 /// # Returns
 ///
-/// * `Result<u64>` - The USD value scaled to 6 decimals, or error if math overflow occurs
+/// * `Result<u64>` - The USD value scaled to 6 decimals, rounding down, or error if the
+///   price is zero or math overflow occurs
 ///
 /// # Math
 ///
-/// 1. Converts amount and price to u128 for safe multiplication
-/// 2. Multiplies amount by price
-/// 3. Adjusts result based on price feed exponent to get 6 decimal places
+/// 1. Multiplies amount by price in u128
+/// 2. Scales by 10^(6 + exponent - token_decimals), the exponent keeping its sign so
+///    Pyth-style negative exponents divide
+/// 3. Divides at most once, at the end, so no precision is lost before scaling
 /// 4. Converts back to u64 after calculations
 pub fn calculate_token_value(
     amount: u64,
     token_decimals: u8,
     price_feed: &PriceFeedMessage,
 ) -> Result<u64> {
-    let amount_u128 = amount as u128;
-    let price_u128 = price_feed.price as u128;
-
-    // Check for overflow before multiplying the amount and price
-    if amount_u128 > u128::MAX / price_u128 {
-        return Err(ErrorCode::MathOverflow.into());
-    }
-
-    // Step 1: Adjust price by its exponent to get the actual price in UI scale
-    let actual_price_u128 = price_u128
-        .checked_mul(10u128.pow(price_feed.exponent.unsigned_abs()))
+    require_gt!(price_feed.price, 0, ErrorCode::InvalidPrice);
+    let base_value = (amount as u128)
+        .checked_mul(price_feed.price as u128)
         .ok_or(ErrorCode::MathOverflow)?;
 
-    // Step 2: Calculate the base value by multiplying the amount and actual price
-    let base_value = amount_u128
-        .checked_mul(actual_price_u128)
-        .ok_or(ErrorCode::MathOverflow)?;
+    let scale = value_scale(token_decimals, price_feed);
+    let scale_factor = scale_factor(scale)?;
+    let final_value_u128 = if scale >= 0 {
+        base_value
+            .checked_mul(scale_factor)
+            .ok_or(ErrorCode::MathOverflow)?
+    } else {
+        base_value / scale_factor
+    };
 
-    // Step 3: Adjust for token decimals by dividing by 10^token_decimals
-    let decimal_adj = base_value
-        .checked_div(10u128.pow(token_decimals as u32))
-        .ok_or(ErrorCode::MathOverflow)?;
+    u64::try_from(final_value_u128).map_err(|_| ErrorCode::MathOverflow.into())
+}
 
-    // Step 4: Adjust for the price exponent to get 6 decimals output
-    let exp_adj = 6 - price_feed.exponent;
-    let adj_factor = 10u128.pow(exp_adj.unsigned_abs());
+/// Calculates the token amount worth at most a USD value using a price feed
+/// Inverse of `calculate_token_value`, rounding down
+///
+/// # Arguments
+///
+/// * `value` - The USD value scaled to 6 decimals
+/// * `token_decimals` - Number of decimals used by the token
+/// * `price_feed` - Price feed message containing current price and exponent
+///
+/// # Returns
+///
+/// * `Result<u64>` - The token amount scaled by token decimals, or error if the price is
+///   zero, math overflow occurs or the amount does not fit in u64
+pub fn calculate_token_amount(
+    value: u128,
+    token_decimals: u8,
+    price_feed: &PriceFeedMessage,
+) -> Result<u64> {
+    require_gt!(price_feed.price, 0, ErrorCode::InvalidPrice);
+    let price_u128 = price_feed.price as u128;
 
-    let final_value_u128 = if exp_adj > 0 {
-        decimal_adj
-            .checked_mul(adj_factor)
-            .ok_or(ErrorCode::MathOverflow)?
+    let scale = value_scale(token_decimals, price_feed);
+    let scale_factor = scale_factor(scale)?;
+    let amount_u128 = if scale >= 0 {
+        value
+            / price_u128
+                .checked_mul(scale_factor)
+                .ok_or(ErrorCode::MathOverflow)?
     } else {
-        decimal_adj
-            .checked_div(adj_factor)
+        value
+            .checked_mul(scale_factor)
             .ok_or(ErrorCode::MathOverflow)?
+            / price_u128
     };
 
-    // Step 5: Verify final value fits within u64 before conversion
-    if final_value_u128 > u64::MAX as u128 {
-        return Err(ErrorCode::MathOverflow.into());
-    }
+    u64::try_from(amount_u128).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// Power of ten turning amount times price into USD with 6 decimals
+fn value_scale(token_decimals: u8, price_feed: &PriceFeedMessage) -> i64 {
+    6 + price_feed.exponent as i64 - token_decimals as i64
+}
 
-    Ok(final_value_u128 as u64)
+/// Ten to the magnitude of a scale, erroring when it does not fit in u128
+fn scale_factor(scale: i64) -> Result<u128> {
+    u32::try_from(scale.unsigned_abs())
+        .ok()
+        .and_then(|exponent| 10u128.checked_pow(exponent))
+        .ok_or(ErrorCode::MathOverflow.into())
 }
 
 #[error_code]
 pub enum ErrorCode {
     #[msg("Math operation overflow")]
     MathOverflow,
+    #[msg("Price must be positive")]
+    InvalidPrice,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Price feed quoting `price` scaled by 10^`exponent`
+    fn price_feed(price: u64, exponent: i32) -> PriceFeedMessage {
+        PriceFeedMessage {
+            price,
+            exponent,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn values_tokens_with_signed_exponents() {
+        // (amount, decimals, price, exponent, value)
+        let cases = [
+            // 2 SOL at a Pyth-style 150.00000000 USD
+            (2_000_000_000, 9, 150_00000000, -8, 300_000_000),
+            // 1 token at 3 * 10^2 USD
+            (1_000_000, 6, 3, 2, 300_000_000),
+            // Half a token at 1 USD keeps its sub-dollar value
+            (500_000, 6, 1, 0, 500_000),
+            // 1 token at 0.00000001 USD rounds down to zero
+            (1_000_000, 6, 1, -8, 0),
+            // A token with no decimals at 1.5 USD
+            (7, 0, 15, -1, 10_500_000),
+        ];
+        for (amount, decimals, price, exponent, value) in cases {
+            let feed = price_feed(price, exponent);
+            assert_eq!(
+                calculate_token_value(amount, decimals, &feed).unwrap(),
+                value,
+                "{amount} at {price}e{exponent}"
+            );
+        }
+    }
+
+    #[test]
+    fn converts_values_back_to_token_amounts() {
+        let feed = price_feed(150_00000000, -8);
+        assert_eq!(
+            calculate_token_amount(300_000_000, 9, &feed).unwrap(),
+            2_000_000_000
+        );
+        assert_eq!(
+            calculate_token_amount(300_000_000, 6, &price_feed(3, 2)).unwrap(),
+            1_000_000
+        );
+        // 1 micro USD of a 3 USD token rounds down
+        assert_eq!(calculate_token_amount(1, 6, &price_feed(3, 0)).unwrap(), 0);
+    }
+
+    #[test]
+    fn rejects_zero_prices_and_overflow() {
+        let zero = price_feed(0, -8);
+        assert_eq!(
+            calculate_token_value(1, 6, &zero).unwrap_err(),
+            ErrorCode::InvalidPrice.into()
+        );
+        assert_eq!(
+            calculate_token_amount(1, 6, &zero).unwrap_err(),
+            ErrorCode::InvalidPrice.into()
+        );
+        assert_eq!(
+            calculate_token_value(u64::MAX, 0, &price_feed(u64::MAX, 0)).unwrap_err(),
+            ErrorCode::MathOverflow.into()
+        );
+        assert_eq!(
+            calculate_token_value(1, 0, &price_feed(1, i32::MAX)).unwrap_err(),
+            ErrorCode::MathOverflow.into()
+        );
+        // More than u64::MAX tokens of a token priced at 10^-8 USD
+        assert_eq!(
+            calculate_token_amount(u64::MAX as u128, 9, &price_feed(1, -8)).unwrap_err(),
+            ErrorCode::MathOverflow.into()
+        );
+    }
 }
@@ -0,0 +1,346 @@
+use anchor_lang::prelude::*;
+
+use crate::controller::bank::{AccountBanks, BankInterface, BankProvider};
+use crate::controller::health::{
+    calculate_health, liquidation_price, max_borrow_amount, max_withdraw_amount, HealthType,
+};
+use crate::protocol::Bank;
+use crate::user::{state::TokenBalance, UserError};
+
+/// Highest maintenance health ratio a liquidation may leave, as a percentage
+/// Liquidators may only restore a position to just above its maintenance requirement
+pub const LIQUIDATION_TARGET_HEALTH: u128 = 105;
+
+/// Weighted collateral and liability values of a position
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Health {
+    /// Weighted collateral in USD with 6 decimal places precision
+    pub collateral: u128,
+    /// Weighted liabilities in USD with 6 decimal places precision
+    pub liability: u128,
+}
+
+impl Health {
+    /// Checks whether collateral covers liabilities
+    pub fn is_healthy(&self) -> bool {
+        self.collateral >= self.liability
+    }
+
+    /// Gets the collateral to liability ratio in basis points
+    /// Returns u64::MAX for a position without liabilities
+    pub fn ratio(&self) -> Result<u64> {
+        if self.liability == 0 {
+            return Ok(u64::MAX);
+        }
+        let ratio = self
+            .collateral
+            .checked_mul(10_000)
+            .ok_or(error!(UserError::MathOverflow))?
+            / self.liability;
+        Ok(u64::try_from(ratio).unwrap_or(u64::MAX))
+    }
+
+    /// Checks whether the collateral to liability ratio is above another position's
+    /// A position without liabilities has the highest ratio
+    fn ratio_exceeds(&self, other: &Health) -> Result<bool> {
+        let this = self
+            .collateral
+            .checked_mul(other.liability)
+            .ok_or(error!(UserError::MathOverflow))?;
+        let that = other
+            .collateral
+            .checked_mul(self.liability)
+            .ok_or(error!(UserError::MathOverflow))?;
+        Ok(this > that)
+    }
+
+    /// Checks whether the collateral to liability ratio is above a percentage
+    fn ratio_exceeds_percent(&self, percent: u128) -> Result<bool> {
+        let collateral = self
+            .collateral
+            .checked_mul(100)
+            .ok_or(error!(UserError::MathOverflow))?;
+        let liability = self
+            .liability
+            .checked_mul(percent)
+            .ok_or(error!(UserError::MathOverflow))?;
+        Ok(collateral > liability)
+    }
+}
+
+/// Enforces position health on every path that adds risk
+/// Withdrawals and borrows must keep a position healthy under both initial and
+/// maintenance weights, and liquidations must move an unhealthy position towards
+/// health without overshooting the liquidation target
+pub struct RiskEngine {
+    banks: Vec<Bank>,
+    timestamp: i64,
+}
+
+impl RiskEngine {
+    /// Builds a risk engine from the banks of a bank interface
+    pub fn new<P: BankProvider>(bank_interface: &BankInterface<P>) -> Result<Self> {
+        Ok(RiskEngine {
+            banks: bank_interface.snapshot()?,
+            timestamp: bank_interface.timestamp,
+        })
+    }
+
+    /// Builds a risk engine from the bank accounts of a pool passed to an instruction
+    /// Fails if a required bank is missing
+    pub fn load<'a, 'c: 'a>(
+        pool_id: u8,
+        bank_ids: Vec<u8>,
+        remaining_accounts: &'c [AccountInfo<'a>],
+    ) -> Result<Self> {
+        let bank_interface: BankInterface<AccountBanks<'a>> =
+            BankInterface::load(pool_id, bank_ids, remaining_accounts)?;
+        Self::new(&bank_interface)
+    }
+
+    /// Values a position with the given weights
+    pub fn health(
+        &self,
+        token_balances: &[TokenBalance; 16],
+        health_type: HealthType,
+    ) -> Result<Health> {
+        let (collateral, liability) =
+            calculate_health(&self.banks, token_balances, health_type, self.timestamp)?;
+        Ok(Health {
+            collateral,
+            liability,
+        })
+    }
+
+    /// Gets the largest amount of collateral withdrawable from a bank without borrowing
+    pub fn max_withdraw(&self, token_balances: &[TokenBalance; 16], bank_id: u8) -> Result<u64> {
+        max_withdraw_amount(&self.banks, token_balances, bank_id, self.timestamp)
+    }
+
+    /// Gets the largest liability a position can open in a bank under initial weights,
+    /// up to `liquidity`
+    /// Collateral held in the bank is assumed withdrawn first
+    pub fn max_borrow(
+        &self,
+        token_balances: &[TokenBalance; 16],
+        bank_id: u8,
+        liquidity: u64,
+    ) -> Result<u64> {
+        max_borrow_amount(
+            &self.banks,
+            token_balances,
+            bank_id,
+            liquidity,
+            self.timestamp,
+        )
+    }
+
+    /// Gets the price of a bank's token at which the position becomes liquidatable
+    /// Returns None when no price of this token makes the position liquidatable
+    pub fn liquidation_price(
+        &self,
+        token_balances: &[TokenBalance; 16],
+        bank_id: u8,
+    ) -> Result<Option<u64>> {
+        liquidation_price(&self.banks, token_balances, bank_id, self.timestamp)
+    }
+
+    /// Requires initial weighted collateral to cover initial weighted liabilities
+    pub fn check_initial_health(&self, token_balances: &[TokenBalance; 16]) -> Result<()> {
+        let health = self.health(token_balances, HealthType::Initial)?;
+        require_gte!(
+            health.collateral,
+            health.liability,
+            UserError::InsufficientCollateral
+        );
+        Ok(())
+    }
+
+    /// Requires maintenance weighted collateral to cover maintenance weighted liabilities
+    pub fn check_maintenance_health(&self, token_balances: &[TokenBalance; 16]) -> Result<()> {
+        let health = self.health(token_balances, HealthType::Maintenance)?;
+        require_gte!(
+            health.collateral,
+            health.liability,
+            UserError::InsufficientCollateral
+        );
+        Ok(())
+    }
+
+    /// Requires a liquidation to raise the maintenance health ratio of a position
+    /// while leaving it no healthier than `LIQUIDATION_TARGET_HEALTH`
+    /// A liquidation repaying the whole liability is always accepted, so no debt is
+    /// left behind that the target would forbid repaying
+    ///
+    /// # Arguments
+    /// * `before` - Maintenance health of the position before the liquidation
+    /// * `token_balances` - Balances of the position after the liquidation
+    pub fn check_liquidation_improves_health(
+        &self,
+        before: &Health,
+        token_balances: &[TokenBalance; 16],
+    ) -> Result<()> {
+        let after = self.health(token_balances, HealthType::Maintenance)?;
+        if after.liability == 0 {
+            return Ok(());
+        }
+        require!(
+            after.ratio_exceeds(before)?,
+            UserError::LiquidationHealthNotImproved
+        );
+        require!(
+            !after.ratio_exceeds_percent(LIQUIDATION_TARGET_HEALTH)?,
+            UserError::LiquidationOvershoot
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::test_utils::bank;
+    use crate::user::state::BalanceType;
+
+    const COLLATERAL_BANK: u8 = 1;
+    const LIABILITY_BANK: u8 = 2;
+
+    /// Collateral tokens at $1 against borrowed tokens
+    fn position(collateral: u64, liability: u64) -> [TokenBalance; 16] {
+        let mut balances = [TokenBalance::default(); 16];
+        balances[0] = TokenBalance {
+            balance: collateral * 1_000_000,
+            bank_id: COLLATERAL_BANK,
+            balance_type: BalanceType::Collateral as u8,
+            padding: [0; 6],
+        };
+        balances[1] = TokenBalance {
+            balance: liability * 1_000_000,
+            bank_id: LIABILITY_BANK,
+            balance_type: BalanceType::Liability as u8,
+            padding: [0; 6],
+        };
+        balances
+    }
+
+    fn risk_engine(liability_price: u64) -> RiskEngine {
+        let interface = BankInterface::from_snapshots(
+            vec![
+                bank(COLLATERAL_BANK, 1),
+                bank(LIABILITY_BANK, liability_price),
+            ],
+            0,
+        );
+        RiskEngine::new(&interface).unwrap()
+    }
+
+    #[test]
+    fn accepts_position_within_initial_margin() {
+        let engine = risk_engine(1);
+        // 80 USD initial collateral against 72 USD initial liability
+        engine.check_initial_health(&position(100, 60)).unwrap();
+        engine.check_maintenance_health(&position(100, 60)).unwrap();
+    }
+
+    #[test]
+    fn rejects_position_beyond_initial_margin() {
+        let engine = risk_engine(1);
+        // 80 USD initial collateral against 84 USD, maintenance still covers 77 USD
+        assert_eq!(
+            engine.check_initial_health(&position(100, 70)).unwrap_err(),
+            error!(UserError::InsufficientCollateral)
+        );
+        engine.check_maintenance_health(&position(100, 70)).unwrap();
+    }
+
+    #[test]
+    fn rejects_position_below_maintenance() {
+        let engine = risk_engine(1);
+        // 90 USD maintenance collateral against 99 USD
+        assert_eq!(
+            engine
+                .check_maintenance_health(&position(100, 90))
+                .unwrap_err(),
+            error!(UserError::InsufficientCollateral)
+        );
+    }
+
+    #[test]
+    fn accepts_liquidation_raising_health_ratio() {
+        let engine = risk_engine(3);
+        // 180 USD maintenance collateral against 198 USD, a 91% ratio
+        let before = engine
+            .health(&position(200, 60), HealthType::Maintenance)
+            .unwrap();
+        assert!(!before.is_healthy());
+
+        // 95.4 USD against 99 USD, a 96% ratio
+        engine
+            .check_liquidation_improves_health(&before, &position(106, 30))
+            .unwrap();
+    }
+
+    #[test]
+    fn rejects_liquidation_lowering_health_ratio() {
+        let engine = risk_engine(4);
+        // 180 USD maintenance collateral against 264 USD, a 68% ratio
+        let before = engine
+            .health(&position(200, 60), HealthType::Maintenance)
+            .unwrap();
+
+        // 66.6 USD against 132 USD, a 50% ratio
+        assert_eq!(
+            engine
+                .check_liquidation_improves_health(&before, &position(74, 30))
+                .unwrap_err(),
+            error!(UserError::LiquidationHealthNotImproved)
+        );
+    }
+
+    #[test]
+    fn rejects_liquidation_beyond_target() {
+        let engine = risk_engine(3);
+        let before = engine
+            .health(&position(200, 60), HealthType::Maintenance)
+            .unwrap();
+
+        // 135 USD against 66 USD, far above the 105% target
+        assert_eq!(
+            engine
+                .check_liquidation_improves_health(&before, &position(150, 20))
+                .unwrap_err(),
+            error!(UserError::LiquidationOvershoot)
+        );
+    }
+
+    #[test]
+    fn accepts_liquidation_repaying_whole_liability() {
+        let engine = risk_engine(3);
+        let before = engine
+            .health(&position(200, 60), HealthType::Maintenance)
+            .unwrap();
+
+        // Any collateral left without a liability is above the target
+        engine
+            .check_liquidation_improves_health(&before, &position(11, 0))
+            .unwrap();
+        engine
+            .check_liquidation_improves_health(&before, &position(0, 0))
+            .unwrap();
+    }
+
+    #[test]
+    fn reports_ratio_in_basis_points() {
+        let engine = risk_engine(1);
+        // 90 USD maintenance collateral against 66 USD maintenance liability
+        let health = engine
+            .health(&position(100, 60), HealthType::Maintenance)
+            .unwrap();
+        assert_eq!(health.ratio().unwrap(), 13_636);
+
+        let health = engine
+            .health(&position(100, 0), HealthType::Maintenance)
+            .unwrap();
+        assert_eq!(health.ratio().unwrap(), u64::MAX);
+    }
+}
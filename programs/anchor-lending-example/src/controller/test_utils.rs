@@ -0,0 +1,22 @@
+//! Fixtures shared by the controller unit tests
+
+use crate::protocol::{Bank, BankStatus, PriceFeedMessage};
+
+/// Active bank with 6 decimals, default weights and an unscaled price
+pub fn bank(bank_id: u8, price: u64) -> Bank {
+    Bank {
+        bank_id,
+        status: BankStatus::Active as u8,
+        decimals: 6,
+        initial_asset_weight: 80,
+        maintenance_asset_weight: 90,
+        initial_liability_weight: 120,
+        maintenance_liability_weight: 110,
+        price_message: PriceFeedMessage {
+            price,
+            ema_price: price,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
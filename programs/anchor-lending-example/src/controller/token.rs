@@ -1,27 +1,76 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    token_interface::{Mint, TokenAccount},
+    token_2022::spl_token_2022::{
+        extension::{
+            transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
+        },
+        onchain::invoke_transfer_checked,
+        state::Mint as MintState,
+    },
     *,
 };
 
+/// Calculates the amount a destination receives after any Token-2022 transfer fee
+/// Mints without the TransferFee extension deliver the full amount
+pub fn calculate_net_amount(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != token_2022::ID {
+        return Ok(amount);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    let fee = match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?,
+        Err(_) => 0,
+    };
+
+    Ok(amount
+        .checked_sub(fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?)
+}
+
+/// Calculates the amount to send for a destination to receive `net_amount` after any
+/// Token-2022 transfer fee
+/// Mints without the TransferFee extension require exactly the net amount
+pub fn calculate_gross_amount(mint: &AccountInfo, net_amount: u64) -> Result<u64> {
+    if *mint.owner != token_2022::ID {
+        return Ok(net_amount);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    let fee = match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?,
+        Err(_) => 0,
+    };
+
+    Ok(net_amount
+        .checked_add(fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?)
+}
+
 /// Interface for working with both token and token-2022 programs
 /// At least one program must be provided
 pub struct TokenInstructionInterface<'a> {
     pub token_program: Option<AccountInfo<'a>>,
     pub token_2022_program: Option<AccountInfo<'a>>,
-    pub mints: Option<Vec<AccountInfo<'a>>>, // Changed to optional vector
+    /// Remaining accounts searched for transfer hook extra account metas
+    pub additional_accounts: Vec<AccountInfo<'a>>,
 }
 
 impl<'a> TokenInstructionInterface<'a> {
     /// Loads token program interfaces from provided accounts
-    /// Returns TokenInterface with optional programs and any mint accounts found
+    /// Returns TokenInterface with optional programs and the accounts available to transfer hooks
     pub fn load(
         token_interface: &AccountInfo<'a>,
         remaining_accounts: &[AccountInfo<'a>],
     ) -> Result<Self> {
         let mut token_program: Option<AccountInfo<'a>> = None;
         let mut token_2022_program: Option<AccountInfo<'a>> = None;
-        let mut mints: Option<Vec<AccountInfo<'a>>> = None;
 
         // First check the token_interface account
         if token_interface.key() == token::ID {
@@ -30,19 +79,12 @@ impl<'a> TokenInstructionInterface<'a> {
             token_2022_program = Some(token_interface.clone());
         }
 
-        // Then scan remaining accounts for any missing program and mints
+        // Then scan remaining accounts for any missing program
         for account in remaining_accounts {
             if account.key() == token::ID && token_program.is_none() {
                 token_program = Some(account.clone());
             } else if account.key() == token_2022::ID && token_2022_program.is_none() {
                 token_2022_program = Some(account.clone());
-            } else if account.owner == &token::ID || account.owner == &token_2022::ID {
-                // Initialize mints vector if this is first mint account found
-                if mints.is_none() {
-                    mints = Some(Vec::new());
-                }
-                // Add mint account to the vector
-                mints.as_mut().unwrap().push(account.clone());
             }
         }
 
@@ -54,132 +96,85 @@ impl<'a> TokenInstructionInterface<'a> {
         Ok(Self {
             token_program,
             token_2022_program,
-            mints,
+            additional_accounts: remaining_accounts.to_vec(),
         })
     }
 
-    /// Transfer tokens from one account to another
-    /// Uses the appropriate token program based on the mint type
-    pub fn transfer(
+    /// Selects the token program owning the source token account
+    fn program_for(&self, source: &AccountInfo<'a>) -> Result<&AccountInfo<'a>> {
+        let token_program = match *source.owner {
+            token::ID => self.token_program.as_ref(),
+            token_2022::ID => self.token_2022_program.as_ref(),
+            _ => None,
+        };
+        Ok(token_program.ok_or(ProgramError::InvalidAccountData)?)
+    }
+
+    /// Invoke transfer_checked, forwarding the extra accounts required by a transfer hook
+    /// The hook's extra account metas are resolved from the additional accounts
+    #[allow(clippy::too_many_arguments)]
+    fn transfer_checked(
         &self,
         source: AccountInfo<'a>,
+        mint: AccountInfo<'a>,
         destination: AccountInfo<'a>,
         authority: AccountInfo<'a>,
         amount: u64,
-    ) -> Result<()> {
-        let token_program = match *source.owner {
-            token::ID => self
-                .token_program
-                .as_ref()
-                .ok_or(ProgramError::InvalidAccountData)?,
-            token_2022::ID => self
-                .token_2022_program
-                .as_ref()
-                .ok_or(ProgramError::InvalidAccountData)?,
-            _ => return Err(ProgramError::InvalidAccountData.into()),
-        };
-
-        // Try transfer_checked if mints are available
-        if let Some(mints) = &self.mints {
-            let source_data = TokenAccount::try_deserialize(&mut source.data.borrow().as_ref())?;
-
-            // Find mint account that matches source token account's mint
-            if let Some(mint) = mints.iter().find(|m| m.key() == source_data.mint) {
-                let decimal = Mint::try_deserialize(&mut mint.data.borrow().as_ref())?.decimals;
-                token_interface::transfer_checked(
-                    CpiContext::new(
-                        token_program.to_account_info(),
-                        token_interface::TransferChecked {
-                            from: source,
-                            mint: mint.clone(),
-                            to: destination,
-                            authority,
-                        },
-                    ),
-                    amount,
-                    decimal,
-                )?;
-                return Ok(());
-            }
-        }
-
-        // Fallback to regular transfer if mints not provided or matching mint not found
-        #[allow(deprecated)]
-        token_interface::transfer(
-            CpiContext::new(
-                token_program.to_account_info(),
-                token_interface::Transfer {
-                    from: source,
-                    to: destination,
-                    authority,
-                },
-            ),
+        decimals: u8,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<u64> {
+        let token_program = self.program_for(&source)?;
+        invoke_transfer_checked(
+            token_program.key,
+            source,
+            mint.clone(),
+            destination,
+            authority,
+            &self.additional_accounts,
             amount,
+            decimals,
+            signer_seeds,
         )?;
-        Ok(())
+        calculate_net_amount(&mint, amount)
+    }
+
+    /// Transfer tokens from one account to another with transfer_checked
+    /// Uses the appropriate token program based on the source account owner
+    /// Returns the amount received by the destination after transfer fees
+    pub fn transfer(
+        &self,
+        source: AccountInfo<'a>,
+        mint: AccountInfo<'a>,
+        destination: AccountInfo<'a>,
+        authority: AccountInfo<'a>,
+        amount: u64,
+        decimals: u8,
+    ) -> Result<u64> {
+        self.transfer_checked(source, mint, destination, authority, amount, decimals, &[])
     }
 
-    /// Transfer tokens using a PDA as the authority signer
-    /// Uses the appropriate token program based on the mint type
+    /// Transfer tokens with transfer_checked using a PDA as the authority signer
+    /// Uses the appropriate token program based on the source account owner
+    /// Returns the amount received by the destination after transfer fees
+    #[allow(clippy::too_many_arguments)]
     pub fn transfer_with_signer(
         &self,
         source: AccountInfo<'a>,
+        mint: AccountInfo<'a>,
         destination: AccountInfo<'a>,
         authority: AccountInfo<'a>,
         amount: u64,
+        decimals: u8,
         signer_seeds: &[&[u8]],
-    ) -> Result<()> {
-        let token_program = match *source.owner {
-            token::ID => self
-                .token_program
-                .as_ref()
-                .ok_or(ProgramError::InvalidAccountData)?,
-            token_2022::ID => self
-                .token_2022_program
-                .as_ref()
-                .ok_or(ProgramError::InvalidAccountData)?,
-            _ => return Err(ProgramError::InvalidAccountData.into()),
-        };
-
-        // Try transfer_checked if mints are available
-        if let Some(mints) = &self.mints {
-            let source_data = TokenAccount::try_deserialize(&mut source.data.borrow().as_ref())?;
-
-            // Find mint account that matches source token account's mint
-            if let Some(mint) = mints.iter().find(|m| m.key() == source_data.mint) {
-                let decimal = Mint::try_deserialize(&mut mint.data.borrow().as_ref())?.decimals;
-                token_interface::transfer_checked(
-                    CpiContext::new_with_signer(
-                        token_program.to_account_info(),
-                        token_interface::TransferChecked {
-                            from: source,
-                            mint: mint.clone(),
-                            to: destination,
-                            authority,
-                        },
-                        &[signer_seeds],
-                    ),
-                    amount,
-                    decimal,
-                )?;
-                return Ok(());
-            }
-        }
-
-        // Fallback to regular transfer if mints not provided or matching mint not found
-        #[allow(deprecated)]
-        token_interface::transfer(
-            CpiContext::new_with_signer(
-                token_program.to_account_info(),
-                token_interface::Transfer {
-                    from: source,
-                    to: destination,
-                    authority,
-                },
-                &[signer_seeds],
-            ),
+    ) -> Result<u64> {
+        self.transfer_checked(
+            source,
+            mint,
+            destination,
+            authority,
             amount,
-        )?;
-        Ok(())
+            decimals,
+            &[signer_seeds],
+        )
     }
 }
@@ -20,20 +20,66 @@ pub mod anchor_lending_example {
         protocol::handle_initialize(ctx)
     }
 
-    /// Update the admin authority to a new account
+    /// Propose a new admin authority
     /// Can only be called by the current authority
-    pub fn update_authority(ctx: Context<UpdateAuthority>) -> Result<()> {
-        protocol::handle_update_authority(ctx)
+    pub fn propose_authority(ctx: Context<ProposeAuthority>) -> Result<()> {
+        protocol::handle_propose_authority(ctx)
     }
 
-    /// Initialize a new pool
+    /// Accept a pending admin authority transfer
+    /// Must be signed by the proposed authority
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        protocol::handle_accept_authority(ctx)
+    }
+
+    /// Cancel a pending admin authority transfer
+    /// Can only be called by the current authority
+    pub fn cancel_authority(ctx: Context<CancelAuthority>) -> Result<()> {
+        protocol::handle_cancel_authority(ctx)
+    }
+
+    /// Grant an admin role to an account
     /// Can only be called by the admin authority
-    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
-        protocol::handle_initialize_pool(ctx)
+    pub fn grant_role(ctx: Context<GrantRole>, role: u8) -> Result<()> {
+        protocol::handle_grant_role(ctx, role)
     }
 
-    /// Initialize configuration for a new bank
+    /// Revoke an admin role from its holder
+    /// Can only be called by the admin authority
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: u8) -> Result<()> {
+        protocol::handle_revoke_role(ctx, role)
+    }
+
+    /// Initialize a new pool managed by the given pool authority
     /// Can only be called by the admin authority
+    pub fn initialize_pool(ctx: Context<InitializePool>, params: PoolConfigParams) -> Result<()> {
+        protocol::handle_initialize_pool(ctx, params)
+    }
+
+    /// Update the configuration of a pool
+    /// Can only be called by the pool authority
+    pub fn update_pool(
+        ctx: Context<UpdatePool>,
+        pool_id: u8,
+        params: UpdatePoolParams,
+    ) -> Result<()> {
+        protocol::handle_update_pool(ctx, pool_id, params)
+    }
+
+    /// Replace the protocol-wide pause flags for deposits, withdrawals and liquidations
+    /// Can only be called by the emergency guardian or admin authority
+    pub fn set_protocol_pause(ctx: Context<SetProtocolPause>, flags: u8) -> Result<()> {
+        protocol::handle_set_protocol_pause(ctx, flags)
+    }
+
+    /// Replace the pause flags of a pool for deposits, withdrawals and liquidations
+    /// Can be called by the pool authority, emergency guardian or admin authority
+    pub fn set_pool_pause(ctx: Context<SetPoolPause>, pool_id: u8, flags: u8) -> Result<()> {
+        protocol::handle_set_pool_pause(ctx, pool_id, flags)
+    }
+
+    /// Initialize configuration for a new bank
+    /// Can only be called by the pool authority, listing manager or admin authority
     pub fn initialize_bank(
         ctx: Context<InitializeBank>,
         pool_id: u8,
@@ -43,13 +89,56 @@ pub mod anchor_lending_example {
     }
 
     /// Update the operational status of a bank
-    /// Can only be called by the admin authority
+    /// Can be called by the pool authority or risk manager,
+    /// or by the emergency guardian to pause, reduce or freeze
     pub fn update_bank_status(ctx: Context<UpdateBankStatus>, new_status: u8) -> Result<()> {
         protocol::handle_update_bank_status(ctx, new_status)
     }
 
+    /// Update the risk weights of a bank
+    /// Can be called by the pool authority or risk manager
+    pub fn update_bank_config(
+        ctx: Context<UpdateBankConfig>,
+        params: UpdateBankConfigParams,
+    ) -> Result<()> {
+        protocol::handle_update_bank_config(ctx, params)
+    }
+
+    /// Update the flash loan fee of a bank in basis points
+    /// Can be called by the pool authority or risk manager
+    pub fn update_flash_loan_fee(ctx: Context<UpdateFlashLoanFee>, fee: u16) -> Result<()> {
+        protocol::handle_update_flash_loan_fee(ctx, fee)
+    }
+
+    /// Start delisting a bank, its asset weights decay to zero by the deadline
+    /// Can only be called by the pool authority or risk manager
+    pub fn sunset_bank(ctx: Context<SunsetBank>, deadline: i64) -> Result<()> {
+        protocol::handle_sunset_bank(ctx, deadline)
+    }
+
+    /// Settle a user position in a sunset bank after its deadline
+    /// Permissionless, collateral is paid out pro rata once liabilities are settled, and
+    /// liabilities are only written off once the user has no collateral left
+    pub fn force_settle<'info>(ctx: Context<'_, '_, '_, 'info, ForceSettle<'info>>) -> Result<()> {
+        protocol::handle_force_settle(ctx)
+    }
+
+    /// Settle a liability in a sunset bank after its deadline against the user's collateral
+    /// Permissionless, the caller repays the liability and receives discounted collateral
+    pub fn force_settle_liability<'info>(
+        ctx: Context<'_, '_, '_, 'info, ForceSettleLiability<'info>>,
+    ) -> Result<()> {
+        protocol::handle_force_settle_liability(ctx)
+    }
+
+    /// Close a sunset bank and its vault once every position is settled
+    /// Permissionless, rent and remaining vault tokens go to the pool authority
+    pub fn close_bank<'info>(ctx: Context<'_, '_, '_, 'info, CloseBank<'info>>) -> Result<()> {
+        protocol::handle_close_bank(ctx)
+    }
+
     /// Update the price feed for a bank
-    /// Can only be called by the admin authority
+    /// Can only be called by the oracle keeper or admin authority
     pub fn update_price(ctx: Context<UpdatePrice>, params: UpdatePriceParams) -> Result<()> {
         protocol::handle_update_price(ctx, params)
     }
@@ -62,20 +151,42 @@ pub mod anchor_lending_example {
 
     /// Deposit tokens into a bank
     /// User must sign the transaction and provide token account with sufficient balance
+    /// With `all` set, the amount is ignored and the whole liability is repaid
     pub fn deposit<'info>(
         ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
         amount: u64,
+        all: bool,
     ) -> Result<()> {
-        user::handle_deposit(ctx, amount)
+        user::handle_deposit(ctx, amount, all)
     }
 
     /// Withdraw tokens from a bank
     /// User must sign the transaction and have sufficient balance in their user account
+    /// With `all` set, the amount is ignored and the whole collateral is withdrawn
     pub fn withdraw<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, Withdraw<'info>>,
         amount: u64,
+        all: bool,
     ) -> Result<()> {
-        user::handle_withdrawal(ctx, amount)
+        user::handle_withdrawal(ctx, amount, all)
+    }
+
+    /// Deposit native SOL into a wrapped SOL bank
+    /// Lamports are wrapped through a temporary account closed back to the user
+    pub fn deposit_native<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositNative<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        user::handle_deposit_native(ctx, amount)
+    }
+
+    /// Withdraw native SOL from a wrapped SOL bank
+    /// Tokens are unwrapped by closing a temporary account to the user
+    pub fn withdraw_native<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, WithdrawNative<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        user::handle_withdraw_native(ctx, amount)
     }
 
     /// Liquidate an unhealthy position
@@ -86,4 +197,84 @@ pub mod anchor_lending_example {
     ) -> Result<()> {
         user::handle_liquidate(ctx, amount)
     }
+
+    /// Flash borrow tokens from a bank vault
+    /// A flash_repay of the amount plus the bank's fee must follow in the same transaction
+    pub fn flash_borrow<'info>(
+        ctx: Context<'_, '_, '_, 'info, FlashBorrow<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        user::handle_flash_borrow(ctx, amount)
+    }
+
+    /// Repay a flash loan with the borrowed amount plus the bank's fee
+    pub fn flash_repay<'info>(
+        ctx: Context<'_, '_, '_, 'info, FlashRepay<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        user::handle_flash_repay(ctx, amount)
+    }
+
+    /// Put a user account in flashloan mode, deferring health checks
+    /// An end_flashloan for the same user account must follow in the same transaction
+    pub fn begin_flashloan(ctx: Context<BeginFlashloan>) -> Result<()> {
+        user::handle_begin_flashloan(ctx)
+    }
+
+    /// Leave flashloan mode once the position passes the initial health check
+    pub fn end_flashloan<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, EndFlashloan<'info>>,
+    ) -> Result<()> {
+        user::handle_end_flashloan(ctx)
+    }
+
+    /// Get the largest amount of collateral withdrawable from a bank without borrowing
+    /// Read-only, the amount is returned through return data for simulations
+    pub fn get_max_withdraw<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, GetMaxWithdraw<'info>>,
+    ) -> Result<u64> {
+        user::handle_get_max_withdraw(ctx)
+    }
+
+    /// Get the largest liability a user can open in a bank under initial weights
+    /// Read-only, the amount is returned through return data for simulations
+    pub fn get_max_borrow<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, GetMaxBorrow<'info>>,
+    ) -> Result<u64> {
+        user::handle_get_max_borrow(ctx)
+    }
+
+    /// Get the price of a bank's token at which a user becomes liquidatable
+    /// Read-only, the price is returned through return data for simulations
+    pub fn get_liquidation_price<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, GetLiquidationPrice<'info>>,
+    ) -> Result<Option<u64>> {
+        user::handle_get_liquidation_price(ctx)
+    }
+
+    /// Get the health of a user position
+    /// Read-only, the values are returned through return data for simulations
+    pub fn get_user_health<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, GetUserHealth<'info>>,
+    ) -> Result<UserHealth> {
+        user::handle_get_user_health(ctx)
+    }
+
+    /// Propose a new authority for a user account
+    /// Can only be called by the current user authority
+    pub fn propose_user_authority(ctx: Context<ProposeUserAuthority>) -> Result<()> {
+        user::handle_propose_user_authority(ctx)
+    }
+
+    /// Accept a pending user account authority transfer
+    /// Must be signed by the proposed authority
+    pub fn accept_user_authority(ctx: Context<AcceptUserAuthority>) -> Result<()> {
+        user::handle_accept_user_authority(ctx)
+    }
+
+    /// Grow a user account created before the pending authority was added to the layout
+    /// Permissionless, the payer funds the extra rent
+    pub fn migrate_user(ctx: Context<MigrateUser>) -> Result<()> {
+        user::handle_migrate_user(ctx)
+    }
 }
@@ -18,6 +18,10 @@ pub enum AdminError {
     /// Numeric overflow occurred
     #[msg("Numeric overflow occurred")]
     Overflow,
+
+    /// No authority transfer is pending
+    #[msg("No pending authority")]
+    NoPendingAuthority,
 }
 
 #[error_code]
@@ -25,9 +29,54 @@ pub enum BankError {
     #[msg("Bank account not found")]
     BankNotFound,
 
-    #[msg("Bank is inactive for deposits")]
-    BankInactive,
+    #[msg("Operation not allowed in current bank status")]
+    OperationNotAllowed,
+
+    #[msg("Bank status transition not allowed")]
+    InvalidStatusTransition,
+
+    #[msg("Bank is frozen and its timelock has not elapsed")]
+    StatusTimelockActive,
+
+    #[msg("Bank is not in sunset")]
+    BankNotSunset,
+
+    #[msg("Sunset deadline has not been reached")]
+    SunsetDeadlineNotReached,
+
+    #[msg("Bank still has open positions")]
+    BankHasOpenPositions,
+
+    #[msg("Mint has an unsupported or restricted extension")]
+    UnsupportedMintExtension,
+
+    #[msg("Bank already has a flash loan outstanding")]
+    FlashLoanActive,
+
+    #[msg("Bank has no flash loan outstanding")]
+    NoFlashLoan,
+
+    #[msg("Flash loan is not repaid later in the transaction")]
+    FlashRepayMissing,
+
+    #[msg("Flash loans must be called directly by the transaction")]
+    FlashLoanCpi,
+
+    #[msg("Flash loan repayment must equal the amount plus fee")]
+    InvalidFlashRepayAmount,
+
+    #[msg("Sunset bank liabilities must be settled before collateral is paid out")]
+    SunsetLiabilitiesOutstanding,
+
+    #[msg("Liability must be settled against the user's collateral first")]
+    SettleAgainstCollateral,
+
+    #[msg("User has no liability in the sunset bank")]
+    NoSunsetLiability,
+
+    #[msg("User has no collateral in the settlement bank")]
+    NoSettlementCollateral,
 
-    #[msg("Bank is not available for withdrawals")]
-    BankNotAvailableForWithdrawal,
+    #[msg("Asset weights must be at most 100 and liability weights at least 100, with maintenance weights no stricter than initial weights")]
+    InvalidWeights,
 }
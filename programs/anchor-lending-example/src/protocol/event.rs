@@ -10,6 +10,30 @@ pub struct AdminInitialized {
     pub authority: Pubkey,
 }
 
+/// Event emitted when a new admin authority is proposed
+#[event]
+pub struct AdminAuthorityProposed {
+    /// The admin account address
+    #[index]
+    pub admin: Pubkey,
+    /// The current authority
+    pub authority: Pubkey,
+    /// The proposed authority
+    pub pending_authority: Pubkey,
+}
+
+/// Event emitted when a pending admin authority proposal is cancelled
+#[event]
+pub struct AdminAuthorityProposalCancelled {
+    /// The admin account address
+    #[index]
+    pub admin: Pubkey,
+    /// The current authority
+    pub authority: Pubkey,
+    /// The proposed authority that was cancelled
+    pub cancelled_authority: Pubkey,
+}
+
 /// Event emitted when admin authority is updated
 #[event]
 pub struct AdminAuthorityUpdated {
@@ -22,6 +46,32 @@ pub struct AdminAuthorityUpdated {
     pub new_authority: Pubkey,
 }
 
+/// Event emitted when an admin role is granted
+#[event]
+pub struct AdminRoleGranted {
+    /// The admin account address
+    #[index]
+    pub admin: Pubkey,
+    /// The role granted
+    pub role: u8,
+    /// The previous role holder
+    pub old_holder: Pubkey,
+    /// The new role holder
+    pub new_holder: Pubkey,
+}
+
+/// Event emitted when an admin role is revoked
+#[event]
+pub struct AdminRoleRevoked {
+    /// The admin account address
+    #[index]
+    pub admin: Pubkey,
+    /// The role revoked
+    pub role: u8,
+    /// The role holder that was removed
+    pub old_holder: Pubkey,
+}
+
 /// Event emitted when a new pool is initialized
 #[event]
 pub struct PoolInitialized {
@@ -32,6 +82,24 @@ pub struct PoolInitialized {
     pub authority: Pubkey,
 }
 
+/// Event emitted when pool configuration is updated
+#[event]
+pub struct PoolUpdated {
+    /// The pool ID that was updated
+    #[index]
+    pub pool_id: u8,
+    /// The pool authority
+    pub authority: Pubkey,
+    /// New pool status
+    pub status: u8,
+    /// New close factor
+    pub close_factor: u8,
+    /// New maximum liquidation bonus
+    pub max_liquidation_bonus: u8,
+    /// New maximum positions per user
+    pub max_positions: u8,
+}
+
 #[event]
 pub struct BankInitialized {
     pub mint: Pubkey,
@@ -50,3 +118,113 @@ pub struct BankStatusUpdated {
     /// New bank status
     pub new_status: u8,
 }
+
+/// Event emitted when protocol-wide pause flags are updated
+#[event]
+pub struct ProtocolPauseUpdated {
+    /// The admin account address
+    #[index]
+    pub admin: Pubkey,
+    /// The signer that updated the flags
+    pub authority: Pubkey,
+    /// Previous pause flags
+    pub old_flags: u8,
+    /// New pause flags
+    pub new_flags: u8,
+}
+
+/// Event emitted when pool pause flags are updated
+#[event]
+pub struct PoolPauseUpdated {
+    /// The pool ID
+    #[index]
+    pub pool_id: u8,
+    /// The signer that updated the flags
+    pub authority: Pubkey,
+    /// Previous pause flags
+    pub old_flags: u8,
+    /// New pause flags
+    pub new_flags: u8,
+}
+
+/// Event emitted when a bank enters sunset
+#[event]
+pub struct BankSunsetStarted {
+    /// The token mint address
+    pub mint: Pubkey,
+    /// Previous bank status
+    pub old_status: u8,
+    /// Unix timestamp after which positions can be force settled
+    pub deadline: i64,
+}
+
+/// Event emitted when a position in a sunset bank is force settled
+#[event]
+pub struct PositionForceSettled {
+    /// The user account that was settled
+    #[index]
+    pub user: Pubkey,
+    /// The token mint address
+    pub mint: Pubkey,
+    /// Signed balance before settlement, liabilities are negative
+    pub previous_balance: i64,
+    /// Amount of collateral paid out from the vault
+    pub settled_amount: u64,
+    /// Amount of collateral or liability that could not be settled and was written off
+    pub written_off: u64,
+}
+
+/// Event emitted when a liability in a sunset bank is settled against collateral
+#[event]
+pub struct SunsetLiabilitySettled {
+    /// The user account that was settled
+    #[index]
+    pub user: Pubkey,
+    /// The sunset bank token mint
+    pub mint: Pubkey,
+    /// The collateral token mint
+    pub collateral_mint: Pubkey,
+    /// Amount of liability repaid by the caller
+    pub repaid_amount: u64,
+    /// Amount of collateral paid to the caller
+    pub seized_amount: u64,
+}
+
+/// Event emitted when a sunset bank and its vault are closed
+#[event]
+pub struct BankClosed {
+    /// The token mint address
+    pub mint: Pubkey,
+    /// The pool ID
+    pub pool_id: u8,
+    /// The bank ID within the pool
+    pub bank_id: u8,
+    /// Remaining vault tokens swept to the pool authority
+    pub swept_amount: u64,
+}
+
+/// Event emitted when a bank's risk weights are updated
+#[event]
+pub struct BankConfigUpdated {
+    /// The token mint address
+    pub mint: Pubkey,
+    /// New initial asset weight
+    pub initial_asset_weight: u8,
+    /// New maintenance asset weight
+    pub maintenance_asset_weight: u8,
+    /// New initial liability weight
+    pub initial_liability_weight: u8,
+    /// New maintenance liability weight
+    pub maintenance_liability_weight: u8,
+}
+
+/// Event emitted when a bank's flash loan fee is updated
+#[event]
+pub struct FlashLoanFeeUpdated {
+    /// The token mint address
+    pub mint: Pubkey,
+    /// Previous fee in basis points
+    pub old_fee: u16,
+    /// New fee in basis points
+    pub new_fee: u16,
+}
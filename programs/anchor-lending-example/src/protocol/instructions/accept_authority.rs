@@ -1,33 +1,31 @@
 use crate::protocol::{event::AdminAuthorityUpdated, Admin, AdminError, ADMIN_SEED};
 use anchor_lang::prelude::*;
 
-/// Accounts required for updating admin authority
+/// Accounts required for accepting a proposed admin authority
 #[derive(Accounts)]
-pub struct UpdateAuthority<'info> {
+pub struct AcceptAuthority<'info> {
     /// The admin account to update
     #[account(
         mut,
         seeds = [ADMIN_SEED],
         bump,
-        has_one = authority @ AdminError::Unauthorized,
+        constraint = admin.load()?.pending_authority == new_authority.key() @ AdminError::Unauthorized,
     )]
     pub admin: AccountLoader<'info, Admin>,
 
-    /// The current authority that must sign
-    pub authority: Signer<'info>,
-
-    /// The new authority to be set
-    pub new_authority: SystemAccount<'info>,
+    /// The proposed authority that must sign to accept
+    pub new_authority: Signer<'info>,
 }
 
-/// Update the admin authority to a new account
-/// Can only be called by the current authority
-pub fn handle_update_authority(ctx: Context<UpdateAuthority>) -> Result<()> {
+/// Accept a pending admin authority transfer
+/// Can only be called by the proposed authority
+pub fn handle_accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
     let mut admin = ctx.accounts.admin.load_mut()?;
     let old_authority = admin.authority;
 
-    // Update the authority
+    // Update the authority and clear the proposal
     admin.authority = ctx.accounts.new_authority.key();
+    admin.pending_authority = Pubkey::default();
 
     msg!(
         "Admin authority updated from {} to {}",
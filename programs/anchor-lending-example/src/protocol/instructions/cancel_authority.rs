@@ -0,0 +1,48 @@
+use crate::protocol::{event::AdminAuthorityProposalCancelled, Admin, AdminError, ADMIN_SEED};
+use anchor_lang::prelude::*;
+
+/// Accounts required for cancelling a proposed admin authority
+#[derive(Accounts)]
+pub struct CancelAuthority<'info> {
+    /// The admin account to update
+    #[account(
+        mut,
+        seeds = [ADMIN_SEED],
+        bump,
+        has_one = authority @ AdminError::Unauthorized,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
+    /// The current authority that must sign
+    pub authority: Signer<'info>,
+}
+
+/// Cancel a pending admin authority transfer
+/// Can only be called by the current authority
+pub fn handle_cancel_authority(ctx: Context<CancelAuthority>) -> Result<()> {
+    let mut admin = ctx.accounts.admin.load_mut()?;
+    let cancelled_authority = admin.pending_authority;
+
+    require_keys_neq!(
+        cancelled_authority,
+        Pubkey::default(),
+        AdminError::NoPendingAuthority
+    );
+
+    // Clear the proposal
+    admin.pending_authority = Pubkey::default();
+
+    msg!(
+        "Admin authority transfer to {} cancelled",
+        cancelled_authority
+    );
+
+    // Emit the cancel event
+    emit!(AdminAuthorityProposalCancelled {
+        admin: ctx.accounts.admin.key(),
+        authority: admin.authority,
+        cancelled_authority,
+    });
+
+    Ok(())
+}
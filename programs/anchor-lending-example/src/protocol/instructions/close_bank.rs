@@ -0,0 +1,135 @@
+use crate::{
+    controller::token::TokenInstructionInterface,
+    protocol::{event::BankClosed, state::*, BankError},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+
+/// Close a fully settled sunset bank and its vault
+#[derive(Accounts)]
+pub struct CloseBank<'info> {
+    /// Anyone may close the bank once every position is settled
+    pub caller: Signer<'info>,
+
+    /// The pool the bank belongs to
+    #[account(
+        seeds = [POOL_SEED, &[bank.load()?.pool_id][..]],
+        bump,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The pool authority receiving the rent of the closed accounts
+    #[account(
+        mut,
+        address = pool.load()?.authority,
+    )]
+    pub pool_authority: SystemAccount<'info>,
+
+    /// The sunset bank to close
+    #[account(
+        mut,
+        seeds = [
+            BANK_SEED,
+            &[bank.load()?.pool_id][..],
+            &[bank.load()?.bank_id][..],
+        ],
+        bump,
+        close = pool_authority,
+    )]
+    pub bank: AccountLoader<'info, Bank>,
+
+    /// Bank's token account to close
+    #[account(
+        mut,
+        seeds = [
+            b"token_account",
+            bank.key().as_ref(),
+        ],
+        bump,
+        token::authority = bank,
+        token::mint = bank.load()?.mint,
+    )]
+    pub bank_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pool authority token account receiving any remaining vault tokens
+    #[account(
+        mut,
+        token::authority = pool_authority,
+        token::mint = bank.load()?.mint,
+    )]
+    pub receiver_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the bank token, required for transfer_checked
+    #[account(address = bank.load()?.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program, either Token or Token2022
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Handles closing a sunset bank once its deadline has passed and no positions remain
+pub fn handle_close_bank<'info>(ctx: Context<'_, '_, '_, 'info, CloseBank<'info>>) -> Result<()> {
+    let bank = *ctx.accounts.bank.load()?;
+    require_eq!(
+        bank.status,
+        BankStatus::Sunset as u8,
+        BankError::BankNotSunset
+    );
+    require_gte!(
+        Clock::get()?.unix_timestamp,
+        bank.sunset_deadline,
+        BankError::SunsetDeadlineNotReached
+    );
+    require!(
+        bank.total_deposits == 0 && bank.total_liabilities == 0,
+        BankError::BankHasOpenPositions
+    );
+
+    let bank_seeds = &[
+        BANK_SEED,
+        &[bank.pool_id][..],
+        &[bank.bank_id][..],
+        &[ctx.bumps.bank][..],
+    ];
+
+    // Sweep rounding dust so the vault can be closed
+    let swept_amount = ctx.accounts.bank_token_account.amount;
+    if swept_amount > 0 {
+        let token_interface =
+            TokenInstructionInterface::load(&ctx.accounts.token_program, ctx.remaining_accounts)?;
+        token_interface.transfer_with_signer(
+            ctx.accounts.bank_token_account.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.receiver_token_account.to_account_info(),
+            ctx.accounts.bank.to_account_info(),
+            swept_amount,
+            bank.decimals,
+            bank_seeds,
+        )?;
+    }
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.bank_token_account.to_account_info(),
+            destination: ctx.accounts.pool_authority.to_account_info(),
+            authority: ctx.accounts.bank.to_account_info(),
+        },
+        &[bank_seeds],
+    ))?;
+
+    emit!(BankClosed {
+        mint: bank.mint,
+        pool_id: bank.pool_id,
+        bank_id: bank.bank_id,
+        swept_amount,
+    });
+
+    msg!(
+        "Closed bank #{} in pool {} for mint: {}",
+        bank.bank_id,
+        bank.pool_id,
+        bank.mint
+    );
+    Ok(())
+}
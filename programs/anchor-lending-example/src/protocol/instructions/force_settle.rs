@@ -0,0 +1,157 @@
+use crate::{
+    controller::token::TokenInstructionInterface,
+    protocol::{event::PositionForceSettled, state::*, BankError},
+    user::state::{BalanceType, Direction, User},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Settle a user position in a sunset bank after its deadline
+#[derive(Accounts)]
+pub struct ForceSettle<'info> {
+    /// Anyone may settle positions once the deadline has passed
+    pub caller: Signer<'info>,
+
+    /// The sunset bank
+    #[account(
+        mut,
+        seeds = [
+            BANK_SEED,
+            &[bank.load()?.pool_id][..],
+            &[bank.load()?.bank_id][..],
+        ],
+        bump,
+    )]
+    pub bank: AccountLoader<'info, Bank>,
+
+    /// Bank's token account paying out collateral
+    #[account(
+        mut,
+        seeds = [
+            b"token_account",
+            bank.key().as_ref(),
+        ],
+        bump,
+        token::authority = bank,
+        token::mint = bank.load()?.mint,
+    )]
+    pub bank_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// User account holding the position
+    #[account(
+        mut,
+        constraint = user_account.load()?.pool_id == bank.load()?.pool_id,
+    )]
+    pub user_account: AccountLoader<'info, User>,
+
+    /// Token account of the user authority receiving collateral
+    #[account(
+        mut,
+        token::authority = user_account.load()?.authority,
+        token::mint = bank.load()?.mint,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the bank token, required for transfer_checked
+    #[account(address = bank.load()?.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program, either Token or Token2022
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Handles closing a user position in a sunset bank
+/// Collateral is paid out once every liability is settled, sharing any vault shortfall
+/// pro rata between depositors
+/// A liability is only written off once the user has no collateral left to settle it
+/// against with `force_settle_liability`
+pub fn handle_force_settle<'info>(
+    ctx: Context<'_, '_, '_, 'info, ForceSettle<'info>>,
+) -> Result<()> {
+    let bank = *ctx.accounts.bank.load()?;
+    require_eq!(
+        bank.status,
+        BankStatus::Sunset as u8,
+        BankError::BankNotSunset
+    );
+    require_gte!(
+        Clock::get()?.unix_timestamp,
+        bank.sunset_deadline,
+        BankError::SunsetDeadlineNotReached
+    );
+
+    let mut user_account = ctx.accounts.user_account.load_mut()?;
+    let previous_balance = user_account.find_balance_by_bank_id(bank.bank_id);
+    let balance = previous_balance.unsigned_abs();
+
+    let (settled_amount, written_off) = if previous_balance > 0 {
+        // Paying out while debt is outstanding would favour the first depositors to settle
+        require_eq!(
+            bank.total_liabilities,
+            0,
+            BankError::SunsetLiabilitiesOutstanding
+        );
+        let vault_amount = ctx.accounts.bank_token_account.amount;
+        let settled_amount = (balance as u128)
+            .checked_mul(vault_amount as u128)
+            .and_then(|amount| amount.checked_div(bank.total_deposits as u128))
+            .map_or(vault_amount, |amount| amount as u64)
+            .min(balance);
+        if settled_amount > 0 {
+            let bank_seeds = &[
+                BANK_SEED,
+                &[bank.pool_id][..],
+                &[bank.bank_id][..],
+                &[ctx.bumps.bank][..],
+            ];
+            let token_interface = TokenInstructionInterface::load(
+                &ctx.accounts.token_program,
+                ctx.remaining_accounts,
+            )?;
+            token_interface.transfer_with_signer(
+                ctx.accounts.bank_token_account.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.user_token_account.to_account_info(),
+                ctx.accounts.bank.to_account_info(),
+                settled_amount,
+                bank.decimals,
+                bank_seeds,
+            )?;
+        }
+        user_account.update_balance(bank.bank_id, balance, Direction::Withdrawal)?;
+        (settled_amount, balance - settled_amount)
+    } else if previous_balance < 0 {
+        let has_collateral = user_account
+            .token_balances
+            .iter()
+            .any(|token| token.balance != 0 && token.balance_type == BalanceType::Collateral as u8);
+        require!(!has_collateral, BankError::SettleAgainstCollateral);
+        user_account.update_balance(bank.bank_id, balance, Direction::Deposit)?;
+        (0, balance)
+    } else {
+        (0, 0)
+    };
+    user_account.clear_balance(bank.bank_id);
+
+    ctx.accounts
+        .bank
+        .load_mut()?
+        .record_balance_change(previous_balance, 0)?;
+
+    emit!(PositionForceSettled {
+        user: ctx.accounts.user_account.key(),
+        mint: bank.mint,
+        previous_balance,
+        settled_amount,
+        written_off,
+    });
+
+    msg!(
+        "Force settled user {} in bank #{}: paid out {}, written off {}",
+        ctx.accounts.user_account.key(),
+        bank.bank_id,
+        settled_amount,
+        written_off
+    );
+    Ok(())
+}
@@ -0,0 +1,249 @@
+use crate::{
+    controller::{
+        calculate_gross_amount, calculate_token_amount, calculate_token_value,
+        TokenInstructionInterface,
+    },
+    protocol::{event::SunsetLiabilitySettled, state::*, BankError},
+    user::{
+        state::{Direction, User},
+        UserError, LIQUIDATION_BONUS,
+    },
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Settle a liability in a sunset bank against a user's collateral in another bank
+#[derive(Accounts)]
+pub struct ForceSettleLiability<'info> {
+    /// Anyone may settle liabilities once the deadline has passed
+    pub caller: Signer<'info>,
+
+    /// Caller's token account repaying the liability
+    #[account(
+        mut,
+        token::authority = caller,
+        token::mint = bank.load()?.mint,
+    )]
+    pub caller_liability_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// Caller's token account receiving collateral
+    #[account(
+        mut,
+        token::authority = caller,
+        token::mint = collateral_bank.load()?.mint,
+    )]
+    pub caller_collateral_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// User account holding the liability
+    #[account(
+        mut,
+        constraint = user_account.load()?.pool_id == bank.load()?.pool_id,
+        constraint = user_account.load()?.pool_id == collateral_bank.load()?.pool_id,
+    )]
+    pub user_account: AccountLoader<'info, User>,
+
+    /// The sunset bank holding the liability
+    #[account(
+        mut,
+        seeds = [
+            BANK_SEED,
+            &[bank.load()?.pool_id][..],
+            &[bank.load()?.bank_id][..],
+        ],
+        bump,
+    )]
+    pub bank: AccountLoader<'info, Bank>,
+
+    /// Bank holding the collateral the liability is settled against
+    #[account(
+        mut,
+        seeds = [
+            BANK_SEED,
+            &[collateral_bank.load()?.pool_id][..],
+            &[collateral_bank.load()?.bank_id][..],
+        ],
+        bump,
+        constraint = collateral_bank.key() != bank.key() @ BankError::NoSettlementCollateral,
+    )]
+    pub collateral_bank: AccountLoader<'info, Bank>,
+
+    /// Sunset bank's token account receiving the repayment
+    #[account(
+        mut,
+        seeds = [
+            b"token_account",
+            bank.key().as_ref(),
+        ],
+        bump,
+        token::authority = bank,
+        token::mint = bank.load()?.mint,
+    )]
+    pub bank_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Collateral bank's token account paying out collateral
+    #[account(
+        mut,
+        seeds = [
+            b"token_account",
+            collateral_bank.key().as_ref(),
+        ],
+        bump,
+        token::authority = collateral_bank,
+        token::mint = collateral_bank.load()?.mint,
+    )]
+    pub bank_collateral_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pool the banks belong to
+    #[account(
+        seeds = [POOL_SEED, &[bank.load()?.pool_id][..]],
+        bump,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// Mint of the sunset bank token, required for transfer_checked
+    #[account(address = bank.load()?.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Mint of the collateral token, required for transfer_checked
+    #[account(address = collateral_bank.load()?.mint)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Handles repaying a user liability in a sunset bank after its deadline
+/// The caller repays as much of the liability as the user's collateral in the collateral
+/// bank covers at the liquidation discount and receives that collateral, so debt is
+/// settled against the user's own assets instead of being written off
+pub fn handle_force_settle_liability<'info>(
+    ctx: Context<'_, '_, '_, 'info, ForceSettleLiability<'info>>,
+) -> Result<()> {
+    let bank = *ctx.accounts.bank.load()?;
+    let collateral_bank = *ctx.accounts.collateral_bank.load()?;
+    require_eq!(
+        bank.status,
+        BankStatus::Sunset as u8,
+        BankError::BankNotSunset
+    );
+    require_gte!(
+        Clock::get()?.unix_timestamp,
+        bank.sunset_deadline,
+        BankError::SunsetDeadlineNotReached
+    );
+    require!(
+        collateral_bank.status()?.allows(BankOperation::Liquidate),
+        BankError::OperationNotAllowed
+    );
+
+    let mut user_account = ctx.accounts.user_account.load_mut()?;
+    let previous_liability = user_account.find_balance_by_bank_id(bank.bank_id);
+    let previous_collateral = user_account.find_balance_by_bank_id(collateral_bank.bank_id);
+    require_gt!(0, previous_liability, BankError::NoSunsetLiability);
+    require_gt!(previous_collateral, 0, BankError::NoSettlementCollateral);
+    let liability = previous_liability.unsigned_abs();
+    let collateral = previous_collateral.unsigned_abs();
+
+    // Collateral is valued at the same discount liquidators receive
+    let liquidation_discount =
+        100 - LIQUIDATION_BONUS.min(ctx.accounts.pool.load()?.max_liquidation_bonus);
+    let liability_value = calculate_token_value(liability, bank.decimals, &bank.price_message)?;
+    let required_value = (liability_value as u128)
+        .checked_mul(100)
+        .ok_or(error!(UserError::MathOverflow))?
+        / liquidation_discount as u128;
+    let required_collateral = calculate_token_amount(
+        required_value,
+        collateral_bank.decimals,
+        &collateral_bank.price_message,
+    )?;
+
+    // Without enough collateral, all of it goes towards repaying part of the liability
+    let (repaid_amount, seized_amount) = if required_collateral <= collateral {
+        (liability, required_collateral)
+    } else {
+        let collateral_value = calculate_token_value(
+            collateral,
+            collateral_bank.decimals,
+            &collateral_bank.price_message,
+        )?;
+        let repaid_value = (collateral_value as u128)
+            .checked_mul(liquidation_discount as u128)
+            .ok_or(error!(UserError::MathOverflow))?
+            / 100;
+        let repaid_amount =
+            calculate_token_amount(repaid_value, bank.decimals, &bank.price_message)?;
+        (repaid_amount.min(liability), collateral)
+    };
+
+    user_account.update_balance(bank.bank_id, repaid_amount, Direction::Deposit)?;
+    user_account.update_balance(
+        collateral_bank.bank_id,
+        seized_amount,
+        Direction::Withdrawal,
+    )?;
+    let new_liability = user_account.find_balance_by_bank_id(bank.bank_id);
+    let new_collateral = user_account.find_balance_by_bank_id(collateral_bank.bank_id);
+    user_account.clear_balance(bank.bank_id);
+    user_account.clear_balance(collateral_bank.bank_id);
+
+    ctx.accounts
+        .bank
+        .load_mut()?
+        .record_balance_change(previous_liability, new_liability)?;
+    ctx.accounts
+        .collateral_bank
+        .load_mut()?
+        .record_balance_change(previous_collateral, new_collateral)?;
+
+    let token_interface =
+        TokenInstructionInterface::load(&ctx.accounts.token_program, ctx.remaining_accounts)?;
+
+    // The vault must receive the repaid amount after any transfer fee
+    if repaid_amount > 0 {
+        let gross_amount =
+            calculate_gross_amount(&ctx.accounts.mint.to_account_info(), repaid_amount)?;
+        token_interface.transfer(
+            ctx.accounts.caller_liability_token.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.bank_token_account.to_account_info(),
+            ctx.accounts.caller.to_account_info(),
+            gross_amount,
+            bank.decimals,
+        )?;
+    }
+
+    let collateral_bank_seeds = &[
+        BANK_SEED,
+        &[collateral_bank.pool_id][..],
+        &[collateral_bank.bank_id][..],
+        &[ctx.bumps.collateral_bank][..],
+    ];
+    token_interface.transfer_with_signer(
+        ctx.accounts.bank_collateral_token.to_account_info(),
+        ctx.accounts.collateral_mint.to_account_info(),
+        ctx.accounts.caller_collateral_token.to_account_info(),
+        ctx.accounts.collateral_bank.to_account_info(),
+        seized_amount,
+        collateral_bank.decimals,
+        collateral_bank_seeds,
+    )?;
+
+    emit!(SunsetLiabilitySettled {
+        user: ctx.accounts.user_account.key(),
+        mint: bank.mint,
+        collateral_mint: collateral_bank.mint,
+        repaid_amount,
+        seized_amount,
+    });
+
+    msg!(
+        "Settled liability of user {} in bank #{}: repaid {}, seized {} from bank #{}",
+        ctx.accounts.user_account.key(),
+        bank.bank_id,
+        repaid_amount,
+        seized_amount,
+        collateral_bank.bank_id
+    );
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::protocol::{event::AdminRoleGranted, Admin, AdminError, AdminRole, ADMIN_SEED};
+use anchor_lang::prelude::*;
+
+/// Accounts required for granting an admin role
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    /// The admin account to update
+    #[account(
+        mut,
+        seeds = [ADMIN_SEED],
+        bump,
+        has_one = authority @ AdminError::Unauthorized,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
+    /// The super-admin authority that must sign
+    pub authority: Signer<'info>,
+
+    /// The account receiving the role
+    pub grantee: SystemAccount<'info>,
+}
+
+/// Grant a role to an account, replacing any previous holder
+/// Can only be called by the super-admin authority
+pub fn handle_grant_role(ctx: Context<GrantRole>, role: u8) -> Result<()> {
+    let admin_role = AdminRole::try_from(role)?;
+    let mut admin = ctx.accounts.admin.load_mut()?;
+
+    let old_holder = admin.role_holder(admin_role);
+    admin.set_role_holder(admin_role, ctx.accounts.grantee.key());
+
+    msg!(
+        "Admin role {:?} granted to {} (previous holder: {})",
+        admin_role,
+        ctx.accounts.grantee.key(),
+        old_holder
+    );
+
+    emit!(AdminRoleGranted {
+        admin: ctx.accounts.admin.key(),
+        role,
+        old_holder,
+        new_holder: ctx.accounts.grantee.key(),
+    });
+
+    Ok(())
+}
@@ -28,6 +28,9 @@ pub fn handle_initialize(ctx: Context<Initialize>) -> Result<()> {
     // Set the authority address
     admin.authority = ctx.accounts.authority.key();
 
+    // No authority transfer pending
+    admin.pending_authority = Pubkey::default();
+
     // Initialize pool count to 0
     admin.pool_count = 0;
 
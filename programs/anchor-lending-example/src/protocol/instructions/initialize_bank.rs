@@ -1,4 +1,7 @@
-use crate::protocol::{event::BankInitialized, state::*, AdminError};
+use crate::{
+    controller::inspect_mint_extensions,
+    protocol::{event::BankInitialized, state::*, AdminError, BankError},
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
@@ -13,6 +16,11 @@ pub struct BankConfigParams {
     pub initial_liability_weight: u8,
     /// Weight applied to liabilities for maintenance requirements
     pub maintenance_liability_weight: u8,
+    /// Restricted mint extensions to accept, see `MintExtension`
+    /// Only the admin authority may allowlist extensions
+    pub allowed_extensions: u16,
+    /// Flash loan fee in basis points of the borrowed amount
+    pub flash_loan_fee: u16,
 }
 
 #[derive(Accounts)]
@@ -22,7 +30,6 @@ pub struct InitializeBank<'info> {
     #[account(
         seeds = [ADMIN_SEED],
         bump,
-        has_one = authority @ AdminError::Unauthorized,
     )]
     pub admin: AccountLoader<'info, Admin>,
 
@@ -31,6 +38,9 @@ pub struct InitializeBank<'info> {
         mut,
         seeds = [POOL_SEED, &[pool_id][..]],
         bump,
+        constraint = pool.load()?.authority == authority.key()
+            || admin.load()?.has_role(AdminRole::ListingManager, &authority.key())
+            @ AdminError::Unauthorized,
     )]
     pub pool: AccountLoader<'info, Pool>,
 
@@ -65,7 +75,7 @@ pub struct InitializeBank<'info> {
     )]
     pub token_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// The authority that must sign to initialize bank
+    /// The pool authority or listing manager that must sign to initialize bank
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -85,6 +95,38 @@ pub fn handle_initialize_bank(
     // Verify pool_id exists
     require_gte!(admin.pool_count, pool_id + 1, AdminError::InvalidGroupId);
 
+    // Restricted mint extensions are rejected unless allowlisted by the admin authority
+    require_eq!(
+        params.allowed_extensions & !ALL_MINT_EXTENSIONS,
+        0,
+        AdminError::InvalidInput
+    );
+    if params.allowed_extensions != 0 {
+        require_keys_eq!(
+            admin.authority,
+            ctx.accounts.authority.key(),
+            AdminError::Unauthorized
+        );
+    }
+    require_gte!(
+        MAX_FLASH_LOAN_FEE,
+        params.flash_loan_fee,
+        AdminError::InvalidInput
+    );
+    validate_weights(
+        params.initial_asset_weight,
+        params.maintenance_asset_weight,
+        params.initial_liability_weight,
+        params.maintenance_liability_weight,
+    )?;
+
+    let extensions = inspect_mint_extensions(&ctx.accounts.mint.to_account_info())?;
+    require_eq!(
+        extensions & !params.allowed_extensions,
+        0,
+        BankError::UnsupportedMintExtension
+    );
+
     let mut pool = ctx.accounts.pool.load_mut()?;
     let bank_id = pool.bank_count;
 
@@ -99,12 +141,15 @@ pub fn handle_initialize_bank(
     bank.pool_id = pool_id;
     bank.bank_id = bank_id;
     bank.status = BankStatus::Inactive as u8;
+    bank.status_updated_at = Clock::get()?.unix_timestamp;
 
     // Set bank weights
     bank.initial_asset_weight = params.initial_asset_weight;
     bank.maintenance_asset_weight = params.maintenance_asset_weight;
     bank.initial_liability_weight = params.initial_liability_weight;
     bank.maintenance_liability_weight = params.maintenance_liability_weight;
+    bank.allowed_extensions = params.allowed_extensions;
+    bank.flash_loan_fee = params.flash_loan_fee;
 
     // Emit event
     emit!(BankInitialized {
@@ -1,16 +1,66 @@
 use crate::protocol::{
-    state::{Admin, Pool},
+    state::{Admin, Pool, PoolStatus},
     AdminError, ADMIN_SEED, POOL_SEED, POOL_SPACE,
 };
 use anchor_lang::prelude::*;
 
+/// Upper bound for the pool liquidation bonus percentage
+pub const MAX_LIQUIDATION_BONUS: u8 = 50;
+/// Upper bound for positions per user, matching the user token balance slots
+pub const MAX_POSITIONS: u8 = 16;
+
 #[event]
 pub struct PoolInitialized {
     pub pool_count: u8,
     pub pool_id: u8,
+    pub authority: Pubkey,
+}
+
+/// Parameters for initializing a new pool
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolConfigParams {
+    /// Pool name, UTF-8 encoded and zero padded
+    pub name: [u8; 32],
+    /// Maximum percentage of a liability that can be repaid in a single liquidation
+    pub close_factor: u8,
+    /// Maximum liquidation bonus percentage paid to liquidators
+    pub max_liquidation_bonus: u8,
+    /// Maximum number of token positions a user can hold in this pool
+    pub max_positions: u8,
 }
 
-pub fn handle_initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
+/// Validates pool risk parameters shared by pool initialization and updates
+pub fn validate_pool_config(
+    close_factor: u8,
+    max_liquidation_bonus: u8,
+    max_positions: u8,
+) -> Result<()> {
+    require!(
+        close_factor > 0 && close_factor <= 100,
+        AdminError::InvalidInput
+    );
+    require_gte!(
+        MAX_LIQUIDATION_BONUS,
+        max_liquidation_bonus,
+        AdminError::InvalidInput
+    );
+    require!(
+        max_positions > 0 && max_positions <= MAX_POSITIONS,
+        AdminError::InvalidInput
+    );
+    Ok(())
+}
+
+pub fn handle_initialize_pool(
+    ctx: Context<InitializePool>,
+    params: PoolConfigParams,
+) -> Result<()> {
+    validate_pool_config(
+        params.close_factor,
+        params.max_liquidation_bonus,
+        params.max_positions,
+    )?;
+
     // Increment pool count in admin account
     let mut admin = ctx.accounts.admin.load_mut()?;
     let pool_id = admin.pool_count;
@@ -21,18 +71,26 @@ pub fn handle_initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
 
     // Initialize pool account
     let mut pool = ctx.accounts.pool.load_init()?;
+    pool.authority = ctx.accounts.pool_authority.key();
+    pool.name = params.name;
     pool.bank_count = 0;
+    pool.status = PoolStatus::Active as u8;
+    pool.close_factor = params.close_factor;
+    pool.max_liquidation_bonus = params.max_liquidation_bonus;
+    pool.max_positions = params.max_positions;
 
     // Log the event
     emit!(PoolInitialized {
         pool_count: admin.pool_count,
         pool_id,
+        authority: pool.authority,
     });
 
     msg!(
-        "Pool initialized successfully. Pool ID: {}, Total pools: {}",
+        "Pool initialized successfully. Pool ID: {}, Total pools: {}, authority: {}",
         pool_id,
-        admin.pool_count
+        admin.pool_count,
+        pool.authority
     );
 
     Ok(())
@@ -63,6 +121,9 @@ pub struct InitializePool<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    /// The authority that will manage the new pool
+    pub pool_authority: SystemAccount<'info>,
+
     /// System program for CPI
     pub system_program: Program<'info, System>,
 }
@@ -1,13 +1,39 @@
+mod accept_authority;
+mod cancel_authority;
+mod close_bank;
+mod force_settle;
+mod force_settle_liability;
+mod grant_role;
 mod initialize;
 mod initialize_bank;
 mod initialize_pool;
-mod update_authority;
+mod propose_authority;
+mod revoke_role;
+mod set_pool_pause;
+mod set_protocol_pause;
+mod sunset_bank;
 mod update_bank;
+mod update_bank_config;
+mod update_flash_loan_fee;
+mod update_pool;
 mod update_price;
 
+pub use accept_authority::*;
+pub use cancel_authority::*;
+pub use close_bank::*;
+pub use force_settle::*;
+pub use force_settle_liability::*;
+pub use grant_role::*;
 pub use initialize::*;
 pub use initialize_bank::*;
 pub use initialize_pool::*;
-pub use update_authority::*;
+pub use propose_authority::*;
+pub use revoke_role::*;
+pub use set_pool_pause::*;
+pub use set_protocol_pause::*;
+pub use sunset_bank::*;
 pub use update_bank::*;
+pub use update_bank_config::*;
+pub use update_flash_loan_fee::*;
+pub use update_pool::*;
 pub use update_price::*;
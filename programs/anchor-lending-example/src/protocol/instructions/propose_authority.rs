@@ -0,0 +1,45 @@
+use crate::protocol::{event::AdminAuthorityProposed, Admin, AdminError, ADMIN_SEED};
+use anchor_lang::prelude::*;
+
+/// Accounts required for proposing a new admin authority
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    /// The admin account to update
+    #[account(
+        mut,
+        seeds = [ADMIN_SEED],
+        bump,
+        has_one = authority @ AdminError::Unauthorized,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
+    /// The current authority that must sign
+    pub authority: Signer<'info>,
+
+    /// The authority proposed to take over admin privileges
+    pub new_authority: SystemAccount<'info>,
+}
+
+/// Propose a new admin authority
+/// The authority only changes once the proposed account accepts
+pub fn handle_propose_authority(ctx: Context<ProposeAuthority>) -> Result<()> {
+    let mut admin = ctx.accounts.admin.load_mut()?;
+
+    // Store the proposed authority until it is accepted
+    admin.pending_authority = ctx.accounts.new_authority.key();
+
+    msg!(
+        "Admin authority transfer proposed from {} to {}",
+        admin.authority,
+        admin.pending_authority
+    );
+
+    // Emit the proposal event
+    emit!(AdminAuthorityProposed {
+        admin: ctx.accounts.admin.key(),
+        authority: admin.authority,
+        pending_authority: admin.pending_authority,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::protocol::{event::AdminRoleRevoked, Admin, AdminError, AdminRole, ADMIN_SEED};
+use anchor_lang::prelude::*;
+
+/// Accounts required for revoking an admin role
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    /// The admin account to update
+    #[account(
+        mut,
+        seeds = [ADMIN_SEED],
+        bump,
+        has_one = authority @ AdminError::Unauthorized,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
+    /// The super-admin authority that must sign
+    pub authority: Signer<'info>,
+}
+
+/// Revoke a role from its current holder
+/// Can only be called by the super-admin authority
+pub fn handle_revoke_role(ctx: Context<RevokeRole>, role: u8) -> Result<()> {
+    let admin_role = AdminRole::try_from(role)?;
+    let mut admin = ctx.accounts.admin.load_mut()?;
+
+    let old_holder = admin.role_holder(admin_role);
+    admin.set_role_holder(admin_role, Pubkey::default());
+
+    msg!("Admin role {:?} revoked from {}", admin_role, old_holder);
+
+    emit!(AdminRoleRevoked {
+        admin: ctx.accounts.admin.key(),
+        role,
+        old_holder,
+    });
+
+    Ok(())
+}
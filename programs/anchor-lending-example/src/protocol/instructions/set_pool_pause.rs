@@ -0,0 +1,53 @@
+use crate::protocol::{event::PoolPauseUpdated, state::*, AdminError};
+use anchor_lang::prelude::*;
+
+/// Accounts required for updating pool pause flags
+#[derive(Accounts)]
+#[instruction(pool_id: u8)]
+pub struct SetPoolPause<'info> {
+    /// The admin account
+    #[account(
+        seeds = [ADMIN_SEED],
+        bump,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
+    /// The pool account holding the pause flags
+    #[account(
+        mut,
+        seeds = [POOL_SEED, &[pool_id][..]],
+        bump,
+        constraint = pool.load()?.authority == authority.key()
+            || admin.load()?.has_role(AdminRole::EmergencyGuardian, &authority.key())
+            @ AdminError::Unauthorized,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The pool authority, emergency guardian or admin authority that must sign
+    pub authority: Signer<'info>,
+}
+
+/// Handles replacing the pause flags of a pool
+pub fn handle_set_pool_pause(ctx: Context<SetPoolPause>, pool_id: u8, flags: u8) -> Result<()> {
+    // Input validation
+    require_eq!(flags & !PAUSE_ALL, 0, AdminError::InvalidInput);
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let old_flags = pool.pause_flags;
+    pool.pause_flags = flags;
+
+    emit!(PoolPauseUpdated {
+        pool_id,
+        authority: ctx.accounts.authority.key(),
+        old_flags,
+        new_flags: flags,
+    });
+
+    msg!(
+        "Pool {} pause flags updated from {:#05b} to {:#05b}",
+        pool_id,
+        old_flags,
+        flags
+    );
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::protocol::{event::ProtocolPauseUpdated, state::*, AdminError};
+use anchor_lang::prelude::*;
+
+/// Accounts required for updating protocol-wide pause flags
+#[derive(Accounts)]
+pub struct SetProtocolPause<'info> {
+    /// The admin account holding the protocol pause flags
+    #[account(
+        mut,
+        seeds = [ADMIN_SEED],
+        bump,
+        constraint = admin.load()?.has_role(AdminRole::EmergencyGuardian, &authority.key()) @ AdminError::Unauthorized,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
+    /// The emergency guardian or admin authority that must sign
+    pub authority: Signer<'info>,
+}
+
+/// Handles replacing the protocol-wide pause flags
+pub fn handle_set_protocol_pause(ctx: Context<SetProtocolPause>, flags: u8) -> Result<()> {
+    // Input validation
+    require_eq!(flags & !PAUSE_ALL, 0, AdminError::InvalidInput);
+
+    let mut admin = ctx.accounts.admin.load_mut()?;
+    let old_flags = admin.pause_flags;
+    admin.pause_flags = flags;
+
+    emit!(ProtocolPauseUpdated {
+        admin: ctx.accounts.admin.key(),
+        authority: ctx.accounts.authority.key(),
+        old_flags,
+        new_flags: flags,
+    });
+
+    msg!(
+        "Protocol pause flags updated from {:#05b} to {:#05b}",
+        old_flags,
+        flags
+    );
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::protocol::{event::BankSunsetStarted, state::*, AdminError};
+use anchor_lang::prelude::*;
+
+/// Start delisting a bank
+#[derive(Accounts)]
+pub struct SunsetBank<'info> {
+    /// The admin account
+    #[account(
+        seeds = [ADMIN_SEED],
+        bump,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
+    /// The pool the bank belongs to
+    #[account(
+        seeds = [POOL_SEED, &[bank.load()?.pool_id][..]],
+        bump,
+        constraint = pool.load()?.authority == authority.key()
+            || admin.load()?.has_role(AdminRole::RiskManager, &authority.key())
+            @ AdminError::Unauthorized,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The pool authority or risk manager that must sign
+    pub authority: Signer<'info>,
+
+    /// The bank account to sunset
+    #[account(mut)]
+    pub bank: AccountLoader<'info, Bank>,
+}
+
+/// Handles moving a bank into sunset with a settlement deadline
+pub fn handle_sunset_bank(ctx: Context<SunsetBank>, deadline: i64) -> Result<()> {
+    // Input validation
+    let now = Clock::get()?.unix_timestamp;
+    require_gt!(deadline, now, AdminError::InvalidInput);
+
+    let mut bank = ctx.accounts.bank.load_mut()?;
+    let old_status = bank.status;
+    bank.transition_status(BankStatus::Sunset, now)?;
+    bank.sunset_deadline = deadline;
+
+    emit!(BankSunsetStarted {
+        mint: bank.mint,
+        old_status,
+        deadline,
+    });
+
+    msg!(
+        "Bank sunset started for mint: {}, deadline: {}",
+        bank.mint,
+        deadline
+    );
+    Ok(())
+}
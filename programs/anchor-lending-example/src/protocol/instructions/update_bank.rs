@@ -1,4 +1,4 @@
-use crate::protocol::{event::BankStatusUpdated, state::*, AdminError};
+use crate::protocol::{event::BankStatusUpdated, state::*, AdminError, BankError};
 use anchor_lang::prelude::*;
 
 /// Update bank status
@@ -8,11 +8,17 @@ pub struct UpdateBankStatus<'info> {
     #[account(
         seeds = [ADMIN_SEED],
         bump,
-        has_one = authority @ AdminError::Unauthorized,
     )]
     pub admin: AccountLoader<'info, Admin>,
 
-    /// The authority that must sign
+    /// The pool the bank belongs to
+    #[account(
+        seeds = [POOL_SEED, &[bank.load()?.pool_id][..]],
+        bump,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The pool authority, risk manager or emergency guardian that must sign
     pub authority: Signer<'info>,
 
     /// The bank account to update
@@ -23,16 +29,38 @@ pub struct UpdateBankStatus<'info> {
 /// Handles updating the status of a bank
 pub fn handle_update_bank_status(ctx: Context<UpdateBankStatus>, new_status: u8) -> Result<()> {
     // Input validation
-    require_gte!(
-        BankStatus::ReduceOnly as u8,
-        new_status,
-        AdminError::InvalidInput
+    let next_status = BankStatus::try_from(new_status)?;
+
+    // Pool authority and risk manager may set any status, emergency guardian may only pause, reduce or freeze
+    let admin = ctx.accounts.admin.load()?;
+    let authority = ctx.accounts.authority.key();
+    if ctx.accounts.pool.load()?.authority != authority
+        && !admin.has_role(AdminRole::RiskManager, &authority)
+    {
+        require!(
+            admin.has_role(AdminRole::EmergencyGuardian, &authority),
+            AdminError::Unauthorized
+        );
+        require!(
+            matches!(
+                next_status,
+                BankStatus::Inactive | BankStatus::ReduceOnly | BankStatus::Frozen
+            ),
+            AdminError::Unauthorized
+        );
+    }
+
+    // Sunset needs a deadline and is entered through sunset_bank
+    require!(
+        next_status != BankStatus::Sunset,
+        BankError::InvalidStatusTransition
     );
 
-    // Load bank and update status
+    // Load bank and enforce the transition graph
     let mut bank = ctx.accounts.bank.load_mut()?;
     let old_status = bank.status;
-    bank.status = new_status;
+    let current_status = bank.status()?;
+    bank.transition_status(next_status, Clock::get()?.unix_timestamp)?;
 
     // Emit event
     emit!(BankStatusUpdated {
@@ -41,6 +69,11 @@ pub fn handle_update_bank_status(ctx: Context<UpdateBankStatus>, new_status: u8)
         new_status,
     });
 
-    msg!("Bank status updated for mint: {}", bank.mint);
+    msg!(
+        "Bank status updated for mint: {} from {:?} to {:?}",
+        bank.mint,
+        current_status,
+        next_status
+    );
     Ok(())
 }
@@ -0,0 +1,89 @@
+use crate::protocol::{event::BankConfigUpdated, state::*, AdminError};
+use anchor_lang::prelude::*;
+
+/// Parameters for updating the risk weights of a listed bank
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UpdateBankConfigParams {
+    /// Weight applied to assets for initial collateral ratio calculations
+    pub initial_asset_weight: u8,
+    /// Weight applied to assets for maintenance collateral ratio calculations
+    pub maintenance_asset_weight: u8,
+    /// Weight applied to liabilities for initial borrowing limits
+    pub initial_liability_weight: u8,
+    /// Weight applied to liabilities for maintenance requirements
+    pub maintenance_liability_weight: u8,
+}
+
+/// Update the risk weights of a bank
+#[derive(Accounts)]
+pub struct UpdateBankConfig<'info> {
+    /// The admin account
+    #[account(
+        seeds = [ADMIN_SEED],
+        bump,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
+    /// The pool the bank belongs to
+    #[account(
+        seeds = [POOL_SEED, &[bank.load()?.pool_id][..]],
+        bump,
+        constraint = pool.load()?.authority == authority.key()
+            || admin.load()?.has_role(AdminRole::RiskManager, &authority.key())
+            @ AdminError::Unauthorized,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The pool authority or risk manager that must sign
+    pub authority: Signer<'info>,
+
+    /// The bank account to update
+    #[account(
+        mut,
+        seeds = [
+            BANK_SEED,
+            &[bank.load()?.pool_id][..],
+            &[bank.load()?.bank_id][..],
+        ],
+        bump,
+    )]
+    pub bank: AccountLoader<'info, Bank>,
+}
+
+/// Handles updating the risk weights of a bank
+/// Weights are validated like at listing and apply to every open position immediately
+pub fn handle_update_bank_config(
+    ctx: Context<UpdateBankConfig>,
+    params: UpdateBankConfigParams,
+) -> Result<()> {
+    validate_weights(
+        params.initial_asset_weight,
+        params.maintenance_asset_weight,
+        params.initial_liability_weight,
+        params.maintenance_liability_weight,
+    )?;
+
+    let mut bank = ctx.accounts.bank.load_mut()?;
+    bank.initial_asset_weight = params.initial_asset_weight;
+    bank.maintenance_asset_weight = params.maintenance_asset_weight;
+    bank.initial_liability_weight = params.initial_liability_weight;
+    bank.maintenance_liability_weight = params.maintenance_liability_weight;
+
+    emit!(BankConfigUpdated {
+        mint: bank.mint,
+        initial_asset_weight: params.initial_asset_weight,
+        maintenance_asset_weight: params.maintenance_asset_weight,
+        initial_liability_weight: params.initial_liability_weight,
+        maintenance_liability_weight: params.maintenance_liability_weight,
+    });
+
+    msg!(
+        "Bank config updated for mint: {}, asset weights {}/{}, liability weights {}/{}",
+        bank.mint,
+        params.initial_asset_weight,
+        params.maintenance_asset_weight,
+        params.initial_liability_weight,
+        params.maintenance_liability_weight
+    );
+    Ok(())
+}
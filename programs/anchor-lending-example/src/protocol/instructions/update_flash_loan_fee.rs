@@ -0,0 +1,61 @@
+use crate::protocol::{event::FlashLoanFeeUpdated, state::*, AdminError};
+use anchor_lang::prelude::*;
+
+/// Update the flash loan fee of a bank
+#[derive(Accounts)]
+pub struct UpdateFlashLoanFee<'info> {
+    /// The admin account
+    #[account(
+        seeds = [ADMIN_SEED],
+        bump,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
+    /// The pool the bank belongs to
+    #[account(
+        seeds = [POOL_SEED, &[bank.load()?.pool_id][..]],
+        bump,
+        constraint = pool.load()?.authority == authority.key()
+            || admin.load()?.has_role(AdminRole::RiskManager, &authority.key())
+            @ AdminError::Unauthorized,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The pool authority or risk manager that must sign
+    pub authority: Signer<'info>,
+
+    /// The bank account to update
+    #[account(
+        mut,
+        seeds = [
+            BANK_SEED,
+            &[bank.load()?.pool_id][..],
+            &[bank.load()?.bank_id][..],
+        ],
+        bump,
+    )]
+    pub bank: AccountLoader<'info, Bank>,
+}
+
+/// Handles updating the flash loan fee of a bank
+pub fn handle_update_flash_loan_fee(ctx: Context<UpdateFlashLoanFee>, fee: u16) -> Result<()> {
+    require_gte!(MAX_FLASH_LOAN_FEE, fee, AdminError::InvalidInput);
+
+    let mut bank = ctx.accounts.bank.load_mut()?;
+    let old_fee = bank.flash_loan_fee;
+    bank.flash_loan_fee = fee;
+
+    emit!(FlashLoanFeeUpdated {
+        mint: bank.mint,
+        old_fee,
+        new_fee: fee,
+    });
+
+    msg!(
+        "Flash loan fee updated for mint: {} from {} to {} bps",
+        bank.mint,
+        old_fee,
+        fee
+    );
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use crate::protocol::{
+    event::PoolUpdated, instructions::validate_pool_config, state::*, AdminError,
+};
+use anchor_lang::prelude::*;
+
+/// Parameters for updating pool configuration
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UpdatePoolParams {
+    /// New operational status
+    pub status: u8,
+    /// Maximum percentage of a liability that can be repaid in a single liquidation
+    pub close_factor: u8,
+    /// Maximum liquidation bonus percentage paid to liquidators
+    pub max_liquidation_bonus: u8,
+    /// Maximum number of token positions a user can hold in this pool
+    pub max_positions: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_id: u8)]
+pub struct UpdatePool<'info> {
+    /// The pool account to update
+    #[account(
+        mut,
+        seeds = [POOL_SEED, &[pool_id][..]],
+        bump,
+        has_one = authority @ AdminError::Unauthorized,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The pool authority that must sign
+    pub authority: Signer<'info>,
+}
+
+/// Handles updating the configuration of a pool
+pub fn handle_update_pool(
+    ctx: Context<UpdatePool>,
+    pool_id: u8,
+    params: UpdatePoolParams,
+) -> Result<()> {
+    // Input validation
+    require_gte!(
+        PoolStatus::Active as u8,
+        params.status,
+        AdminError::InvalidInput
+    );
+    validate_pool_config(
+        params.close_factor,
+        params.max_liquidation_bonus,
+        params.max_positions,
+    )?;
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.status = params.status;
+    pool.close_factor = params.close_factor;
+    pool.max_liquidation_bonus = params.max_liquidation_bonus;
+    pool.max_positions = params.max_positions;
+
+    emit!(PoolUpdated {
+        pool_id,
+        authority: pool.authority,
+        status: pool.status,
+        close_factor: pool.close_factor,
+        max_liquidation_bonus: pool.max_liquidation_bonus,
+        max_positions: pool.max_positions,
+    });
+
+    msg!(
+        "Pool {} updated: status {}, close factor {}, max liquidation bonus {}, max positions {}",
+        pool_id,
+        pool.status,
+        pool.close_factor,
+        pool.max_liquidation_bonus,
+        pool.max_positions
+    );
+    Ok(())
+}
@@ -22,7 +22,9 @@ pub struct UpdatePriceParams {
 pub struct UpdatePrice<'info> {
     /// The admin account containing authority info
     #[account(
-        constraint = admin.load()?.authority == authority.key() @ AdminError::Unauthorized,
+        seeds = [ADMIN_SEED],
+        bump,
+        constraint = admin.load()?.has_role(AdminRole::OracleKeeper, &authority.key()) @ AdminError::Unauthorized,
     )]
     pub admin: AccountLoader<'info, Admin>,
 
@@ -30,7 +32,7 @@ pub struct UpdatePrice<'info> {
     #[account(mut)]
     pub bank: AccountLoader<'info, Bank>,
 
-    /// The authority that can update prices
+    /// The oracle keeper that can update prices
     pub authority: Signer<'info>,
 }
 
@@ -1,3 +1,4 @@
+use crate::protocol::{AdminError, BankError};
 use anchor_lang::prelude::*;
 use std::mem::size_of;
 
@@ -8,8 +9,83 @@ use std::mem::size_of;
 pub struct Admin {
     /// The authority pubkey that has admin privileges
     pub authority: Pubkey,
+    /// The authority proposed to take over admin privileges, default if none pending
+    pub pending_authority: Pubkey,
+    /// Account allowed to manage bank risk settings
+    pub risk_manager: Pubkey,
+    /// Account allowed to list new banks
+    pub listing_manager: Pubkey,
+    /// Account allowed to pause banks or set them to reduce-only
+    pub emergency_guardian: Pubkey,
+    /// Account allowed to update bank prices
+    pub oracle_keeper: Pubkey,
     /// Number of pools that have been initialized
     pub pool_count: u8,
+    /// Protocol-wide paused operations, see `PauseFlag`
+    pub pause_flags: u8,
+}
+
+impl Admin {
+    /// Gets the account currently holding a role
+    /// Returns the default pubkey if the role is not granted
+    pub fn role_holder(&self, role: AdminRole) -> Pubkey {
+        match role {
+            AdminRole::RiskManager => self.risk_manager,
+            AdminRole::ListingManager => self.listing_manager,
+            AdminRole::EmergencyGuardian => self.emergency_guardian,
+            AdminRole::OracleKeeper => self.oracle_keeper,
+        }
+    }
+
+    /// Sets the account holding a role
+    /// Use the default pubkey to revoke the role
+    pub fn set_role_holder(&mut self, role: AdminRole, holder: Pubkey) {
+        match role {
+            AdminRole::RiskManager => self.risk_manager = holder,
+            AdminRole::ListingManager => self.listing_manager = holder,
+            AdminRole::EmergencyGuardian => self.emergency_guardian = holder,
+            AdminRole::OracleKeeper => self.oracle_keeper = holder,
+        }
+    }
+
+    /// Checks whether an operation is paused protocol-wide
+    pub fn is_paused(&self, flag: PauseFlag) -> bool {
+        self.pause_flags & flag as u8 != 0
+    }
+
+    /// Checks whether an account may act with a role
+    /// The super-admin authority implicitly holds every role
+    pub fn has_role(&self, role: AdminRole, key: &Pubkey) -> bool {
+        *key == self.authority || (*key != Pubkey::default() && *key == self.role_holder(role))
+    }
+}
+
+/// Roles that can be delegated by the super-admin authority
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(u8)]
+pub enum AdminRole {
+    /// Manages bank weights, fees and status
+    RiskManager = 0,
+    /// Lists new banks
+    ListingManager = 1,
+    /// Pauses, freezes or sets banks to reduce-only during incidents
+    EmergencyGuardian = 2,
+    /// Updates bank prices
+    OracleKeeper = 3,
+}
+
+impl TryFrom<u8> for AdminRole {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(AdminRole::RiskManager),
+            1 => Ok(AdminRole::ListingManager),
+            2 => Ok(AdminRole::EmergencyGuardian),
+            3 => Ok(AdminRole::OracleKeeper),
+            _ => Err(error!(AdminError::InvalidInput)),
+        }
+    }
 }
 
 /// Pool account data  
@@ -17,8 +93,56 @@ pub struct Admin {
 #[repr(C)]
 #[derive(Default)]
 pub struct Pool {
+    /// The authority allowed to manage this pool
+    pub authority: Pubkey,
+    /// Pool name, UTF-8 encoded and zero padded
+    pub name: [u8; 32],
     /// Number of banks initialized in this pool
     pub bank_count: u8,
+    /// Current operational status
+    pub status: u8,
+    /// Maximum percentage of a liability that can be repaid in a single liquidation
+    pub close_factor: u8,
+    /// Maximum liquidation bonus percentage paid to liquidators
+    pub max_liquidation_bonus: u8,
+    /// Maximum number of token positions a user can hold in this pool
+    pub max_positions: u8,
+    /// Paused operations for this pool, see `PauseFlag`
+    pub pause_flags: u8,
+}
+
+impl Pool {
+    /// Checks whether an operation is paused for this pool
+    pub fn is_paused(&self, flag: PauseFlag) -> bool {
+        self.pause_flags & flag as u8 != 0
+    }
+}
+
+/// User operations that can be paused individually at protocol or pool level
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(u8)]
+pub enum PauseFlag {
+    /// Pause deposits, repayments stay open
+    Deposit = 1 << 0,
+    /// Pause withdrawals and borrows
+    Withdraw = 1 << 1,
+    /// Pause liquidations
+    Liquidate = 1 << 2,
+}
+
+/// All pause flags combined
+pub const PAUSE_ALL: u8 =
+    PauseFlag::Deposit as u8 | PauseFlag::Withdraw as u8 | PauseFlag::Liquidate as u8;
+
+/// Pool status indicating operational state
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+#[repr(u8)]
+pub enum PoolStatus {
+    #[default]
+    /// Pool is inactive, no new users or deposits allowed
+    Inactive = 0,
+    /// Pool is active and can be used for all operations
+    Active = 1,
 }
 
 /// Constants for account initialization
@@ -27,6 +151,36 @@ pub const ADMIN_SPACE: usize = 8 + size_of::<Admin>();
 pub const POOL_SEED: &[u8] = b"pool";
 pub const POOL_SPACE: usize = 8 + size_of::<Pool>();
 
+/// Token-2022 mint extensions that can break vault accounting, combined as bit flags
+/// Banks reject mints carrying any of these unless the admin authority allowlists them
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(u16)]
+pub enum MintExtension {
+    /// A delegate can move or burn tokens out of the vault
+    PermanentDelegate = 1 << 0,
+    /// Tokens cannot be transferred out of the vault
+    NonTransferable = 1 << 1,
+    /// Balances can move through confidential transfers the bank cannot see
+    ConfidentialTransfer = 1 << 2,
+    /// Transfers invoke an external program
+    TransferHook = 1 << 3,
+    /// New token accounts, including the vault, may start frozen
+    DefaultAccountState = 1 << 4,
+    /// The mint can be closed and recreated once supply reaches zero
+    MintCloseAuthority = 1 << 5,
+}
+
+/// All restricted mint extensions combined
+pub const ALL_MINT_EXTENSIONS: u16 = MintExtension::PermanentDelegate as u16
+    | MintExtension::NonTransferable as u16
+    | MintExtension::ConfidentialTransfer as u16
+    | MintExtension::TransferHook as u16
+    | MintExtension::DefaultAccountState as u16
+    | MintExtension::MintCloseAuthority as u16;
+
+/// Seconds a bank must stay frozen before its status can change again
+pub const FROZEN_TIMELOCK_SECONDS: i64 = 24 * 60 * 60;
+
 /// Bank status indicating operational state
 #[derive(Default, Clone, Copy, PartialEq, Debug)]
 #[repr(u8)]
@@ -38,6 +192,75 @@ pub enum BankStatus {
     Active = 1,
     /// Bank is in reduce-only mode - no new positions allowed
     ReduceOnly = 2,
+    /// Bank only accepts deposits and repayments, no funds can leave
+    DepositOnly = 3,
+    /// Bank is frozen during an incident, every operation is blocked
+    Frozen = 4,
+    /// Bank is being delisted, positions can only be closed
+    Sunset = 5,
+}
+
+/// User operations gated by bank status
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BankOperation {
+    /// Add collateral to the bank
+    Deposit,
+    /// Pay back an outstanding liability
+    Repay,
+    /// Remove collateral from the bank
+    Withdraw,
+    /// Open or grow a liability
+    Borrow,
+    /// Seize collateral from an unhealthy position
+    Liquidate,
+    /// Borrow from the vault and repay within the same transaction
+    FlashLoan,
+}
+
+impl BankStatus {
+    /// Checks whether an operation is allowed while the bank is in this status
+    pub fn allows(&self, op: BankOperation) -> bool {
+        use BankOperation::*;
+        match self {
+            BankStatus::Inactive | BankStatus::Frozen => false,
+            BankStatus::Active => true,
+            BankStatus::ReduceOnly | BankStatus::Sunset => {
+                matches!(op, Repay | Withdraw | Liquidate)
+            }
+            BankStatus::DepositOnly => matches!(op, Deposit | Repay),
+        }
+    }
+
+    /// Checks whether the bank may move from this status to the next one
+    /// Sunset is terminal, and a frozen bank must reopen through a restricted mode
+    /// Leaving Frozen is additionally subject to `FROZEN_TIMELOCK_SECONDS`
+    pub fn can_transition_to(&self, next: BankStatus) -> bool {
+        use BankStatus::*;
+        if *self == next {
+            return true;
+        }
+        match self {
+            Inactive | Active | ReduceOnly | DepositOnly => true,
+            Frozen => next != Active,
+            Sunset => false,
+        }
+    }
+}
+
+impl TryFrom<u8> for BankStatus {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(BankStatus::Inactive),
+            1 => Ok(BankStatus::Active),
+            2 => Ok(BankStatus::ReduceOnly),
+            3 => Ok(BankStatus::DepositOnly),
+            4 => Ok(BankStatus::Frozen),
+            5 => Ok(BankStatus::Sunset),
+            _ => Err(error!(AdminError::InvalidInput)),
+        }
+    }
 }
 
 /// Bank account data
@@ -63,6 +286,26 @@ pub struct Bank {
     /// Weight applied to liabilities for maintenance requirements
     pub maintenance_liability_weight: u8,
 
+    /// Restricted mint extensions allowlisted by the admin authority, see `MintExtension`
+    pub allowed_extensions: u16,
+    /// Flash loan fee in basis points of the borrowed amount
+    pub flash_loan_fee: u16,
+    pub padding: [u8; 4],
+
+    /// Unix timestamp of the last status change
+    pub status_updated_at: i64,
+    /// Unix timestamp after which a sunset bank can be force settled
+    pub sunset_deadline: i64,
+
+    /// Total collateral deposited by users
+    pub total_deposits: u64,
+    /// Total liabilities owed by users
+    pub total_liabilities: u64,
+    /// Flash loan repayment owed to the vault including the fee, only non-zero within a transaction
+    pub flash_loan_outstanding: u64,
+    /// Flash loan fees accrued to the vault
+    pub flash_loan_fees: u64,
+
     /// The token mint address
     pub mint: Pubkey,
     /// The price message
@@ -88,5 +331,102 @@ pub struct PriceFeedMessage {
     pub publish_time: i64,
 }
 
+impl Bank {
+    /// Gets the typed operational status
+    pub fn status(&self) -> Result<BankStatus> {
+        BankStatus::try_from(self.status)
+    }
+
+    /// Moves the bank to a new status, enforcing the transition graph and frozen timelock
+    pub fn transition_status(&mut self, next: BankStatus, now: i64) -> Result<()> {
+        let current = self.status()?;
+        require!(
+            current.can_transition_to(next),
+            BankError::InvalidStatusTransition
+        );
+
+        if current != next {
+            // A frozen bank stays frozen until the timelock has elapsed
+            if current == BankStatus::Frozen {
+                require_gte!(
+                    now,
+                    self.status_updated_at
+                        .checked_add(FROZEN_TIMELOCK_SECONDS)
+                        .ok_or(AdminError::Overflow)?,
+                    BankError::StatusTimelockActive
+                );
+            }
+            self.status_updated_at = now;
+        }
+
+        self.status = next as u8;
+        Ok(())
+    }
+
+    /// Applies the sunset decay to an asset weight
+    /// During Sunset the weight falls linearly from its configured value to zero at the deadline
+    pub fn asset_weight(&self, weight: u8, now: i64) -> u8 {
+        if self.status != BankStatus::Sunset as u8 {
+            return weight;
+        }
+        if now >= self.sunset_deadline {
+            return 0;
+        }
+
+        let remaining = (self.sunset_deadline - now.max(self.status_updated_at)) as i128;
+        let duration = (self.sunset_deadline - self.status_updated_at).max(1) as i128;
+        (weight as i128 * remaining / duration) as u8
+    }
+
+    /// Gets the tokens held by the vault that are not lent out
+    pub fn available_liquidity(&self) -> u64 {
+        self.total_deposits.saturating_sub(self.total_liabilities)
+    }
+
+    /// Calculates the flash loan fee owed on a borrowed amount, rounded up
+    pub fn flash_loan_fee_amount(&self, amount: u64) -> Result<u64> {
+        let fee = (amount as u128)
+            .checked_mul(self.flash_loan_fee as u128)
+            .ok_or(error!(AdminError::Overflow))?
+            .div_ceil(MAX_FLASH_LOAN_FEE as u128);
+        u64::try_from(fee).map_err(|_| error!(AdminError::Overflow))
+    }
+
+    /// Updates the deposit and liability totals after a user balance moved from `previous` to `new`
+    /// Balances are signed, with liabilities negative
+    pub fn record_balance_change(&mut self, previous: i64, new: i64) -> Result<()> {
+        let deposit_delta = new.max(0) as i128 - previous.max(0) as i128;
+        let liability_delta = (-new).max(0) as i128 - (-previous).max(0) as i128;
+
+        self.total_deposits = u64::try_from(self.total_deposits as i128 + deposit_delta)
+            .map_err(|_| error!(AdminError::Overflow))?;
+        self.total_liabilities = u64::try_from(self.total_liabilities as i128 + liability_delta)
+            .map_err(|_| error!(AdminError::Overflow))?;
+        Ok(())
+    }
+}
+
+/// Largest flash loan fee in basis points, the whole borrowed amount
+pub const MAX_FLASH_LOAN_FEE: u16 = 10_000;
+
+/// Checks bank weights, as percentages, before they are stored
+/// Assets may count for at most their value and liabilities for at least theirs,
+/// and maintenance weights may not be stricter than initial weights
+pub fn validate_weights(
+    initial_asset_weight: u8,
+    maintenance_asset_weight: u8,
+    initial_liability_weight: u8,
+    maintenance_liability_weight: u8,
+) -> Result<()> {
+    require!(
+        maintenance_asset_weight <= 100
+            && initial_asset_weight <= maintenance_asset_weight
+            && maintenance_liability_weight >= 100
+            && initial_liability_weight >= maintenance_liability_weight,
+        BankError::InvalidWeights
+    );
+    Ok(())
+}
+
 pub const BANK_SEED: &[u8] = b"bank";
 pub const BANK_SPACE: usize = 8 + size_of::<Bank>();
@@ -20,4 +20,32 @@ pub enum UserError {
     PositionHealthy,
     #[msg("Math operation overflow")]
     MathOverflow,
+    #[msg("Pool is inactive")]
+    PoolInactive,
+    #[msg("Reach max positions allowed by the pool")]
+    MaxPositions,
+    #[msg("Liquidation amount exceeds pool close factor")]
+    CloseFactorExceeded,
+    #[msg("Operation is paused protocol-wide")]
+    ProtocolPaused,
+    #[msg("Operation is paused for this pool")]
+    PoolPaused,
+    #[msg("Bank mint is not the native SOL mint")]
+    NotNativeMint,
+    #[msg("Liquidation must raise the position health ratio")]
+    LiquidationHealthNotImproved,
+    #[msg("Liquidation leaves the position healthier than the liquidation target")]
+    LiquidationOvershoot,
+    #[msg("No position in this bank to clear")]
+    NoPositionToClear,
+    #[msg("User account is in flashloan mode")]
+    InFlashloan,
+    #[msg("User account is not in flashloan mode")]
+    NotInFlashloan,
+    #[msg("Transaction must call end_flashloan for this user account")]
+    EndFlashloanMissing,
+    #[msg("User has no liability in the liability bank")]
+    NoLiabilityToRepay,
+    #[msg("User account already has the current layout")]
+    AlreadyMigrated,
 }
@@ -34,3 +34,73 @@ pub struct UserBalanceUpdated {
     /// Timestamp of the update
     pub timestamp: i64,
 }
+
+/// Event emitted when a new authority is proposed for a user account
+#[event]
+pub struct UserAuthorityProposed {
+    /// The user account address
+    #[index]
+    pub user: Pubkey,
+    /// The current authority
+    pub authority: Pubkey,
+    /// The proposed authority
+    pub pending_authority: Pubkey,
+}
+
+/// Event emitted when a user account is transferred to a new authority
+#[event]
+pub struct UserAuthorityTransferred {
+    /// The user account address
+    #[index]
+    pub user: Pubkey,
+    /// The old authority
+    pub old_authority: Pubkey,
+    /// The new authority
+    pub new_authority: Pubkey,
+}
+
+/// Event emitted when tokens are flash borrowed from a bank vault
+#[event]
+pub struct FlashLoanBorrowed {
+    /// The bank account address
+    #[index]
+    pub bank: Pubkey,
+    /// The borrower's wallet
+    pub borrower: Pubkey,
+    /// Amount borrowed
+    pub amount: u64,
+    /// Fee owed on top of the amount
+    pub fee: u64,
+}
+
+/// Event emitted when a flash loan is repaid to a bank vault
+#[event]
+pub struct FlashLoanRepaid {
+    /// The bank account address
+    #[index]
+    pub bank: Pubkey,
+    /// The repaying wallet
+    pub borrower: Pubkey,
+    /// Amount repaid including the fee
+    pub amount: u64,
+}
+
+/// Event emitted when a user account enters flashloan mode
+#[event]
+pub struct FlashloanStarted {
+    /// The user account address
+    #[index]
+    pub user: Pubkey,
+    /// The authority that started the flashloan
+    pub authority: Pubkey,
+}
+
+/// Event emitted when a user account leaves flashloan mode with a healthy position
+#[event]
+pub struct FlashloanEnded {
+    /// The user account address
+    #[index]
+    pub user: Pubkey,
+    /// The authority that ended the flashloan
+    pub authority: Pubkey,
+}
@@ -0,0 +1,42 @@
+use crate::user::{state::User, UserAuthorityTransferred, UserError};
+use anchor_lang::prelude::*;
+
+/// Accounts required for accepting a proposed user account authority
+#[derive(Accounts)]
+pub struct AcceptUserAuthority<'info> {
+    /// The user account to transfer
+    #[account(
+        mut,
+        constraint = user_account.load()?.pending_authority == new_authority.key() @ UserError::InvalidAuthority,
+    )]
+    pub user_account: AccountLoader<'info, User>,
+
+    /// The proposed authority that must sign to accept the transfer
+    pub new_authority: Signer<'info>,
+}
+
+/// Accept a pending authority transfer for the user account
+/// All token balances move to the new authority without any token transfer
+pub fn handle_accept_user_authority(ctx: Context<AcceptUserAuthority>) -> Result<()> {
+    let mut user_account = ctx.accounts.user_account.load_mut()?;
+    let old_authority = user_account.authority;
+
+    // Hand over the account and clear the pending proposal
+    user_account.authority = ctx.accounts.new_authority.key();
+    user_account.pending_authority = Pubkey::default();
+
+    msg!(
+        "User {} authority transferred from {} to {}",
+        ctx.accounts.user_account.key(),
+        old_authority,
+        user_account.authority
+    );
+
+    emit!(UserAuthorityTransferred {
+        user: ctx.accounts.user_account.key(),
+        old_authority,
+        new_authority: user_account.authority,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::{
+    controller::introspection::{has_later_instruction, load_top_level_index},
+    instruction,
+    user::{state::User, FlashloanStarted, UserError, END_FLASHLOAN_USER_INDEX},
+};
+use anchor_lang::{
+    prelude::*, solana_program::sysvar::instructions as instructions_sysvar, Discriminator,
+};
+
+/// Accounts required for starting flashloan mode on a user account
+#[derive(Accounts)]
+pub struct BeginFlashloan<'info> {
+    /// The user account to defer health checks for
+    #[account(
+        mut,
+        constraint = user_account.load()?.authority == authority.key() @ UserError::InvalidAuthority,
+    )]
+    pub user_account: AccountLoader<'info, User>,
+
+    /// The user authority that must sign
+    pub authority: Signer<'info>,
+
+    /// Instructions sysvar used to find the closing end_flashloan
+    /// CHECK: address is checked against the instructions sysvar id
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Put a user account in flashloan mode
+/// Withdrawals and borrows skip health checks until the end_flashloan that must follow
+/// in the same transaction, which requires the final position to be healthy
+pub fn handle_begin_flashloan(ctx: Context<BeginFlashloan>) -> Result<()> {
+    let user_key = ctx.accounts.user_account.key();
+    let mut user_account = ctx.accounts.user_account.load_mut()?;
+    require!(!user_account.in_flashloan(), UserError::InFlashloan);
+
+    // Health is only enforced if the transaction is guaranteed to reach end_flashloan
+    let instructions = ctx.accounts.instructions.to_account_info();
+    let current_index = load_top_level_index(&instructions)?;
+    let ended = has_later_instruction(
+        &instructions,
+        current_index,
+        &instruction::EndFlashloan::DISCRIMINATOR,
+        |ix| {
+            Ok(ix
+                .accounts
+                .get(END_FLASHLOAN_USER_INDEX)
+                .is_some_and(|meta| meta.pubkey == user_key))
+        },
+    )?;
+    require!(ended, UserError::EndFlashloanMissing);
+
+    user_account.flashloan = 1;
+
+    msg!(
+        "User {} flashloan started by {}",
+        user_key,
+        ctx.accounts.authority.key()
+    );
+
+    emit!(FlashloanStarted {
+        user: user_key,
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
@@ -1,14 +1,15 @@
 use crate::{
-    controller::token::TokenInstructionInterface,
+    controller::token::{calculate_gross_amount, TokenInstructionInterface},
     protocol::{
-        state::{Bank, BankStatus},
-        BankError, BANK_SEED,
+        state::{Admin, Bank, BankOperation, PauseFlag, Pool, PoolStatus},
+        BankError, ADMIN_SEED, BANK_SEED, POOL_SEED,
     },
     user::event::UserBalanceUpdated,
     user::state::{Direction, User},
+    user::UserError,
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 /// Instruction context for depositing tokens
 #[derive(Accounts)]
@@ -58,6 +59,24 @@ pub struct Deposit<'info> {
     )]
     pub bank: AccountLoader<'info, Bank>,
 
+    /// Pool the bank belongs to
+    #[account(
+        seeds = [POOL_SEED, &[bank.load()?.pool_id][..]],
+        bump,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// Admin account holding protocol-wide pause flags
+    #[account(
+        seeds = [ADMIN_SEED],
+        bump,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
+    /// Mint of the bank token, required for transfer_checked
+    #[account(address = bank.load()?.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
     /// Token program, either Token or Token2022
     pub token_program: Interface<'info, TokenInterface>,
 }
@@ -65,40 +84,140 @@ pub struct Deposit<'info> {
 pub fn handle_deposit<'info>(
     ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
     amount: u64,
+    all: bool,
 ) -> Result<()> {
-    let bank = ctx.accounts.bank.load()?;
-    let mut user_account = ctx.accounts.user_account.load_mut()?;
-
-    // Check bank status - only allow deposits when Active
-    require_eq!(
-        bank.status,
-        BankStatus::Active as u8,
-        BankError::BankInactive
-    );
+    let token_interface =
+        TokenInstructionInterface::load(&ctx.accounts.token_program, ctx.remaining_accounts)?;
+
+    process_deposit(
+        DepositAccounts {
+            user: ctx.accounts.user.to_account_info(),
+            source_token_account: ctx.accounts.user_token_account.to_account_info(),
+            bank_token_account: ctx.accounts.bank_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            user_account: &ctx.accounts.user_account,
+            bank: &ctx.accounts.bank,
+            pool: &ctx.accounts.pool,
+            admin: &ctx.accounts.admin,
+        },
+        &token_interface,
+        amount,
+        all,
+    )
+}
+
+/// Accounts shared by token and native deposits
+pub(crate) struct DepositAccounts<'a, 'info> {
+    pub user: AccountInfo<'info>,
+    pub source_token_account: AccountInfo<'info>,
+    pub bank_token_account: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub user_account: &'a AccountLoader<'info, User>,
+    pub bank: &'a AccountLoader<'info, Bank>,
+    pub pool: &'a AccountLoader<'info, Pool>,
+    pub admin: &'a AccountLoader<'info, Admin>,
+}
+
+/// Checks pause flags and bank status, transfers from the source token account
+/// and credits the user with the amount the vault received
+/// With `all` set, `amount` is ignored and the whole liability is repaid and cleared
+pub(crate) fn process_deposit<'info>(
+    accounts: DepositAccounts<'_, 'info>,
+    token_interface: &TokenInstructionInterface<'info>,
+    amount: u64,
+    all: bool,
+) -> Result<()> {
+    let bank = *accounts.bank.load()?;
+    let pool = accounts.pool.load()?;
+    let mut user_account = accounts.user_account.load_mut()?;
 
     let previous_balance = user_account.find_balance_by_bank_id(bank.bank_id);
-    let previous_asset_type = user_account.get_balance_type_by_bank_id(bank.bank_id);
 
-    let token_interface =
-        TokenInstructionInterface::load(&ctx.accounts.token_program, ctx.remaining_accounts)?;
+    // Repaying all sends enough for the vault to receive the liability after transfer fees
+    let amount = if all {
+        require!(previous_balance < 0, UserError::NoPositionToClear);
+        calculate_gross_amount(&accounts.mint, previous_balance.unsigned_abs())?
+    } else {
+        amount
+    };
+
+    // Paying down an existing liability is a repayment
+    let operation = if all || (previous_balance < 0 && amount <= previous_balance.unsigned_abs()) {
+        BankOperation::Repay
+    } else {
+        BankOperation::Deposit
+    };
+
+    // Check pool status and pause flags before bank status
+    // Only new collateral is gated, repayments are always accepted so borrowers can reduce debt
+    if operation == BankOperation::Deposit {
+        require_eq!(
+            pool.status,
+            PoolStatus::Active as u8,
+            UserError::PoolInactive
+        );
+        require!(
+            !accounts.admin.load()?.is_paused(PauseFlag::Deposit),
+            UserError::ProtocolPaused
+        );
+        require!(!pool.is_paused(PauseFlag::Deposit), UserError::PoolPaused);
+    }
+
+    // Check bank status
+    require!(
+        bank.status()?.allows(operation),
+        BankError::OperationNotAllowed
+    );
 
-    token_interface.transfer(
-        ctx.accounts.user_token_account.to_account_info(),
-        ctx.accounts.bank_token_account.to_account_info(),
-        ctx.accounts.user.to_account_info(),
+    let previous_asset_type = user_account.get_balance_type_by_bank_id(bank.bank_id);
+
+    // Credit only what the vault actually receives after any transfer fee
+    let received_amount = token_interface.transfer(
+        accounts.source_token_account,
+        accounts.mint,
+        accounts.bank_token_account,
+        accounts.user.clone(),
         amount,
+        bank.decimals,
     )?;
 
+    // Repaying all credits at most the liability so fee rounding cannot open collateral,
+    // any excess stays in the vault
+    let credited_amount = if all {
+        received_amount.min(previous_balance.unsigned_abs())
+    } else {
+        received_amount
+    };
+
     // Use Direction::Deposit for adding collateral
-    user_account.update_balance(bank.bank_id, amount, Direction::Deposit)?;
+    let previous_positions = user_account.position_count();
+    user_account.update_balance(bank.bank_id, credited_amount, Direction::Deposit)?;
+
+    // New positions must stay within the pool limit
+    if user_account.position_count() > previous_positions {
+        require_gte!(
+            pool.max_positions,
+            user_account.position_count(),
+            UserError::MaxPositions
+        );
+    }
+
+    if all {
+        user_account.clear_balance(bank.bank_id);
+    }
 
     let new_balance = user_account.find_balance_by_bank_id(bank.bank_id);
     let new_asset_type = user_account.get_balance_type_by_bank_id(bank.bank_id);
 
+    accounts
+        .bank
+        .load_mut()?
+        .record_balance_change(previous_balance, new_balance)?;
+
     let clock = Clock::get()?;
 
     emit!(UserBalanceUpdated {
-        user: ctx.accounts.user_account.key(),
+        user: accounts.user_account.key(),
         token_id: bank.bank_id,
         previous_balance: previous_balance.unsigned_abs(), // Convert to u64 for event
         previous_asset_type,
@@ -108,9 +227,10 @@ pub fn handle_deposit<'info>(
     });
 
     msg!(
-        "Deposit completed: amount {} deposited for user {}, new balance: {} ({:?})",
+        "Deposit completed: amount {} deposited ({} received) for user {}, new balance: {} ({:?})",
         amount,
-        ctx.accounts.user.key(),
+        received_amount,
+        accounts.user.key(),
         new_balance,
         new_asset_type
     );
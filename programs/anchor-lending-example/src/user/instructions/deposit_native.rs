@@ -0,0 +1,164 @@
+use crate::{
+    controller::token::TokenInstructionInterface,
+    protocol::{
+        state::{Admin, Bank, Pool},
+        ADMIN_SEED, BANK_SEED, POOL_SEED,
+    },
+    user::{
+        instructions::deposit::{process_deposit, DepositAccounts},
+        state::User,
+        UserError,
+    },
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+};
+use anchor_spl::{
+    token::spl_token::native_mint,
+    token_2022::spl_token_2022::native_mint as native_mint_2022,
+    token_interface::{self, CloseAccount, Mint, SyncNative, TokenAccount, TokenInterface},
+};
+
+/// Instruction context for depositing native SOL into a wrapped SOL bank
+#[derive(Accounts)]
+pub struct DepositNative<'info> {
+    /// User's wallet that must sign and pays the lamports
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Temporary wrapped SOL account, closed back to the user after the deposit
+    #[account(
+        init,
+        payer = user,
+        seeds = [
+            b"native_token_account",
+            user_account.key().as_ref(),
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub temporary_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Bank's token account to deposit to
+    #[account(
+        mut,
+        seeds = [
+            b"token_account",
+            bank.key().as_ref(),
+        ],
+        bump,
+        token::authority = bank,
+        token::mint = bank.load()?.mint,
+    )]
+    pub bank_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// User account to update balance
+    #[account(
+        mut,
+        constraint = user_account.load()?.authority == user.key(),
+        constraint = user_account.load()?.pool_id == bank.load()?.pool_id,
+    )]
+    pub user_account: AccountLoader<'info, User>,
+
+    /// Bank account to validate status
+    #[account(
+        mut,
+        seeds = [
+            BANK_SEED,
+            &[bank.load()?.pool_id][..],
+            &[bank.load()?.bank_id][..],
+        ],
+        bump,
+    )]
+    pub bank: AccountLoader<'info, Bank>,
+
+    /// Pool the bank belongs to
+    #[account(
+        seeds = [POOL_SEED, &[bank.load()?.pool_id][..]],
+        bump,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// Admin account holding protocol-wide pause flags
+    #[account(
+        seeds = [ADMIN_SEED],
+        bump,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
+    /// Native mint of the bank, required for transfer_checked
+    #[account(
+        address = bank.load()?.mint,
+        constraint = mint.key() == native_mint::ID || mint.key() == native_mint_2022::ID
+            @ UserError::NotNativeMint,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program, either Token or Token2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program to fund the temporary account
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_deposit_native<'info>(
+    ctx: Context<'_, '_, '_, 'info, DepositNative<'info>>,
+    amount: u64,
+) -> Result<()> {
+    // Wrap the lamports into the temporary account
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.temporary_token_account.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    token_interface::sync_native(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        SyncNative {
+            account: ctx.accounts.temporary_token_account.to_account_info(),
+        },
+    ))?;
+
+    let token_interface =
+        TokenInstructionInterface::load(&ctx.accounts.token_program, ctx.remaining_accounts)?;
+
+    process_deposit(
+        DepositAccounts {
+            user: ctx.accounts.user.to_account_info(),
+            source_token_account: ctx.accounts.temporary_token_account.to_account_info(),
+            bank_token_account: ctx.accounts.bank_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            user_account: &ctx.accounts.user_account,
+            bank: &ctx.accounts.bank,
+            pool: &ctx.accounts.pool,
+            admin: &ctx.accounts.admin,
+        },
+        &token_interface,
+        amount,
+        false,
+    )?;
+
+    // Return the rent of the emptied temporary account to the user
+    token_interface::close_account(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.temporary_token_account.to_account_info(),
+            destination: ctx.accounts.user.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    ))?;
+
+    msg!(
+        "Native deposit completed: {} lamports wrapped for user {}",
+        amount,
+        ctx.accounts.user.key()
+    );
+    Ok(())
+}
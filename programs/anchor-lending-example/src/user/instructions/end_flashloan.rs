@@ -0,0 +1,52 @@
+use crate::{
+    controller::RiskEngine,
+    user::{state::User, FlashloanEnded, UserError},
+};
+use anchor_lang::prelude::*;
+
+/// Position of the user account in the end_flashloan accounts, found by begin_flashloan
+pub(crate) const END_FLASHLOAN_USER_INDEX: usize = 0;
+
+/// Accounts required for leaving flashloan mode
+/// The banks of every open position are passed as remaining accounts
+#[derive(Accounts)]
+pub struct EndFlashloan<'info> {
+    /// The user account in flashloan mode
+    #[account(
+        mut,
+        constraint = user_account.load()?.authority == authority.key() @ UserError::InvalidAuthority,
+    )]
+    pub user_account: AccountLoader<'info, User>,
+
+    /// The user authority that must sign
+    pub authority: Signer<'info>,
+}
+
+/// Leave flashloan mode, requiring the final position to pass the initial health check
+pub fn handle_end_flashloan<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, EndFlashloan<'info>>,
+) -> Result<()> {
+    let mut user_account = ctx.accounts.user_account.load_mut()?;
+    require!(user_account.in_flashloan(), UserError::NotInFlashloan);
+
+    let bank_ids = user_account.open_bank_ids();
+
+    // A single check covers every change made during the flashloan
+    let risk_engine = RiskEngine::load(user_account.pool_id, bank_ids, ctx.remaining_accounts)?;
+    risk_engine.check_initial_health(&user_account.token_balances)?;
+
+    user_account.flashloan = 0;
+
+    msg!(
+        "User {} flashloan ended by {}",
+        ctx.accounts.user_account.key(),
+        ctx.accounts.authority.key()
+    );
+
+    emit!(FlashloanEnded {
+        user: ctx.accounts.user_account.key(),
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use crate::{
+    controller::{
+        introspection::{has_later_instruction, load_top_level_index},
+        token::TokenInstructionInterface,
+    },
+    instruction,
+    protocol::{
+        state::{Admin, Bank, BankOperation, PauseFlag, Pool},
+        BankError, ADMIN_SEED, BANK_SEED, POOL_SEED,
+    },
+    user::{
+        event::FlashLoanBorrowed, instructions::flash_repay::FLASH_REPAY_BANK_INDEX, UserError,
+    },
+};
+use anchor_lang::{
+    prelude::*, solana_program::sysvar::instructions as instructions_sysvar, Discriminator,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Instruction context for flash borrowing from a bank vault
+#[derive(Accounts)]
+pub struct FlashBorrow<'info> {
+    /// Borrower's wallet that must sign
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Borrower's token account to receive the loan
+    #[account(
+        mut,
+        token::authority = user,
+        token::mint = bank.load()?.mint,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Bank's token account to lend from
+    #[account(
+        mut,
+        seeds = [
+            b"token_account",
+            bank.key().as_ref(),
+        ],
+        bump,
+        token::authority = bank,
+        token::mint = bank.load()?.mint,
+    )]
+    pub bank_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Bank account to validate status and sign token transfer
+    #[account(
+        mut,
+        seeds = [
+            BANK_SEED,
+            &[bank.load()?.pool_id][..],
+            &[bank.load()?.bank_id][..],
+        ],
+        bump,
+    )]
+    pub bank: AccountLoader<'info, Bank>,
+
+    /// Pool the bank belongs to
+    #[account(
+        seeds = [POOL_SEED, &[bank.load()?.pool_id][..]],
+        bump,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// Admin account holding protocol-wide pause flags
+    #[account(
+        seeds = [ADMIN_SEED],
+        bump,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
+    /// Mint of the bank token, required for transfer_checked
+    #[account(address = bank.load()?.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program, either Token or Token2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Instructions sysvar, used to find the matching repayment
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Lends tokens from a bank vault for the rest of the transaction
+/// A `flash_repay` of the amount plus the bank's fee to the same bank must follow
+/// later in the transaction, and the fee is accrued to the bank
+pub fn handle_flash_borrow<'info>(
+    ctx: Context<'_, '_, '_, 'info, FlashBorrow<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let token_interface =
+        TokenInstructionInterface::load(&ctx.accounts.token_program, ctx.remaining_accounts)?;
+    let bank = *ctx.accounts.bank.load()?;
+
+    // Check protocol and pool pause flags before bank status
+    require!(
+        !ctx.accounts.admin.load()?.is_paused(PauseFlag::Withdraw),
+        UserError::ProtocolPaused
+    );
+    require!(
+        !ctx.accounts.pool.load()?.is_paused(PauseFlag::Withdraw),
+        UserError::PoolPaused
+    );
+    require!(
+        bank.status()?.allows(BankOperation::FlashLoan),
+        BankError::OperationNotAllowed
+    );
+
+    // One loan per bank at a time, so a repayment cannot be matched by two loans
+    require_eq!(bank.flash_loan_outstanding, 0, BankError::FlashLoanActive);
+
+    let fee = bank.flash_loan_fee_amount(amount)?;
+    let repay_amount = amount
+        .checked_add(fee)
+        .ok_or(error!(UserError::MathOverflow))?;
+    require_repayment(
+        &ctx.accounts.instructions,
+        &ctx.accounts.bank.key(),
+        repay_amount,
+    )?;
+
+    {
+        let mut bank = ctx.accounts.bank.load_mut()?;
+        bank.flash_loan_outstanding = repay_amount;
+        bank.flash_loan_fees = bank
+            .flash_loan_fees
+            .checked_add(fee)
+            .ok_or(error!(UserError::MathOverflow))?;
+    }
+
+    let bank_seeds = &[
+        BANK_SEED,
+        &[bank.pool_id][..],
+        &[bank.bank_id][..],
+        &[ctx.bumps.bank][..],
+    ];
+
+    token_interface.transfer_with_signer(
+        ctx.accounts.bank_token_account.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.user_token_account.to_account_info(),
+        ctx.accounts.bank.to_account_info(),
+        amount,
+        bank.decimals,
+        bank_seeds,
+    )?;
+
+    emit!(FlashLoanBorrowed {
+        bank: ctx.accounts.bank.key(),
+        borrower: ctx.accounts.user.key(),
+        amount,
+        fee,
+    });
+
+    msg!(
+        "Flash loan borrowed: amount {} with fee {} from bank {} by {}",
+        amount,
+        fee,
+        bank.bank_id,
+        ctx.accounts.user.key()
+    );
+    Ok(())
+}
+
+/// Requires the transaction to call `flash_repay` for `repay_amount` to the bank after
+/// the current instruction, which must itself be called directly by the transaction
+fn require_repayment(instructions: &AccountInfo, bank: &Pubkey, repay_amount: u64) -> Result<()> {
+    let current_index = load_top_level_index(instructions)?;
+    let repaid = has_later_instruction(
+        instructions,
+        current_index,
+        &instruction::FlashRepay::DISCRIMINATOR,
+        |ix| {
+            let repays_bank = ix
+                .accounts
+                .get(FLASH_REPAY_BANK_INDEX)
+                .is_some_and(|meta| meta.pubkey == *bank);
+            let args = instruction::FlashRepay::try_from_slice(&ix.data[8..])?;
+            Ok(repays_bank && args.amount == repay_amount)
+        },
+    )?;
+    require!(repaid, BankError::FlashRepayMissing);
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use crate::{
+    controller::token::{calculate_gross_amount, TokenInstructionInterface},
+    protocol::{state::Bank, BankError, BANK_SEED},
+    user::event::FlashLoanRepaid,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Position of the bank in the `FlashRepay` accounts, read by `flash_borrow`
+pub(crate) const FLASH_REPAY_BANK_INDEX: usize = 3;
+
+/// Instruction context for repaying a flash loan
+#[derive(Accounts)]
+pub struct FlashRepay<'info> {
+    /// Wallet repaying the loan that must sign
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Token account to repay from
+    #[account(
+        mut,
+        token::authority = user,
+        token::mint = bank.load()?.mint,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Bank's token account receiving the repayment
+    #[account(
+        mut,
+        seeds = [
+            b"token_account",
+            bank.key().as_ref(),
+        ],
+        bump,
+        token::authority = bank,
+        token::mint = bank.load()?.mint,
+    )]
+    pub bank_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Bank the loan was taken from
+    #[account(
+        mut,
+        seeds = [
+            BANK_SEED,
+            &[bank.load()?.pool_id][..],
+            &[bank.load()?.bank_id][..],
+        ],
+        bump,
+    )]
+    pub bank: AccountLoader<'info, Bank>,
+
+    /// Mint of the bank token, required for transfer_checked
+    #[account(address = bank.load()?.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program, either Token or Token2022
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Repays a flash loan, `amount` must be the borrowed amount plus the fee
+/// Repayment is never paused so a borrowed vault can always be made whole
+pub fn handle_flash_repay<'info>(
+    ctx: Context<'_, '_, '_, 'info, FlashRepay<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let token_interface =
+        TokenInstructionInterface::load(&ctx.accounts.token_program, ctx.remaining_accounts)?;
+    let bank = *ctx.accounts.bank.load()?;
+
+    require_neq!(bank.flash_loan_outstanding, 0, BankError::NoFlashLoan);
+    require_eq!(
+        amount,
+        bank.flash_loan_outstanding,
+        BankError::InvalidFlashRepayAmount
+    );
+
+    // The vault must receive the full repayment after any transfer fee
+    let mint = ctx.accounts.mint.to_account_info();
+    let gross_amount = calculate_gross_amount(&mint, amount)?;
+    let received_amount = token_interface.transfer(
+        ctx.accounts.user_token_account.to_account_info(),
+        mint,
+        ctx.accounts.bank_token_account.to_account_info(),
+        ctx.accounts.user.to_account_info(),
+        gross_amount,
+        bank.decimals,
+    )?;
+    require_gte!(received_amount, amount, BankError::InvalidFlashRepayAmount);
+
+    ctx.accounts.bank.load_mut()?.flash_loan_outstanding = 0;
+
+    emit!(FlashLoanRepaid {
+        bank: ctx.accounts.bank.key(),
+        borrower: ctx.accounts.user.key(),
+        amount,
+    });
+
+    msg!(
+        "Flash loan repaid: amount {} ({} sent) to bank {} by {}",
+        amount,
+        gross_amount,
+        bank.bank_id,
+        ctx.accounts.user.key()
+    );
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::{
+    controller::RiskEngine,
+    protocol::{state::Bank, BANK_SEED},
+    user::state::User,
+};
+use anchor_lang::prelude::*;
+
+/// Accounts required to query the price at which a position becomes liquidatable
+/// The banks of every open position and the queried bank are passed as remaining accounts
+#[derive(Accounts)]
+pub struct GetLiquidationPrice<'info> {
+    /// The user account to value
+    #[account(
+        constraint = user_account.load()?.pool_id == bank.load()?.pool_id,
+    )]
+    pub user_account: AccountLoader<'info, User>,
+
+    /// Bank whose token price is solved for
+    #[account(
+        seeds = [
+            BANK_SEED,
+            &[bank.load()?.pool_id][..],
+            &[bank.load()?.bank_id][..],
+        ],
+        bump,
+    )]
+    pub bank: AccountLoader<'info, Bank>,
+}
+
+/// Get the price of a bank's token at which the position becomes liquidatable, holding
+/// every other price constant
+/// The price uses the bank's price feed units and exponent, None when no price of this
+/// token makes the position liquidatable
+/// Read-only, meant to be simulated by clients reading the return data
+pub fn handle_get_liquidation_price<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, GetLiquidationPrice<'info>>,
+) -> Result<Option<u64>> {
+    let bank = ctx.accounts.bank.load()?;
+    let user_account = ctx.accounts.user_account.load()?;
+
+    // Open positions and the queried bank
+    let mut bank_ids = user_account.open_bank_ids();
+    if !bank_ids.contains(&bank.bank_id) {
+        bank_ids.push(bank.bank_id);
+    }
+
+    let risk_engine = RiskEngine::load(user_account.pool_id, bank_ids, ctx.remaining_accounts)?;
+    let price = risk_engine.liquidation_price(&user_account.token_balances, bank.bank_id)?;
+
+    msg!(
+        "User {} liquidation price in bank {}: {:?}",
+        ctx.accounts.user_account.key(),
+        bank.bank_id,
+        price
+    );
+
+    Ok(price)
+}
@@ -0,0 +1,62 @@
+use crate::{
+    controller::RiskEngine,
+    protocol::{state::Bank, BANK_SEED},
+    user::state::User,
+};
+use anchor_lang::prelude::*;
+
+/// Accounts required to query the largest borrow from a bank
+/// The banks of every open position and the queried bank are passed as remaining accounts
+#[derive(Accounts)]
+pub struct GetMaxBorrow<'info> {
+    /// The user account to value
+    #[account(
+        constraint = user_account.load()?.pool_id == bank.load()?.pool_id,
+    )]
+    pub user_account: AccountLoader<'info, User>,
+
+    /// Bank to borrow from
+    #[account(
+        seeds = [
+            BANK_SEED,
+            &[bank.load()?.pool_id][..],
+            &[bank.load()?.bank_id][..],
+        ],
+        bump,
+    )]
+    pub bank: AccountLoader<'info, Bank>,
+}
+
+/// Get the largest liability a position can open in a bank under initial weights
+/// Collateral held in the bank is assumed withdrawn first, so a single withdrawal of
+/// the max withdraw and max borrow amounts is the largest the position allows
+/// Capped by the bank's liquidity left after the collateral withdrawal
+/// Read-only, meant to be simulated by clients reading the return data
+pub fn handle_get_max_borrow<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, GetMaxBorrow<'info>>,
+) -> Result<u64> {
+    let bank = ctx.accounts.bank.load()?;
+    let user_account = ctx.accounts.user_account.load()?;
+
+    // Open positions and the queried bank
+    let mut bank_ids = user_account.open_bank_ids();
+    if !bank_ids.contains(&bank.bank_id) {
+        bank_ids.push(bank.bank_id);
+    }
+
+    // The user's own collateral leaves the vault before the borrow
+    let collateral = user_account.find_balance_by_bank_id(bank.bank_id).max(0) as u64;
+    let liquidity = bank.available_liquidity().saturating_sub(collateral);
+
+    let risk_engine = RiskEngine::load(user_account.pool_id, bank_ids, ctx.remaining_accounts)?;
+    let amount = risk_engine.max_borrow(&user_account.token_balances, bank.bank_id, liquidity)?;
+
+    msg!(
+        "User {} can borrow {} from bank {}",
+        ctx.accounts.user_account.key(),
+        amount,
+        bank.bank_id
+    );
+
+    Ok(amount)
+}
@@ -0,0 +1,58 @@
+use crate::{
+    controller::RiskEngine,
+    protocol::{state::Bank, BANK_SEED},
+    user::state::User,
+};
+use anchor_lang::prelude::*;
+
+/// Accounts required to query the largest withdrawal from a bank
+/// The banks of every open position and the queried bank are passed as remaining accounts
+#[derive(Accounts)]
+pub struct GetMaxWithdraw<'info> {
+    /// The user account to value
+    #[account(
+        constraint = user_account.load()?.pool_id == bank.load()?.pool_id,
+    )]
+    pub user_account: AccountLoader<'info, User>,
+
+    /// Bank to withdraw from
+    #[account(
+        seeds = [
+            BANK_SEED,
+            &[bank.load()?.pool_id][..],
+            &[bank.load()?.bank_id][..],
+        ],
+        bump,
+    )]
+    pub bank: AccountLoader<'info, Bank>,
+}
+
+/// Get the largest amount of collateral withdrawable from a bank without borrowing
+/// Keeps initial weighted collateral above liabilities and is capped by the bank's liquidity
+/// Read-only, meant to be simulated by clients reading the return data
+pub fn handle_get_max_withdraw<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, GetMaxWithdraw<'info>>,
+) -> Result<u64> {
+    let bank = ctx.accounts.bank.load()?;
+    let user_account = ctx.accounts.user_account.load()?;
+
+    // Open positions and the queried bank
+    let mut bank_ids = user_account.open_bank_ids();
+    if !bank_ids.contains(&bank.bank_id) {
+        bank_ids.push(bank.bank_id);
+    }
+
+    let risk_engine = RiskEngine::load(user_account.pool_id, bank_ids, ctx.remaining_accounts)?;
+    let amount = risk_engine
+        .max_withdraw(&user_account.token_balances, bank.bank_id)?
+        .min(bank.available_liquidity());
+
+    msg!(
+        "User {} can withdraw {} from bank {}",
+        ctx.accounts.user_account.key(),
+        amount,
+        bank.bank_id
+    );
+
+    Ok(amount)
+}
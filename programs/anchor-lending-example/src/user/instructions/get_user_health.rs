@@ -0,0 +1,72 @@
+use crate::{
+    controller::{HealthType, RiskEngine},
+    user::state::User,
+};
+use anchor_lang::prelude::*;
+
+/// Health of a user position, returned to clients through return data
+/// Values are in USD with 6 decimal places precision
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct UserHealth {
+    /// Collateral value without weights
+    pub total_collateral: u128,
+    /// Liability value without weights
+    pub total_liability: u128,
+    /// Collateral value with initial asset weights
+    pub weighted_collateral: u128,
+    /// Liability value with initial liability weights
+    pub weighted_liability: u128,
+    /// Collateral value with maintenance asset weights
+    pub maintenance_collateral: u128,
+    /// Liability value with maintenance liability weights
+    pub maintenance_liability: u128,
+    /// Maintenance collateral to liability ratio in basis points
+    /// u64::MAX when the position has no liabilities
+    pub health_ratio: u64,
+    /// Whether the position is below its maintenance requirement
+    pub liquidatable: bool,
+}
+
+/// Accounts required to query the health of a user position
+/// The banks of every open position are passed as remaining accounts
+#[derive(Accounts)]
+pub struct GetUserHealth<'info> {
+    /// The user account to value
+    pub user_account: AccountLoader<'info, User>,
+}
+
+/// Value a user position with every set of weights
+/// Read-only, meant to be simulated by clients reading the return data
+pub fn handle_get_user_health<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, GetUserHealth<'info>>,
+) -> Result<UserHealth> {
+    let user_account = ctx.accounts.user_account.load()?;
+
+    let bank_ids = user_account.open_bank_ids();
+
+    let risk_engine = RiskEngine::load(user_account.pool_id, bank_ids, ctx.remaining_accounts)?;
+    let total = risk_engine.health(&user_account.token_balances, HealthType::Unweighted)?;
+    let initial = risk_engine.health(&user_account.token_balances, HealthType::Initial)?;
+    let maintenance = risk_engine.health(&user_account.token_balances, HealthType::Maintenance)?;
+
+    let health = UserHealth {
+        total_collateral: total.collateral,
+        total_liability: total.liability,
+        weighted_collateral: initial.collateral,
+        weighted_liability: initial.liability,
+        maintenance_collateral: maintenance.collateral,
+        maintenance_liability: maintenance.liability,
+        health_ratio: maintenance.ratio()?,
+        liquidatable: !maintenance.is_healthy(),
+    };
+
+    msg!(
+        "User {} health: maintenance collateral {}, maintenance liability {}, ratio {} bps",
+        ctx.accounts.user_account.key(),
+        health.maintenance_collateral,
+        health.maintenance_liability,
+        health.health_ratio
+    );
+
+    Ok(health)
+}
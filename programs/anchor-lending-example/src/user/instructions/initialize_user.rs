@@ -1,5 +1,5 @@
 use crate::{
-    protocol::{Admin, ADMIN_SEED},
+    protocol::{Pool, PoolStatus, POOL_SEED},
     user::{state::BalanceType, TokenBalance, User, UserError, UserInitialized},
 };
 use anchor_lang::prelude::*;
@@ -26,12 +26,12 @@ pub struct InitializeUser<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    /// The admin account for the protocol
+    /// The pool the user joins
     #[account(
-        seeds = [ADMIN_SEED],
+        seeds = [POOL_SEED, &[pool_id][..]],
         bump,
     )]
-    pub admin: AccountLoader<'info, Admin>,
+    pub pool: AccountLoader<'info, Pool>,
 
     /// System program for CPI
     pub system_program: Program<'info, System>,
@@ -42,8 +42,12 @@ pub fn handle_initialize_user(
     pool_id: u8,
     user_id: u16,
 ) -> Result<()> {
-    let admin = ctx.accounts.admin.load()?;
-    require_gte!(admin.pool_count, pool_id, UserError::PoolNotFound);
+    let pool = ctx.accounts.pool.load()?;
+    require_eq!(
+        pool.status,
+        PoolStatus::Active as u8,
+        UserError::PoolInactive
+    );
 
     // Initialize user account with default collateral balance type
     let mut user = ctx.accounts.user.load_init()?;
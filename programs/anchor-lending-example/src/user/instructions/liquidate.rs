@@ -1,8 +1,11 @@
 use crate::{
-    controller::{oracle::calculate_token_value, token::TokenInstructionInterface, BankInterface},
+    controller::{
+        calculate_net_amount, calculate_token_amount, calculate_token_value, HealthType,
+        RiskEngine, TokenInstructionInterface,
+    },
     protocol::{
-        state::{Bank, BankStatus},
-        BankError, BANK_SEED,
+        state::{Admin, Bank, BankOperation, PauseFlag, Pool},
+        BankError, ADMIN_SEED, BANK_SEED, POOL_SEED,
     },
     user::{
         event::UserBalanceUpdated,
@@ -11,10 +14,10 @@ use crate::{
     },
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-// Constant for liquidation discount (5%)
-const LIQUIDATION_DISCOUNT: u8 = 95;
+// Constant for liquidation bonus (5%), capped by the pool setting
+pub(crate) const LIQUIDATION_BONUS: u8 = 5;
 
 /// Instruction context for liquidating an unhealthy position
 #[derive(Accounts)]
@@ -97,6 +100,28 @@ pub struct Liquidate<'info> {
     )]
     pub bank_liability_token: InterfaceAccount<'info, TokenAccount>,
 
+    /// Pool the banks belong to
+    #[account(
+        seeds = [POOL_SEED, &[collateral_bank.load()?.pool_id][..]],
+        bump,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// Mint of the collateral token, required for transfer_checked
+    #[account(address = collateral_bank.load()?.mint)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    /// Mint of the liability token, required for transfer_checked
+    #[account(address = liability_bank.load()?.mint)]
+    pub liability_mint: InterfaceAccount<'info, Mint>,
+
+    /// Admin account holding protocol-wide pause flags
+    #[account(
+        seeds = [ADMIN_SEED],
+        bump,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
     /// Token program
     pub token_program: Interface<'info, TokenInterface>,
 }
@@ -106,93 +131,150 @@ pub fn handle_liquidate<'c: 'info, 'info>(
     liability_amount: u64,
 ) -> Result<()> {
     // Load accounts
-    let collateral_bank = ctx.accounts.collateral_bank.load()?;
-    let liability_bank = ctx.accounts.liability_bank.load()?;
+    let collateral_bank = *ctx.accounts.collateral_bank.load()?;
+    let liability_bank = *ctx.accounts.liability_bank.load()?;
     let collateral_bank_id = collateral_bank.bank_id;
     let liability_bank_id = liability_bank.bank_id;
+    let pool = ctx.accounts.pool.load()?;
     let mut user_account = ctx.accounts.user_account.load_mut()?;
 
-    // Verify banks are active
+    // Check protocol and pool pause flags before bank status
     require!(
-        collateral_bank.status == BankStatus::Active as u8,
-        BankError::BankInactive
+        !ctx.accounts.admin.load()?.is_paused(PauseFlag::Liquidate),
+        UserError::ProtocolPaused
     );
+    require!(!pool.is_paused(PauseFlag::Liquidate), UserError::PoolPaused);
+
+    // A position in flashloan mode is only valued once end_flashloan runs
+    require!(!user_account.in_flashloan(), UserError::InFlashloan);
+
+    // Verify collateral can be seized and the liability repaid
     require!(
-        liability_bank.status == BankStatus::Active as u8
-            || liability_bank.status == BankStatus::ReduceOnly as u8,
-        BankError::BankNotAvailableForWithdrawal
+        collateral_bank.status()?.allows(BankOperation::Liquidate),
+        BankError::OperationNotAllowed
+    );
+    require!(
+        liability_bank.status()?.allows(BankOperation::Repay),
+        BankError::OperationNotAllowed
     );
 
-    // Collect bank IDs from non-zero balances
-    let bank_ids: Vec<u8> = user_account
-        .token_balances
-        .iter()
-        .filter(|balance| balance.balance != 0 && balance.bank_id != 0)
-        .map(|balance| balance.bank_id)
-        .collect();
+    let bank_ids = user_account.open_bank_ids();
 
-    let bank_interface = BankInterface::load(bank_ids, ctx.remaining_accounts)?;
+    let risk_engine = RiskEngine::load(user_account.pool_id, bank_ids, ctx.remaining_accounts)?;
 
     // Check if position is liquidatable
-    let (maintenance_collateral, maintenance_liability) =
-        bank_interface.calculate_total_maintenance_values(user_account.token_balances)?;
+    let health_before =
+        risk_engine.health(&user_account.token_balances, HealthType::Maintenance)?;
+    require!(!health_before.is_healthy(), UserError::PositionHealthy);
 
-    require!(
-        maintenance_collateral < maintenance_liability,
-        UserError::PositionHealthy
+    // Only a liability can be repaid, naming a collateral bank would force a deposit
+    let liability_balance = user_account.find_balance_by_bank_id(liability_bank_id);
+    require_gt!(0, liability_balance, UserError::NoLiabilityToRepay);
+
+    // Repayment is limited to the pool close factor of the outstanding liability
+    let max_repay = (liability_balance.unsigned_abs() as u128)
+        .checked_mul(pool.close_factor as u128)
+        .ok_or(error!(UserError::MathOverflow))?
+        .checked_div(100)
+        .ok_or(error!(UserError::MathOverflow))?;
+    require_gte!(
+        max_repay,
+        liability_amount as u128,
+        UserError::CloseFactorExceeded
     );
 
-    // Calculate repayment and collateral amount with discount
-    let liability_value = calculate_token_value(
+    // Only what the vault receives after transfer fees counts as repaid
+    let repaid_amount = calculate_net_amount(
+        &ctx.accounts.liability_mint.to_account_info(),
         liability_amount,
+    )?;
+
+    // Calculate collateral amount with discount, in collateral token units
+    let liquidation_discount = 100 - LIQUIDATION_BONUS.min(pool.max_liquidation_bonus);
+    let liability_value = calculate_token_value(
+        repaid_amount,
         liability_bank.decimals,
         &liability_bank.price_message,
     )?;
-    let collateral_amount = ((liability_value as u128)
+    let collateral_value = (liability_value as u128)
         .checked_mul(100)
         .ok_or(error!(UserError::MathOverflow))?
-        .checked_div(LIQUIDATION_DISCOUNT as u128)
-        .ok_or(error!(UserError::MathOverflow))?) as u64;
+        .checked_div(liquidation_discount as u128)
+        .ok_or(error!(UserError::MathOverflow))?;
+    let collateral_amount = calculate_token_amount(
+        collateral_value,
+        collateral_bank.decimals,
+        &collateral_bank.price_message,
+    )?;
+
+    // Only collateral the user holds can be seized
+    require_gte!(
+        user_account.find_balance_by_bank_id(collateral_bank_id) as i128,
+        collateral_amount as i128,
+        UserError::InsufficientCollateral
+    );
+
+    // Update user balances
+    let clock = Clock::get()?;
+
+    // For liability
+    let previous_liability = user_account.find_balance_by_bank_id(liability_bank_id);
+    let previous_liability_type = user_account.get_balance_type_by_bank_id(liability_bank_id);
+    user_account.update_balance(liability_bank_id, repaid_amount, Direction::Deposit)?;
+    let new_liability = user_account.find_balance_by_bank_id(liability_bank_id);
+    let new_liability_type = user_account.get_balance_type_by_bank_id(liability_bank_id);
+    ctx.accounts
+        .liability_bank
+        .load_mut()?
+        .record_balance_change(previous_liability, new_liability)?;
+
+    // For collateral
+    let previous_collateral = user_account.find_balance_by_bank_id(collateral_bank_id);
+    let previous_collateral_type = user_account.get_balance_type_by_bank_id(collateral_bank_id);
+    user_account.update_balance(collateral_bank_id, collateral_amount, Direction::Withdrawal)?;
+    let new_collateral = user_account.find_balance_by_bank_id(collateral_bank_id);
+    let new_collateral_type = user_account.get_balance_type_by_bank_id(collateral_bank_id);
+    ctx.accounts
+        .collateral_bank
+        .load_mut()?
+        .record_balance_change(previous_collateral, new_collateral)?;
+
+    // Check the liquidation moves the position towards health before moving tokens
+    risk_engine.check_liquidation_improves_health(&health_before, &user_account.token_balances)?;
 
     // Get token interface
     let token_interface =
         TokenInstructionInterface::load(&ctx.accounts.token_program, ctx.remaining_accounts)?;
 
-    // Bank seed for signing transfers
-    let collateral_bank_seeds = &[
-        BANK_SEED,
-        &[collateral_bank.pool_id][..],
-        &[collateral_bank.bank_id][..],
-        &[ctx.bumps.collateral_bank][..],
-    ];
-
     // First repay liability
     token_interface.transfer(
         ctx.accounts.liquidator_liability_token.to_account_info(),
+        ctx.accounts.liability_mint.to_account_info(),
         ctx.accounts.bank_liability_token.to_account_info(),
         ctx.accounts.liquidator.to_account_info(),
         liability_amount,
+        liability_bank.decimals,
     )?;
 
+    // Bank seed for signing transfers
+    let collateral_bank_seeds = &[
+        BANK_SEED,
+        &[collateral_bank.pool_id][..],
+        &[collateral_bank.bank_id][..],
+        &[ctx.bumps.collateral_bank][..],
+    ];
+
     // Then transfer collateral
     token_interface.transfer_with_signer(
         ctx.accounts.bank_collateral_token.to_account_info(),
+        ctx.accounts.collateral_mint.to_account_info(),
         ctx.accounts.liquidator_collateral_token.to_account_info(),
         ctx.accounts.collateral_bank.to_account_info(),
         collateral_amount,
+        collateral_bank.decimals,
         collateral_bank_seeds,
     )?;
 
-    // Update user balances
-    let clock = Clock::get()?;
-
-    // For liability
-    let previous_liability = user_account.find_balance_by_bank_id(liability_bank_id);
-    let previous_liability_type = user_account.get_balance_type_by_bank_id(liability_bank_id);
-    user_account.update_balance(liability_bank_id, liability_amount, Direction::Deposit)?;
-    let new_liability = user_account.find_balance_by_bank_id(liability_bank_id);
-    let new_liability_type = user_account.get_balance_type_by_bank_id(liability_bank_id);
-
     emit!(UserBalanceUpdated {
         user: ctx.accounts.user_account.key(),
         token_id: liability_bank_id,
@@ -203,13 +285,6 @@ pub fn handle_liquidate<'c: 'info, 'info>(
         timestamp: clock.unix_timestamp,
     });
 
-    // For collateral
-    let previous_collateral = user_account.find_balance_by_bank_id(collateral_bank_id);
-    let previous_collateral_type = user_account.get_balance_type_by_bank_id(collateral_bank_id);
-    user_account.update_balance(collateral_bank_id, collateral_amount, Direction::Withdrawal)?;
-    let new_collateral = user_account.find_balance_by_bank_id(collateral_bank_id);
-    let new_collateral_type = user_account.get_balance_type_by_bank_id(collateral_bank_id);
-
     emit!(UserBalanceUpdated {
         user: ctx.accounts.user_account.key(),
         token_id: collateral_bank_id,
@@ -220,19 +295,10 @@ pub fn handle_liquidate<'c: 'info, 'info>(
         timestamp: clock.unix_timestamp,
     });
 
-    // Check final position health improved
-    let (final_maintenance_collateral, _final_maintenance_liability) =
-        bank_interface.calculate_total_maintenance_values(user_account.token_balances)?;
-
-    require_gte!(
-        final_maintenance_collateral,
-        maintenance_collateral,
-        UserError::InsufficientCollateral
-    );
-
     msg!(
-        "Liquidation completed: {} liability repaid with {} collateral seized from user {}",
+        "Liquidation completed: {} liability repaid ({} received) with {} collateral seized from user {}",
         liability_amount,
+        repaid_amount,
         collateral_amount,
         ctx.accounts.user_account.key()
     );
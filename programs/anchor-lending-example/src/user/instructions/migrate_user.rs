@@ -0,0 +1,58 @@
+use crate::user::{state::User, UserError};
+use anchor_lang::{prelude::*, system_program, Discriminator};
+
+/// Accounts required for migrating a user account to the current layout
+#[derive(Accounts)]
+pub struct MigrateUser<'info> {
+    /// The user account to grow, checked by hand since the legacy size does not load
+    /// CHECK: owner, discriminator and size are validated in the handler
+    #[account(mut, owner = crate::ID)]
+    pub user_account: UncheckedAccount<'info>,
+
+    /// Pays the rent for the appended fields
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program for the rent transfer
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow a legacy user account to `User::LEN`
+/// Appended fields are zero initialized, so no authority transfer is pending
+pub fn handle_migrate_user(ctx: Context<MigrateUser>) -> Result<()> {
+    let user_account = ctx.accounts.user_account.to_account_info();
+    {
+        let data = user_account.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == User::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        require_eq!(data.len(), User::LEGACY_LEN, UserError::AlreadyMigrated);
+    }
+
+    // Top up rent for the larger account before growing it
+    let rent = Rent::get()?
+        .minimum_balance(User::LEN)
+        .saturating_sub(user_account.lamports());
+    if rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: user_account.clone(),
+                },
+            ),
+            rent,
+        )?;
+    }
+    user_account.realloc(User::LEN, true)?;
+
+    msg!(
+        "User {} migrated from {} to {} bytes",
+        user_account.key(),
+        User::LEGACY_LEN,
+        User::LEN
+    );
+    Ok(())
+}
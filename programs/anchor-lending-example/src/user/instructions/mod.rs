@@ -1,9 +1,35 @@
+pub use accept_user_authority::*;
+pub use begin_flashloan::*;
 pub use deposit::*;
+pub use deposit_native::*;
+pub use end_flashloan::*;
+pub use flash_borrow::*;
+pub use flash_repay::*;
+pub use get_liquidation_price::*;
+pub use get_max_borrow::*;
+pub use get_max_withdraw::*;
+pub use get_user_health::*;
 pub use initialize_user::*;
 pub use liquidate::*;
+pub use migrate_user::*;
+pub use propose_user_authority::*;
+pub use withdraw_native::*;
 pub use withdrawal::*;
 
+mod accept_user_authority;
+mod begin_flashloan;
 mod deposit;
+mod deposit_native;
+mod end_flashloan;
+mod flash_borrow;
+mod flash_repay;
+mod get_liquidation_price;
+mod get_max_borrow;
+mod get_max_withdraw;
+mod get_user_health;
 mod initialize_user;
 mod liquidate;
+mod migrate_user;
+mod propose_user_authority;
+mod withdraw_native;
 mod withdrawal;
@@ -0,0 +1,43 @@
+use crate::user::{state::User, UserAuthorityProposed, UserError};
+use anchor_lang::prelude::*;
+
+/// Accounts required for proposing a new user account authority
+#[derive(Accounts)]
+pub struct ProposeUserAuthority<'info> {
+    /// The user account to transfer
+    #[account(
+        mut,
+        constraint = user_account.load()?.authority == authority.key() @ UserError::InvalidAuthority,
+    )]
+    pub user_account: AccountLoader<'info, User>,
+
+    /// The current authority that must sign
+    pub authority: Signer<'info>,
+
+    /// The authority proposed to take over the user account
+    pub new_authority: SystemAccount<'info>,
+}
+
+/// Propose a new authority for the user account
+/// The transfer only takes effect once the new authority accepts it
+pub fn handle_propose_user_authority(ctx: Context<ProposeUserAuthority>) -> Result<()> {
+    let mut user_account = ctx.accounts.user_account.load_mut()?;
+
+    // Store the proposed authority until it is accepted
+    user_account.pending_authority = ctx.accounts.new_authority.key();
+
+    msg!(
+        "User {} authority transfer proposed from {} to {}",
+        ctx.accounts.user_account.key(),
+        user_account.authority,
+        user_account.pending_authority
+    );
+
+    emit!(UserAuthorityProposed {
+        user: ctx.accounts.user_account.key(),
+        authority: user_account.authority,
+        pending_authority: user_account.pending_authority,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use crate::{
+    controller::token::TokenInstructionInterface,
+    protocol::{
+        state::{Admin, Bank, Pool},
+        ADMIN_SEED, BANK_SEED, POOL_SEED,
+    },
+    user::{
+        instructions::withdrawal::{process_withdrawal, WithdrawAccounts},
+        state::User,
+        UserError,
+    },
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::spl_token::native_mint,
+    token_2022::spl_token_2022::native_mint as native_mint_2022,
+    token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface},
+};
+
+/// Instruction context for withdrawing native SOL from a wrapped SOL bank
+#[derive(Accounts)]
+pub struct WithdrawNative<'info> {
+    /// User's wallet that must sign and receives the lamports
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Temporary wrapped SOL account, closed to the user to unwrap the withdrawal
+    #[account(
+        init,
+        payer = user,
+        seeds = [
+            b"native_token_account",
+            user_account.key().as_ref(),
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub temporary_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Bank's token account to withdraw from
+    #[account(
+        mut,
+        seeds = [
+            b"token_account",
+            bank.key().as_ref(),
+        ],
+        bump,
+        token::authority = bank,
+        token::mint = bank.load()?.mint,
+    )]
+    pub bank_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// User account to update balance
+    #[account(
+        mut,
+        constraint = user_account.load()?.authority == user.key(),
+        constraint = user_account.load()?.pool_id == bank.load()?.pool_id,
+    )]
+    pub user_account: AccountLoader<'info, User>,
+
+    /// Bank account to validate status and sign token transfer
+    #[account(
+        mut,
+        seeds = [
+            BANK_SEED,
+            &[bank.load()?.pool_id][..],
+            &[bank.load()?.bank_id][..],
+        ],
+        bump,
+    )]
+    pub bank: AccountLoader<'info, Bank>,
+
+    /// Pool the bank belongs to
+    #[account(
+        seeds = [POOL_SEED, &[bank.load()?.pool_id][..]],
+        bump,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// Admin account holding protocol-wide pause flags
+    #[account(
+        seeds = [ADMIN_SEED],
+        bump,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
+    /// Native mint of the bank, required for transfer_checked
+    #[account(
+        address = bank.load()?.mint,
+        constraint = mint.key() == native_mint::ID || mint.key() == native_mint_2022::ID
+            @ UserError::NotNativeMint,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program, either Token or Token2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program to create the temporary account
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_withdraw_native<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, WithdrawNative<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let token_interface =
+        TokenInstructionInterface::load(&ctx.accounts.token_program, ctx.remaining_accounts)?;
+
+    process_withdrawal(
+        WithdrawAccounts {
+            user: ctx.accounts.user.to_account_info(),
+            destination_token_account: ctx.accounts.temporary_token_account.to_account_info(),
+            bank_token_account: ctx.accounts.bank_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            user_account: &ctx.accounts.user_account,
+            bank: &ctx.accounts.bank,
+            pool: &ctx.accounts.pool,
+            admin: &ctx.accounts.admin,
+        },
+        &token_interface,
+        ctx.remaining_accounts,
+        ctx.bumps.bank,
+        amount,
+        false,
+    )?;
+
+    // Closing the wrapped account unwraps the withdrawal and returns its rent
+    token_interface::close_account(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.temporary_token_account.to_account_info(),
+            destination: ctx.accounts.user.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    ))?;
+
+    msg!(
+        "Native withdrawal completed: {} lamports unwrapped for user {}",
+        amount,
+        ctx.accounts.user.key()
+    );
+    Ok(())
+}
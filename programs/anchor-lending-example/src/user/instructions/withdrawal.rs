@@ -1,8 +1,8 @@
 use crate::{
-    controller::{token::TokenInstructionInterface, BankInterface},
+    controller::{token::TokenInstructionInterface, RiskEngine},
     protocol::{
-        state::{Bank, BankStatus},
-        BankError, BANK_SEED,
+        state::{Admin, Bank, BankOperation, PauseFlag, Pool},
+        BankError, ADMIN_SEED, BANK_SEED, POOL_SEED,
     },
     user::{
         event::UserBalanceUpdated,
@@ -11,7 +11,7 @@ use crate::{
     },
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 /// Instruction context for withdrawing tokens
 #[derive(Accounts)]
@@ -61,6 +61,24 @@ pub struct Withdraw<'info> {
     )]
     pub bank: AccountLoader<'info, Bank>,
 
+    /// Pool the bank belongs to
+    #[account(
+        seeds = [POOL_SEED, &[bank.load()?.pool_id][..]],
+        bump,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// Admin account holding protocol-wide pause flags
+    #[account(
+        seeds = [ADMIN_SEED],
+        bump,
+    )]
+    pub admin: AccountLoader<'info, Admin>,
+
+    /// Mint of the bank token, required for transfer_checked
+    #[account(address = bank.load()?.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
     /// Token program, either Token or Token2022
     pub token_program: Interface<'info, TokenInterface>,
 }
@@ -68,72 +86,148 @@ pub struct Withdraw<'info> {
 pub fn handle_withdrawal<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, Withdraw<'info>>,
     amount: u64,
+    all: bool,
 ) -> Result<()> {
-    let bank = ctx.accounts.bank.load()?;
-    let mut user_account = ctx.accounts.user_account.load_mut()?;
+    let token_interface =
+        TokenInstructionInterface::load(&ctx.accounts.token_program, ctx.remaining_accounts)?;
+
+    process_withdrawal(
+        WithdrawAccounts {
+            user: ctx.accounts.user.to_account_info(),
+            destination_token_account: ctx.accounts.user_token_account.to_account_info(),
+            bank_token_account: ctx.accounts.bank_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            user_account: &ctx.accounts.user_account,
+            bank: &ctx.accounts.bank,
+            pool: &ctx.accounts.pool,
+            admin: &ctx.accounts.admin,
+        },
+        &token_interface,
+        ctx.remaining_accounts,
+        ctx.bumps.bank,
+        amount,
+        all,
+    )
+}
 
+/// Accounts shared by token and native withdrawals
+pub(crate) struct WithdrawAccounts<'a, 'info> {
+    pub user: AccountInfo<'info>,
+    pub destination_token_account: AccountInfo<'info>,
+    pub bank_token_account: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub user_account: &'a AccountLoader<'info, User>,
+    pub bank: &'a AccountLoader<'info, Bank>,
+    pub pool: &'a AccountLoader<'info, Pool>,
+    pub admin: &'a AccountLoader<'info, Admin>,
+}
+
+/// Checks pause flags and bank status, verifies the position stays collateralized
+/// and transfers from the bank vault to the destination token account
+/// With `all` set, `amount` is ignored and the whole collateral is withdrawn and cleared
+pub(crate) fn process_withdrawal<'c: 'info, 'info>(
+    accounts: WithdrawAccounts<'_, 'info>,
+    token_interface: &TokenInstructionInterface<'info>,
+    remaining_accounts: &'c [AccountInfo<'info>],
+    bank_bump: u8,
+    amount: u64,
+    all: bool,
+) -> Result<()> {
+    let bank = *accounts.bank.load()?;
+    let pool = accounts.pool.load()?;
+    let mut user_account = accounts.user_account.load_mut()?;
+
+    // Check protocol and pool pause flags before bank status
     require!(
-        bank.status == BankStatus::Active as u8 || bank.status == BankStatus::ReduceOnly as u8,
-        BankError::BankNotAvailableForWithdrawal
+        !accounts.admin.load()?.is_paused(PauseFlag::Withdraw),
+        UserError::ProtocolPaused
     );
+    require!(!pool.is_paused(PauseFlag::Withdraw), UserError::PoolPaused);
 
     let previous_balance = user_account.find_balance_by_bank_id(bank.bank_id);
-    let previous_asset_type = user_account.get_balance_type_by_bank_id(bank.bank_id);
 
-    let bank_seeds = &[
-        BANK_SEED,
-        &[bank.pool_id][..],
-        &[bank.bank_id][..],
-        &[ctx.bumps.bank][..],
-    ];
+    // Withdrawing all takes the current collateral balance
+    let amount = if all {
+        require!(previous_balance > 0, UserError::NoPositionToClear);
+        previous_balance.unsigned_abs()
+    } else {
+        amount
+    };
 
-    let token_interface =
-        TokenInstructionInterface::load(&ctx.accounts.token_program, ctx.remaining_accounts)?;
+    // Check bank status - withdrawing beyond the deposited balance is a borrow
+    let operation = if previous_balance >= 0 && amount <= previous_balance.unsigned_abs() {
+        BankOperation::Withdraw
+    } else {
+        BankOperation::Borrow
+    };
+    require!(
+        bank.status()?.allows(operation),
+        BankError::OperationNotAllowed
+    );
 
-    token_interface.transfer_with_signer(
-        ctx.accounts.bank_token_account.to_account_info(),
-        ctx.accounts.user_token_account.to_account_info(),
-        ctx.accounts.bank.to_account_info(),
-        amount,
-        bank_seeds,
-    )?;
+    let previous_asset_type = user_account.get_balance_type_by_bank_id(bank.bank_id);
 
     // Use Direction::Withdrawal to handle balance type conversion automatically
+    let previous_positions = user_account.position_count();
     user_account.update_balance(bank.bank_id, amount, Direction::Withdrawal)?;
 
+    // New positions must stay within the pool limit
+    if user_account.position_count() > previous_positions {
+        require_gte!(
+            pool.max_positions,
+            user_account.position_count(),
+            UserError::MaxPositions
+        );
+    }
+
+    if all {
+        user_account.clear_balance(bank.bank_id);
+    }
+
     let final_balance = user_account.find_balance_by_bank_id(bank.bank_id);
     let new_asset_type = user_account.get_balance_type_by_bank_id(bank.bank_id);
 
-    let clock = Clock::get()?;
+    accounts
+        .bank
+        .load_mut()?
+        .record_balance_change(previous_balance, final_balance)?;
+
+    let mut bank_ids = user_account.open_bank_ids();
 
-    let (weighted_collateral, weighted_liability) = {
-        // Collect bank IDs from non-zero balances
-        let mut bank_ids: Vec<u8> = user_account
-            .token_balances
-            .iter()
-            .filter(|balance| balance.balance != 0 && balance.bank_id != 0)
-            .map(|balance| balance.bank_id)
-            .collect();
+    // Add withdrawal bank ID if not already included
+    if !bank_ids.contains(&bank.bank_id) {
+        bank_ids.push(bank.bank_id);
+    };
 
-        // Add withdrawal bank ID if not already included
-        if !bank_ids.contains(&bank.bank_id) {
-            bank_ids.push(bank.bank_id);
-        };
+    // The position must stay healthy before any tokens leave the vault
+    // In flashloan mode the check is deferred to end_flashloan
+    if !user_account.in_flashloan() {
+        let risk_engine = RiskEngine::load(user_account.pool_id, bank_ids, remaining_accounts)?;
+        risk_engine.check_initial_health(&user_account.token_balances)?;
+        risk_engine.check_maintenance_health(&user_account.token_balances)?;
+    }
 
-        let bank_interface = BankInterface::load(bank_ids, ctx.remaining_accounts)?;
+    let bank_seeds = &[
+        BANK_SEED,
+        &[bank.pool_id][..],
+        &[bank.bank_id][..],
+        &[bank_bump][..],
+    ];
 
-        // Calculate total values
-        bank_interface.calculate_total_weighted_values(user_account.token_balances)?
-    };
+    token_interface.transfer_with_signer(
+        accounts.bank_token_account,
+        accounts.mint,
+        accounts.destination_token_account,
+        accounts.bank.to_account_info(),
+        amount,
+        bank.decimals,
+        bank_seeds,
+    )?;
 
-    require_gte!(
-        weighted_collateral,
-        weighted_liability,
-        UserError::InsufficientCollateral
-    );
+    let clock = Clock::get()?;
 
     emit!(UserBalanceUpdated {
-        user: ctx.accounts.user_account.key(),
+        user: accounts.user_account.key(),
         token_id: bank.bank_id,
         previous_balance: previous_balance.unsigned_abs(),
         previous_asset_type,
@@ -145,7 +239,7 @@ pub fn handle_withdrawal<'c: 'info, 'info>(
     msg!(
         "Withdrawal completed: amount {} withdrawn for user {}, new balance: {} ({:?})",
         amount,
-        ctx.accounts.user.key(),
+        accounts.user.key(),
         final_balance,
         new_asset_type
     );
@@ -50,16 +50,29 @@ pub struct User {
     pub pool_id: u8,
     /// Bump seed for PDA validation
     pub bump: u8,
+    /// Non-zero while in flashloan mode, health checks are deferred to end_flashloan
+    pub flashloan: u8,
     /// Padding for memory alignment
-    pub padding: [u8; 4],
+    pub padding: [u8; 3],
     /// Token balances stored as array of TokenBalance
     /// Maximum 16 different tokens per user
     pub token_balances: [TokenBalance; 16],
+    /// Authority proposed to take over this account, default if none pending
+    /// Appended after the balances so accounts created before it keep their layout
+    pub pending_authority: Pubkey,
 }
 
 impl User {
     pub const LEN: usize = 8 + size_of::<User>();
 
+    /// Size of accounts created before `pending_authority` was appended
+    pub const LEGACY_LEN: usize = Self::LEN - size_of::<Pubkey>();
+
+    /// Whether health checks are deferred until end_flashloan
+    pub fn in_flashloan(&self) -> bool {
+        self.flashloan != 0
+    }
+
     /// Gets the token balance for a specific bank ID
     /// Returns the signed balance if found, or 0 if not found
     /// Liability balances are returned as negative values
@@ -121,9 +134,10 @@ impl User {
     /// ```
     pub fn update_balance(&mut self, bank_id: u8, delta: u64, direction: Direction) -> Result<()> {
         // Search for an existing entry with the matching bank_id
+        // Empty slots also carry bank_id 0, so a bank 0 entry needs a balance
         let mut found = false;
         for token in self.token_balances.iter_mut() {
-            if token.bank_id == bank_id {
+            if token.bank_id == bank_id && (bank_id != 0 || token.balance != 0) {
                 match direction {
                     Direction::Deposit => {
                         if token.balance_type == BalanceType::Liability as u8 {
@@ -166,50 +180,40 @@ impl User {
                         }
                     }
                     Direction::Withdrawal => {
-                        // For withdrawals, we need to handle potential conversion
-                        if token.balance >= delta {
+                        if token.balance_type == BalanceType::Liability as u8 {
+                            // Already liability, the full withdrawal is borrowed
+                            token.balance = token
+                                .balance
+                                .checked_add(delta)
+                                .ok_or(error!(UserError::BalanceUpdateOverflow))?;
+
+                            msg!(
+                                "Added to liability balance. New liability: {}",
+                                token.balance
+                            );
+                        } else if token.balance >= delta {
                             // Simple withdrawal, stays as collateral
                             token.balance = token
                                 .balance
                                 .checked_sub(delta)
                                 .ok_or(error!(UserError::BalanceUpdateOverflow))?;
-                            // Keep existing balance type
 
                             msg!(
                                 "Withdrew from collateral. Remaining balance: {}",
                                 token.balance
                             );
                         } else {
-                            // Withdrawal exceeds balance, becomes/adds to liability
-                            if token.balance_type == BalanceType::Collateral as u8 {
-                                // Converting from collateral to liability
-                                // First subtract full collateral
-                                let remaining_delta = delta
-                                    .checked_sub(token.balance)
-                                    .ok_or(error!(UserError::BalanceUpdateOverflow))?;
-                                // Then set as liability
-                                token.balance = remaining_delta;
-                                token.balance_type = BalanceType::Liability as u8;
-
-                                msg!(
-                                    "Converted collateral to liability. New liability: {}",
-                                    remaining_delta
-                                );
-                            } else {
-                                // Already liability, just add to it
-                                let additional_liability = delta
-                                    .checked_sub(token.balance)
-                                    .ok_or(error!(UserError::BalanceUpdateOverflow))?;
-                                token.balance = token
-                                    .balance
-                                    .checked_add(additional_liability)
-                                    .ok_or(error!(UserError::BalanceUpdateOverflow))?;
+                            // Withdrawal exceeds collateral, the remainder is borrowed
+                            let remaining_delta = delta
+                                .checked_sub(token.balance)
+                                .ok_or(error!(UserError::BalanceUpdateOverflow))?;
+                            token.balance = remaining_delta;
+                            token.balance_type = BalanceType::Liability as u8;
 
-                                msg!(
-                                    "Added to liability balance. New liability: {}",
-                                    token.balance
-                                );
-                            }
+                            msg!(
+                                "Converted collateral to liability. New liability: {}",
+                                remaining_delta
+                            );
                         }
                     }
                 }
@@ -245,14 +249,47 @@ impl User {
                 }
             }
             require_eq!(inserted, true, UserError::MaxTokenTypes);
+        }
 
-            // Sort the array after successful insertion
+        // Sort the array after a new position or a bank 0 position closing to an empty slot
+        if !found || bank_id == 0 {
             self.sort_token_balances();
         }
 
         Ok(())
     }
 
+    /// Frees the slot of a bank whose balance has reached zero
+    /// Slots still holding a balance are left untouched
+    pub fn clear_balance(&mut self, bank_id: u8) {
+        let slot = self
+            .token_balances
+            .iter_mut()
+            .find(|token| token.bank_id == bank_id && token.balance == 0);
+        if let Some(token) = slot {
+            *token = TokenBalance::default();
+            self.sort_token_balances();
+        }
+    }
+
+    /// Bank IDs of every open position
+    /// Empty slots carry bank ID 0 too, so only a non-zero balance marks a position
+    pub fn open_bank_ids(&self) -> Vec<u8> {
+        self.token_balances
+            .iter()
+            .filter(|token| token.balance != 0)
+            .map(|token| token.bank_id)
+            .collect()
+    }
+
+    /// Counts the token balance slots currently occupied by a position
+    pub fn position_count(&self) -> u8 {
+        self.token_balances
+            .iter()
+            .filter(|token| token.bank_id != 0 || token.balance != 0)
+            .count() as u8
+    }
+
     /// Sorts the token balances array by bank_id in ascending order
     /// This ensures all non-zero balances are at the front of the array
     fn sort_token_balances(&mut self) {
@@ -284,3 +321,327 @@ impl User {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeMap;
+
+    /// Number of distinct banks operations draw from, enough to fill every slot
+    const BANK_COUNT: u8 = 24;
+
+    /// A single balance update
+    #[derive(Clone, Copy, Debug)]
+    struct Operation {
+        bank_id: u8,
+        delta: u64,
+        direction: Direction,
+    }
+
+    fn operation(bank_ids: std::ops::Range<u8>) -> impl Strategy<Value = Operation> {
+        (bank_ids, 0..1_000_000u64, any::<bool>()).prop_map(|(bank_id, delta, deposit)| Operation {
+            bank_id,
+            delta,
+            direction: if deposit {
+                Direction::Deposit
+            } else {
+                Direction::Withdrawal
+            },
+        })
+    }
+
+    fn operations(bank_ids: std::ops::Range<u8>) -> impl Strategy<Value = Vec<Operation>> {
+        prop::collection::vec(operation(bank_ids), 0..64)
+    }
+
+    fn signed_delta(operation: &Operation) -> i64 {
+        match operation.direction {
+            Direction::Deposit => operation.delta as i64,
+            Direction::Withdrawal => -(operation.delta as i64),
+        }
+    }
+
+    /// Whether a slot holds a position, matching `position_count`
+    fn is_occupied(token: &TokenBalance) -> bool {
+        token.bank_id != 0 || token.balance != 0
+    }
+
+    /// Whether the user already holds a position in a bank
+    fn has_position(user: &User, bank_id: u8) -> bool {
+        user.token_balances
+            .iter()
+            .any(|token| token.bank_id == bank_id && is_occupied(token))
+    }
+
+    /// Applies operations, checking the slot layout after each one, that `MaxTokenTypes`
+    /// is returned exactly when a new bank is touched with all 16 slots live and that
+    /// failures change nothing
+    /// Returns the expected signed balance of every bank that accepted an update
+    fn apply(user: &mut User, operations: &[Operation]) -> BTreeMap<u8, i64> {
+        let mut expected = BTreeMap::new();
+        for operation in operations {
+            let before = *user;
+            let is_full = !has_position(user, operation.bank_id) && user.position_count() == 16;
+
+            let result =
+                user.update_balance(operation.bank_id, operation.delta, operation.direction);
+
+            match result {
+                Ok(()) => {
+                    assert!(!is_full, "update succeeded with every slot taken");
+                    *expected.entry(operation.bank_id).or_insert(0) += signed_delta(operation);
+                }
+                Err(error) => {
+                    assert!(is_full, "unexpected error {error:?}");
+                    assert_eq!(error, error!(UserError::MaxTokenTypes));
+                    assert_eq!(
+                        bytemuck::bytes_of(user),
+                        bytemuck::bytes_of(&before),
+                        "failed update modified the account"
+                    );
+                }
+            }
+            assert_layout(user);
+        }
+        expected
+    }
+
+    /// Slots as (bank ID, balance type, balance) for failure messages
+    fn layout(user: &User) -> Vec<(u8, u8, u64)> {
+        user.token_balances
+            .iter()
+            .map(|token| (token.bank_id, token.balance_type, token.balance))
+            .collect()
+    }
+
+    /// Occupied slots come first in ascending bank ID order, each bank at most once
+    fn assert_layout(user: &User) {
+        let occupied = user
+            .token_balances
+            .iter()
+            .take_while(|token| is_occupied(token))
+            .count();
+        assert_eq!(
+            occupied,
+            user.position_count() as usize,
+            "empty slot before a position: {:?}",
+            layout(user)
+        );
+        assert!(
+            user.token_balances[..occupied]
+                .windows(2)
+                .all(|pair| pair[0].bank_id < pair[1].bank_id),
+            "positions not sorted or duplicated: {:?}",
+            layout(user)
+        );
+    }
+
+    fn assert_balances(user: &User, expected: &BTreeMap<u8, i64>) {
+        for (bank_id, balance) in expected {
+            assert_eq!(
+                user.find_balance_by_bank_id(*bank_id),
+                *balance,
+                "bank {bank_id}"
+            );
+        }
+    }
+
+    /// Bank used by the transition table
+    const BANK: u8 = 1;
+
+    /// Starting slot, update and resulting slot of a single balance transition
+    struct Transition {
+        name: &'static str,
+        initial: Option<(u64, BalanceType)>,
+        delta: u64,
+        direction: Direction,
+        expected: Result<(u64, BalanceType)>,
+    }
+
+    fn transitions() -> Vec<Transition> {
+        use BalanceType::{Collateral, Liability};
+        use Direction::{Deposit, Withdrawal};
+
+        let overflow = || Err(error!(UserError::BalanceUpdateOverflow));
+        vec![
+            Transition {
+                name: "deposit opens collateral",
+                initial: None,
+                delta: 100,
+                direction: Deposit,
+                expected: Ok((100, Collateral)),
+            },
+            Transition {
+                name: "deposit adds to collateral",
+                initial: Some((100, Collateral)),
+                delta: 50,
+                direction: Deposit,
+                expected: Ok((150, Collateral)),
+            },
+            Transition {
+                name: "deposit reduces liability",
+                initial: Some((100, Liability)),
+                delta: 40,
+                direction: Deposit,
+                expected: Ok((60, Liability)),
+            },
+            Transition {
+                name: "deposit repays liability exactly",
+                initial: Some((100, Liability)),
+                delta: 100,
+                direction: Deposit,
+                expected: Ok((0, Collateral)),
+            },
+            Transition {
+                name: "deposit beyond liability becomes collateral",
+                initial: Some((100, Liability)),
+                delta: 150,
+                direction: Deposit,
+                expected: Ok((50, Collateral)),
+            },
+            Transition {
+                name: "deposit overflowing collateral fails",
+                initial: Some((u64::MAX, Collateral)),
+                delta: 1,
+                direction: Deposit,
+                expected: overflow(),
+            },
+            Transition {
+                name: "withdrawal opens liability",
+                initial: None,
+                delta: 100,
+                direction: Withdrawal,
+                expected: Ok((100, Liability)),
+            },
+            Transition {
+                name: "withdrawal reduces collateral",
+                initial: Some((100, Collateral)),
+                delta: 40,
+                direction: Withdrawal,
+                expected: Ok((60, Collateral)),
+            },
+            Transition {
+                name: "withdrawal empties collateral",
+                initial: Some((100, Collateral)),
+                delta: 100,
+                direction: Withdrawal,
+                expected: Ok((0, Collateral)),
+            },
+            Transition {
+                name: "withdrawal beyond collateral borrows the remainder",
+                initial: Some((100, Collateral)),
+                delta: 150,
+                direction: Withdrawal,
+                expected: Ok((50, Liability)),
+            },
+            Transition {
+                name: "withdrawal smaller than liability adds to it",
+                initial: Some((100, Liability)),
+                delta: 40,
+                direction: Withdrawal,
+                expected: Ok((140, Liability)),
+            },
+            Transition {
+                name: "withdrawal larger than liability adds to it",
+                initial: Some((100, Liability)),
+                delta: 150,
+                direction: Withdrawal,
+                expected: Ok((250, Liability)),
+            },
+            Transition {
+                name: "withdrawal overflowing liability fails",
+                initial: Some((u64::MAX, Liability)),
+                delta: 1,
+                direction: Withdrawal,
+                expected: overflow(),
+            },
+        ]
+    }
+
+    #[test]
+    fn applies_every_balance_transition() {
+        for transition in transitions() {
+            let mut user = User::default();
+            if let Some((balance, balance_type)) = transition.initial {
+                user.token_balances[0] = TokenBalance {
+                    balance,
+                    bank_id: BANK,
+                    balance_type: balance_type as u8,
+                    padding: [0; 6],
+                };
+            }
+            let before = user;
+
+            let result = user.update_balance(BANK, transition.delta, transition.direction);
+
+            match transition.expected {
+                Ok((balance, balance_type)) => {
+                    assert!(result.is_ok(), "{}: {:?}", transition.name, result);
+                    let token = &user.token_balances[0];
+                    assert_eq!(
+                        (token.bank_id, token.balance, token.balance_type),
+                        (BANK, balance, balance_type as u8),
+                        "{}",
+                        transition.name
+                    );
+                }
+                Err(error) => {
+                    assert_eq!(result.unwrap_err(), error, "{}", transition.name);
+                    assert_eq!(
+                        bytemuck::bytes_of(&user),
+                        bytemuck::bytes_of(&before),
+                        "{}: failed update modified the account",
+                        transition.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn clears_only_empty_positions() {
+        let mut user = User::default();
+        user.update_balance(1, 100, Direction::Deposit).unwrap();
+        user.update_balance(2, 50, Direction::Withdrawal).unwrap();
+        user.update_balance(3, 10, Direction::Deposit).unwrap();
+
+        // A slot still holding a balance is kept
+        user.clear_balance(2);
+        assert_eq!(user.position_count(), 3);
+
+        user.update_balance(1, 100, Direction::Withdrawal).unwrap();
+        user.clear_balance(1);
+        assert_eq!(user.position_count(), 2);
+        assert_layout(&user);
+        assert_eq!(user.find_balance_by_bank_id(2), -50);
+        assert_eq!(user.find_balance_by_bank_id(3), 10);
+    }
+
+    #[test]
+    fn lists_open_bank_ids_including_bank_zero() {
+        let mut user = User::default();
+        assert!(user.open_bank_ids().is_empty());
+
+        user.update_balance(3, 10, Direction::Deposit).unwrap();
+        user.update_balance(0, 50, Direction::Withdrawal).unwrap();
+        user.update_balance(1, 100, Direction::Deposit).unwrap();
+        user.update_balance(1, 100, Direction::Withdrawal).unwrap();
+        assert_eq!(user.open_bank_ids(), vec![0, 3]);
+    }
+
+    proptest! {
+        #[test]
+        fn keeps_positions_unique_and_sorted(operations in operations(0..BANK_COUNT)) {
+            let mut user = User::default();
+            apply(&mut user, &operations);
+        }
+
+        #[test]
+        fn tracks_net_balance(operations in operations(0..BANK_COUNT)) {
+            let mut user = User::default();
+            let expected = apply(&mut user, &operations);
+            assert_balances(&user, &expected);
+        }
+    }
+}
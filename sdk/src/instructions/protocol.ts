@@ -1,4 +1,8 @@
-import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import {
+  AccountMeta,
+  PublicKey,
+  TransactionInstruction,
+} from "@solana/web3.js";
 import * as anchor from "@coral-xyz/anchor";
 import { BN, Program } from "@coral-xyz/anchor";
 import idl from "../idl/anchor_lending_example.json";
@@ -10,7 +14,7 @@ import {
   getBankTokenAccountPublicKey,
 } from "../pda";
 import { TokenProgram, getTokenProgramId } from "../types/tokenProgram";
-import { BankStatus } from "../states";
+import { AdminRole, BankStatus, PoolStatus } from "../states";
 import { PROGRAM_ID } from "../utils";
 
 const program = new Program<AnchorLendingExample>(idl as AnchorLendingExample);
@@ -36,20 +40,20 @@ export async function getInitializeIx(
 }
 
 /**
- * Create instruction to update the admin authority
+ * Create instruction to propose a new admin authority
  * @param currentAuthority Current admin authority
- * @param newAuthority New admin authority to set
+ * @param newAuthority Authority proposed to take over the admin account
  * @param programId Program ID, defaults to the main program ID
- * @returns Update authority instruction
+ * @returns Propose authority instruction
  */
-export async function getUpdateAuthorityIx(
+export async function getProposeAuthorityIx(
   currentAuthority: PublicKey,
   newAuthority: PublicKey,
   programId: PublicKey = PROGRAM_ID
 ): Promise<TransactionInstruction> {
   const admin = getAdminPublicKey(programId);
   return await program.methods
-    .updateAuthority()
+    .proposeAuthority()
     .accountsPartial({
       admin,
       authority: currentAuthority,
@@ -58,23 +62,244 @@ export async function getUpdateAuthorityIx(
     .instruction();
 }
 
+/**
+ * Create instruction to accept a pending admin authority transfer
+ * @param newAuthority Proposed authority accepting the transfer
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Accept authority instruction
+ */
+export async function getAcceptAuthorityIx(
+  newAuthority: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const admin = getAdminPublicKey(programId);
+  return await program.methods
+    .acceptAuthority()
+    .accountsPartial({
+      admin,
+      newAuthority,
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to cancel a pending admin authority transfer
+ * @param currentAuthority Current admin authority
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Cancel authority instruction
+ */
+export async function getCancelAuthorityIx(
+  currentAuthority: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const admin = getAdminPublicKey(programId);
+  return await program.methods
+    .cancelAuthority()
+    .accountsPartial({
+      admin,
+      authority: currentAuthority,
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to grant an admin role to an account
+ * @param authority Admin authority
+ * @param role Role to grant
+ * @param grantee Account receiving the role
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Grant role instruction
+ */
+export async function getGrantRoleIx(
+  authority: PublicKey,
+  role: AdminRole,
+  grantee: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const admin = getAdminPublicKey(programId);
+  return await program.methods
+    .grantRole(role)
+    .accountsPartial({
+      admin,
+      authority,
+      grantee,
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to revoke an admin role from its holder
+ * @param authority Admin authority
+ * @param role Role to revoke
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Revoke role instruction
+ */
+export async function getRevokeRoleIx(
+  authority: PublicKey,
+  role: AdminRole,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const admin = getAdminPublicKey(programId);
+  return await program.methods
+    .revokeRole(role)
+    .accountsPartial({
+      admin,
+      authority,
+    })
+    .instruction();
+}
+
+/**
+ * Parameters for initializing a new pool
+ */
+export interface PoolConfigParams {
+  /** Pool name, UTF-8 encoded and zero padded to 32 bytes */
+  name: number[];
+  /** Maximum percentage of a liability that can be repaid in a single liquidation */
+  closeFactor: number;
+  /** Maximum liquidation bonus percentage paid to liquidators */
+  maxLiquidationBonus: number;
+  /** Maximum number of token positions a user can hold in this pool */
+  maxPositions: number;
+}
+
+/**
+ * Parameters for updating pool configuration
+ */
+export interface UpdatePoolParams {
+  /** New operational status */
+  status: PoolStatus;
+  /** Maximum percentage of a liability that can be repaid in a single liquidation */
+  closeFactor: number;
+  /** Maximum liquidation bonus percentage paid to liquidators */
+  maxLiquidationBonus: number;
+  /** Maximum number of token positions a user can hold in this pool */
+  maxPositions: number;
+}
+
+/**
+ * Default pool configuration: 50% close factor, 5% liquidation bonus cap, 16 positions
+ */
+export const DEFAULT_POOL_CONFIG: PoolConfigParams = {
+  name: new Array(32).fill(0),
+  closeFactor: 50,
+  maxLiquidationBonus: 5,
+  maxPositions: 16,
+};
+
+/**
+ * Encode a pool name into the fixed 32 byte on-chain representation
+ * @param name Pool name, at most 32 bytes once UTF-8 encoded
+ * @returns Zero padded name bytes
+ * @throws If the encoded name is longer than 32 bytes
+ */
+export function encodePoolName(name: string): number[] {
+  const bytes = Buffer.from(name, "utf8");
+  if (bytes.length > 32) {
+    throw new Error("Pool name too long");
+  }
+  const padded = Buffer.alloc(32);
+  bytes.copy(padded);
+  return Array.from(padded);
+}
+
 /**
  * Create instruction to initialize a new pool
  * @param authority Admin authority
  * @param poolId Pool ID to create
+ * @param params Pool configuration parameters, defaults to DEFAULT_POOL_CONFIG
+ * @param poolAuthority Authority that will manage the pool, defaults to the admin authority
  * @param programId Program ID, defaults to the main program ID
  * @returns Initialize pool instruction
  */
 export async function getInitializePoolIx(
   authority: PublicKey,
   poolId: number,
+  params: PoolConfigParams = DEFAULT_POOL_CONFIG,
+  poolAuthority: PublicKey = authority,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const admin = getAdminPublicKey(programId);
+  const pool = getPoolPublicKey(poolId, programId);
+
+  return await program.methods
+    .initializePool(params)
+    .accountsPartial({
+      authority,
+      admin,
+      pool,
+      poolAuthority,
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to update pool configuration
+ * @param authority Pool authority
+ * @param poolId Pool ID to update
+ * @param params New pool configuration
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Update pool instruction
+ */
+export async function getUpdatePoolIx(
+  authority: PublicKey,
+  poolId: number,
+  params: UpdatePoolParams,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const pool = getPoolPublicKey(poolId, programId);
+
+  return await program.methods
+    .updatePool(poolId, params)
+    .accountsPartial({
+      authority,
+      pool,
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to set the protocol-wide pause flags
+ * @param authority Emergency guardian or admin authority
+ * @param flags Combination of PauseFlag values, replaces the current flags
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Set protocol pause instruction
+ */
+export async function getSetProtocolPauseIx(
+  authority: PublicKey,
+  flags: number,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const admin = getAdminPublicKey(programId);
+
+  return await program.methods
+    .setProtocolPause(flags)
+    .accountsPartial({
+      authority,
+      admin,
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to set the pause flags of a pool
+ * @param authority Pool authority, emergency guardian or admin authority
+ * @param poolId Pool ID to pause
+ * @param flags Combination of PauseFlag values, replaces the current flags
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Set pool pause instruction
+ */
+export async function getSetPoolPauseIx(
+  authority: PublicKey,
+  poolId: number,
+  flags: number,
   programId: PublicKey = PROGRAM_ID
 ): Promise<TransactionInstruction> {
   const admin = getAdminPublicKey(programId);
   const pool = getPoolPublicKey(poolId, programId);
 
   return await program.methods
-    .initializePool()
+    .setPoolPause(poolId, flags)
     .accountsPartial({
       authority,
       admin,
@@ -83,13 +308,45 @@ export async function getInitializePoolIx(
     .instruction();
 }
 
+/**
+ * Parameters for initializing a new bank, weights are percentages
+ */
+export interface BankConfigParams {
+  /** Weight applied to assets for initial collateral ratio calculations */
+  initialAssetWeight: number;
+  /** Weight applied to assets for maintenance collateral ratio calculations */
+  maintenanceAssetWeight: number;
+  /** Weight applied to liabilities for initial borrowing limits */
+  initialLiabilityWeight: number;
+  /** Weight applied to liabilities for maintenance requirements */
+  maintenanceLiabilityWeight: number;
+  /** Restricted mint extensions to accept as MintExtension flags, admin authority only */
+  allowedExtensions: number;
+  /** Flash loan fee in basis points of the borrowed amount */
+  flashLoanFee: number;
+}
+
+/**
+ * Default bank configuration: 80/90% asset weights, 120/110% liability weights,
+ * no flash loan fee
+ */
+export const DEFAULT_BANK_CONFIG: BankConfigParams = {
+  initialAssetWeight: 80,
+  maintenanceAssetWeight: 90,
+  initialLiabilityWeight: 120,
+  maintenanceLiabilityWeight: 110,
+  allowedExtensions: 0,
+  flashLoanFee: 0,
+};
+
 /**
  * Create instruction to initialize bank for a token
- * @param authority Admin authority
+ * @param authority Pool authority, listing manager or admin authority
  * @param mint Token mint address
  * @param poolId Pool ID
  * @param bankId Bank ID to create
  * @param tokenProgram Optional token program ID, defaults to normal SPL token program
+ * @param params Bank configuration parameters, defaults to DEFAULT_BANK_CONFIG
  * @param programId Program ID, defaults to the main program ID
  * @returns Initialize bank instruction
  */
@@ -99,6 +356,7 @@ export async function getInitializeBankIx(
   poolId: number,
   bankId: number,
   tokenProgram: TokenProgram = TokenProgram.TOKEN_PROGRAM,
+  params: BankConfigParams = DEFAULT_BANK_CONFIG,
   programId: PublicKey = PROGRAM_ID
 ): Promise<TransactionInstruction> {
   // Get required PDAs
@@ -108,7 +366,7 @@ export async function getInitializeBankIx(
   const tokenAccount = getBankTokenAccountPublicKey(bank, programId);
 
   return await program.methods
-    .initializeBank(poolId)
+    .initializeBank(poolId, params)
     .accountsPartial({
       authority,
       admin,
@@ -123,7 +381,7 @@ export async function getInitializeBankIx(
 
 /**
  * Create instruction to update bank operational status
- * @param authority Admin authority
+ * @param authority Pool authority, risk manager, emergency guardian or admin authority
  * @param newStatus New operational status to set (must be BankStatus enum value)
  * @param poolId Pool ID
  * @param bankId Bank ID within the pool
@@ -139,18 +397,270 @@ export async function getUpdateBankStatusIx(
   programId: PublicKey = PROGRAM_ID
 ): Promise<TransactionInstruction> {
   // Validate new status is within enum range
-  if (newStatus > BankStatus.ReduceOnly || newStatus < BankStatus.Inactive) {
+  if (newStatus > BankStatus.Sunset || newStatus < BankStatus.Inactive) {
     throw new Error("Invalid bank status value");
   }
 
   const bank = getBankPublicKey(poolId, bankId, programId);
   const admin = getAdminPublicKey(programId);
+  const pool = getPoolPublicKey(poolId, programId);
   return await program.methods
     .updateBankStatus(newStatus)
     .accountsPartial({
       authority,
       bank,
       admin,
+      pool,
+    })
+    .instruction();
+}
+
+/**
+ * Parameters for updating the risk weights of a listed bank, weights are percentages
+ */
+export type UpdateBankConfigParams = Pick<
+  BankConfigParams,
+  | "initialAssetWeight"
+  | "maintenanceAssetWeight"
+  | "initialLiabilityWeight"
+  | "maintenanceLiabilityWeight"
+>;
+
+/**
+ * Create instruction to update the risk weights of a bank
+ * @param authority Pool authority or risk manager
+ * @param params New weights, asset weights at most 100 and liability weights at least 100
+ * @param poolId Pool ID
+ * @param bankId Bank ID within the pool
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Update bank config instruction
+ */
+export async function getUpdateBankConfigIx(
+  authority: PublicKey,
+  params: UpdateBankConfigParams,
+  poolId: number,
+  bankId: number,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const bank = getBankPublicKey(poolId, bankId, programId);
+  const admin = getAdminPublicKey(programId);
+  const pool = getPoolPublicKey(poolId, programId);
+  return await program.methods
+    .updateBankConfig(params)
+    .accountsPartial({
+      authority,
+      bank,
+      admin,
+      pool,
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to update the flash loan fee of a bank
+ * @param authority Pool authority or risk manager
+ * @param fee Flash loan fee in basis points, at most 10,000
+ * @param poolId Pool ID
+ * @param bankId Bank ID within the pool
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Update flash loan fee instruction
+ */
+export async function getUpdateFlashLoanFeeIx(
+  authority: PublicKey,
+  fee: number,
+  poolId: number,
+  bankId: number,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const bank = getBankPublicKey(poolId, bankId, programId);
+  const admin = getAdminPublicKey(programId);
+  const pool = getPoolPublicKey(poolId, programId);
+  return await program.methods
+    .updateFlashLoanFee(fee)
+    .accountsPartial({
+      authority,
+      bank,
+      admin,
+      pool,
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to start delisting a bank
+ * @param authority Pool authority, risk manager or admin authority
+ * @param poolId Pool ID
+ * @param bankId Bank ID within the pool
+ * @param deadline Unix timestamp after which remaining positions can be force settled
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Sunset bank instruction
+ */
+export async function getSunsetBankIx(
+  authority: PublicKey,
+  poolId: number,
+  bankId: number,
+  deadline: number,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const admin = getAdminPublicKey(programId);
+  const pool = getPoolPublicKey(poolId, programId);
+  const bank = getBankPublicKey(poolId, bankId, programId);
+
+  return await program.methods
+    .sunsetBank(new BN(deadline))
+    .accountsPartial({
+      admin,
+      pool,
+      authority,
+      bank,
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to force settle a user position in a sunset bank
+ * @param caller Any signer, settlement is permissionless after the deadline
+ * @param poolId Pool ID
+ * @param bankId Bank ID within the pool
+ * @param userAccount User account PDA holding the position
+ * @param userTokenAccount Token account of the user authority receiving collateral
+ * @param mint Mint of the bank token, required for transfer_checked
+ * @param tokenProgram Optional token program ID, defaults to normal SPL token program
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Force settle instruction
+ */
+export async function getForceSettleIx(
+  caller: PublicKey,
+  poolId: number,
+  bankId: number,
+  userAccount: PublicKey,
+  userTokenAccount: PublicKey,
+  mint: PublicKey,
+  tokenProgram: TokenProgram = TokenProgram.TOKEN_PROGRAM,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const bank = getBankPublicKey(poolId, bankId, programId);
+  const bankTokenAccount = getBankTokenAccountPublicKey(bank, programId);
+
+  return await program.methods
+    .forceSettle()
+    .accountsPartial({
+      caller,
+      bank,
+      bankTokenAccount,
+      userAccount,
+      userTokenAccount,
+      mint,
+      tokenProgram: getTokenProgramId(tokenProgram),
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to settle a liability in a sunset bank against the user's collateral
+ * @param caller Any signer repaying the liability, permissionless after the deadline
+ * @param poolId Pool ID
+ * @param bankId Sunset bank ID holding the liability
+ * @param collateralBankId Bank ID holding the collateral the liability is settled against
+ * @param userAccount User account PDA holding the liability
+ * @param callerLiabilityTokenAccount Caller token account repaying the liability
+ * @param callerCollateralTokenAccount Caller token account receiving collateral
+ * @param mint Mint of the sunset bank token
+ * @param collateralMint Mint of the collateral token
+ * @param tokenProgram Optional token program ID of the sunset bank mint, defaults to SPL token
+ * @param collateralTokenProgram Optional token program ID of the collateral mint, defaults to SPL token
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Force settle liability instruction
+ */
+export async function getForceSettleLiabilityIx(
+  caller: PublicKey,
+  poolId: number,
+  bankId: number,
+  collateralBankId: number,
+  userAccount: PublicKey,
+  callerLiabilityTokenAccount: PublicKey,
+  callerCollateralTokenAccount: PublicKey,
+  mint: PublicKey,
+  collateralMint: PublicKey,
+  tokenProgram: TokenProgram = TokenProgram.TOKEN_PROGRAM,
+  collateralTokenProgram: TokenProgram = TokenProgram.TOKEN_PROGRAM,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const pool = getPoolPublicKey(poolId, programId);
+  const bank = getBankPublicKey(poolId, bankId, programId);
+  const collateralBank = getBankPublicKey(poolId, collateralBankId, programId);
+  const bankTokenAccount = getBankTokenAccountPublicKey(bank, programId);
+  const bankCollateralToken = getBankTokenAccountPublicKey(
+    collateralBank,
+    programId
+  );
+
+  const remainingAccounts: AccountMeta[] = [];
+  if (tokenProgram != collateralTokenProgram) {
+    remainingAccounts.push({
+      pubkey: getTokenProgramId(tokenProgram),
+      isSigner: false,
+      isWritable: false,
+    });
+  }
+
+  return await program.methods
+    .forceSettleLiability()
+    .accountsPartial({
+      caller,
+      callerLiabilityToken: callerLiabilityTokenAccount,
+      callerCollateralToken: callerCollateralTokenAccount,
+      userAccount,
+      bank,
+      collateralBank,
+      bankTokenAccount,
+      bankCollateralToken,
+      pool,
+      mint,
+      collateralMint,
+      tokenProgram: getTokenProgramId(collateralTokenProgram),
+    })
+    .remainingAccounts(remainingAccounts)
+    .instruction();
+}
+
+/**
+ * Create instruction to close a fully settled sunset bank and its vault
+ * @param caller Any signer, closing is permissionless once no positions remain
+ * @param poolId Pool ID
+ * @param bankId Bank ID within the pool
+ * @param poolAuthority Pool authority receiving rent
+ * @param receiverTokenAccount Pool authority token account receiving remaining vault tokens
+ * @param mint Mint of the bank token, required for transfer_checked
+ * @param tokenProgram Optional token program ID, defaults to normal SPL token program
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Close bank instruction
+ */
+export async function getCloseBankIx(
+  caller: PublicKey,
+  poolId: number,
+  bankId: number,
+  poolAuthority: PublicKey,
+  receiverTokenAccount: PublicKey,
+  mint: PublicKey,
+  tokenProgram: TokenProgram = TokenProgram.TOKEN_PROGRAM,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const pool = getPoolPublicKey(poolId, programId);
+  const bank = getBankPublicKey(poolId, bankId, programId);
+  const bankTokenAccount = getBankTokenAccountPublicKey(bank, programId);
+
+  return await program.methods
+    .closeBank()
+    .accountsPartial({
+      caller,
+      pool,
+      poolAuthority,
+      bank,
+      bankTokenAccount,
+      receiverTokenAccount,
+      mint,
+      tokenProgram: getTokenProgramId(tokenProgram),
     })
     .instruction();
 }
@@ -175,7 +685,7 @@ export interface UpdatePriceParams {
 
 /**
  * Create instruction to update price data for a bank
- * @param authority Admin authority
+ * @param authority Oracle keeper or admin authority
  * @param params Price update parameters containing new price data
  * @param poolId Pool ID
  * @param bankId Bank ID within the pool
@@ -0,0 +1,63 @@
+import {
+  Commitment,
+  Connection,
+  PublicKey,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import {
+  addExtraAccountMetasForExecute,
+  getMint,
+  getTransferHook,
+} from "@solana/spl-token";
+import { TokenProgram, getTokenProgramId } from "../types/tokenProgram";
+
+/**
+ * Append the accounts a transfer hook mint needs to a lending instruction
+ * The program resolves the hook's extra account metas from its remaining accounts,
+ * mints without a transfer hook leave the instruction unchanged
+ * @param connection Connection used to read the mint and validation account
+ * @param instruction Deposit, withdraw or liquidate instruction to extend
+ * @param mint Mint being transferred, also passed to the instruction for transfer_checked
+ * @param source Token account the tokens move from
+ * @param destination Token account the tokens move to
+ * @param authority Owner or bank PDA authorizing the transfer
+ * @param amount Amount of tokens transferred
+ * @param tokenProgram Token program of the mint, defaults to Token-2022
+ * @param commitment Optional commitment for the account reads
+ * @returns The same instruction with the hook accounts appended
+ */
+export async function addTransferHookAccounts(
+  connection: Connection,
+  instruction: TransactionInstruction,
+  mint: PublicKey,
+  source: PublicKey,
+  destination: PublicKey,
+  authority: PublicKey,
+  amount: number | bigint,
+  tokenProgram: TokenProgram = TokenProgram.TOKEN_2022_PROGRAM,
+  commitment?: Commitment
+): Promise<TransactionInstruction> {
+  const mintInfo = await getMint(
+    connection,
+    mint,
+    commitment,
+    getTokenProgramId(tokenProgram)
+  );
+  const transferHook = getTransferHook(mintInfo);
+  if (!transferHook || transferHook.programId.equals(PublicKey.default)) {
+    return instruction;
+  }
+
+  await addExtraAccountMetasForExecute(
+    connection,
+    instruction,
+    transferHook.programId,
+    source,
+    mint,
+    destination,
+    authority,
+    amount,
+    commitment
+  );
+  return instruction;
+}
@@ -7,14 +7,20 @@ import { Program } from "@coral-xyz/anchor";
 import idl from "../idl/anchor_lending_example.json";
 import { AnchorLendingExample } from "../types/anchor_lending_example";
 import {
+  getAdminPublicKey,
   getBankPublicKey,
   getBankTokenAccountPublicKey,
+  getNativeTokenAccountPublicKey,
+  getPoolPublicKey,
   getUserPublicKey,
 } from "../pda";
 import { TokenProgram, getTokenProgramId } from "../types/tokenProgram";
 import { PROGRAM_ID } from "../utils";
 import { BN } from "bn.js";
-import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  NATIVE_MINT,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
 
 const program = new Program<AnchorLendingExample>(idl as AnchorLendingExample);
 
@@ -33,95 +39,97 @@ export async function getInitializeUserIx(
   programId: PublicKey = PROGRAM_ID
 ): Promise<TransactionInstruction> {
   const user = getUserPublicKey(poolId, userId, authority, programId);
+  const pool = getPoolPublicKey(poolId, programId);
 
   return await program.methods
     .initializeUser(poolId, userId)
     .accountsPartial({
       user,
       authority,
+      pool,
     })
     .instruction();
 }
 
 /**
  * Create instruction to deposit tokens into a bank
- * @param user User's wallet
- * @param userTokenAccount User's token account to deposit from
- * @param bankTokenAccount Bank's token account to deposit to
- * @param userAccount User account PDA
+ * @param user User's wallet, the current authority of the user account
+ * @param userAccount User account PDA holding the position
  * @param poolId Pool ID of the bank
  * @param bankId Bank ID to deposit to
  * @param amount Amount of tokens to deposit
+ * @param userTokenAccount User's token account to deposit from
+ * @param mint Mint of the bank token, required for transfer_checked
  * @param tokenProgram Optional token program ID, defaults to normal SPL token program
+ * @param all Repay the whole liability, ignoring amount
  * @param programId Program ID, defaults to the main program ID
  * @returns Deposit instruction
  */
 export async function getDepositIx(
   user: PublicKey,
-  userId: number,
+  userAccount: PublicKey,
   poolId: number,
   bankId: number,
   amount: number,
   userTokenAccount: PublicKey,
-  mint?: PublicKey,
+  mint: PublicKey,
   tokenProgram: TokenProgram = TokenProgram.TOKEN_PROGRAM,
+  all: boolean = false,
   programId: PublicKey = PROGRAM_ID
 ): Promise<TransactionInstruction> {
   const bank = getBankPublicKey(poolId, bankId, programId);
-  const userAccount = getUserPublicKey(poolId, userId, user, programId);
   const bankTokenAccount = getBankTokenAccountPublicKey(bank, programId);
-
-  let remainingAccounts: AccountMeta[] = [];
-  if (mint) {
-    remainingAccounts.push({
-      pubkey: mint,
-      isWritable: false,
-      isSigner: false,
-    });
-  }
+  const pool = getPoolPublicKey(poolId, programId);
+  const admin = getAdminPublicKey(programId);
 
   return await program.methods
-    .deposit(new BN(amount))
+    .deposit(new BN(amount), all)
     .accountsPartial({
       user,
       userTokenAccount,
       bankTokenAccount,
       userAccount,
       bank,
+      pool,
+      admin,
+      mint,
       tokenProgram: getTokenProgramId(tokenProgram),
     })
-    .remainingAccounts(remainingAccounts)
     .instruction();
 }
 
 /**
  * Create instruction to withdraw tokens from a bank
- * @param user User's wallet
- * @param userId User ID in the pool
+ * @param user User's wallet, the current authority of the user account
+ * @param userAccount User account PDA holding the position
  * @param poolId Pool ID of the bank
  * @param bankId Bank ID to withdraw from
  * @param amount Amount of tokens to withdraw
  * @param userTokenAccount User's token account to receive tokens
- * @param mint Optional mint account for transfer_checked
+ * @param userBankId Bank IDs of the user's open positions for the health check
+ * @param mint Mint of the bank token, required for transfer_checked
  * @param tokenProgram Optional token program ID, defaults to normal SPL token program
+ * @param all Withdraw the whole collateral, ignoring amount
  * @param programId Program ID, defaults to the main program ID
  * @returns Withdraw instruction
  */
 export async function getWithdrawIx(
   user: PublicKey,
-  userId: number,
+  userAccount: PublicKey,
   poolId: number,
   bankId: number,
   amount: number,
   userTokenAccount: PublicKey,
   userBankId: number[],
-  mint?: PublicKey,
+  mint: PublicKey,
   tokenProgram: TokenProgram = TokenProgram.TOKEN_PROGRAM,
+  all: boolean = false,
   programId: PublicKey = PROGRAM_ID
 ): Promise<TransactionInstruction> {
   const bank = getBankPublicKey(poolId, bankId, programId);
-  const userAccount = getUserPublicKey(poolId, userId, user, programId);
   const bankTokenAccount = getBankTokenAccountPublicKey(bank, programId);
+  const pool = getPoolPublicKey(poolId, programId);
+  const admin = getAdminPublicKey(programId);
 
   let remainingAccounts: AccountMeta[] = [];
 
@@ -134,22 +142,120 @@ export async function getWithdrawIx(
     });
   }
 
-  if (mint) {
-    remainingAccounts.push({
-      pubkey: mint,
-      isWritable: false,
-      isSigner: false,
-    });
-  }
-
   return await program.methods
-    .withdraw(new BN(amount))
+    .withdraw(new BN(amount), all)
     .accountsPartial({
       user,
       userTokenAccount,
       bankTokenAccount,
       userAccount,
       bank,
+      pool,
+      admin,
+      mint,
+      tokenProgram: getTokenProgramId(tokenProgram),
+    })
+    .remainingAccounts(remainingAccounts)
+    .instruction();
+}
+
+/**
+ * Create instruction to deposit native SOL into a wrapped SOL bank
+ * The lamports are wrapped through a temporary account closed back to the user
+ * @param user User's wallet paying the lamports, the user account authority
+ * @param userAccount User account PDA holding the position
+ * @param poolId Pool ID of the bank
+ * @param bankId Wrapped SOL bank ID to deposit to
+ * @param amount Amount of lamports to deposit
+ * @param mint Native mint of the bank, defaults to the SPL token native mint
+ * @param tokenProgram Optional token program ID, defaults to normal SPL token program
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Deposit native instruction
+ */
+export async function getDepositNativeIx(
+  user: PublicKey,
+  userAccount: PublicKey,
+  poolId: number,
+  bankId: number,
+  amount: number,
+  mint: PublicKey = NATIVE_MINT,
+  tokenProgram: TokenProgram = TokenProgram.TOKEN_PROGRAM,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const bank = getBankPublicKey(poolId, bankId, programId);
+  const bankTokenAccount = getBankTokenAccountPublicKey(bank, programId);
+  const pool = getPoolPublicKey(poolId, programId);
+  const admin = getAdminPublicKey(programId);
+
+  return await program.methods
+    .depositNative(new BN(amount))
+    .accountsPartial({
+      user,
+      temporaryTokenAccount: getNativeTokenAccountPublicKey(
+        userAccount,
+        programId
+      ),
+      bankTokenAccount,
+      userAccount,
+      bank,
+      pool,
+      admin,
+      mint,
+      tokenProgram: getTokenProgramId(tokenProgram),
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to withdraw native SOL from a wrapped SOL bank
+ * The tokens are unwrapped by closing a temporary account to the user
+ * @param user User's wallet receiving the lamports, the user account authority
+ * @param userAccount User account PDA holding the position
+ * @param poolId Pool ID of the bank
+ * @param bankId Wrapped SOL bank ID to withdraw from
+ * @param amount Amount of lamports to withdraw
+ * @param userBankId Bank IDs of the user's open positions for the health check
+ * @param mint Native mint of the bank, defaults to the SPL token native mint
+ * @param tokenProgram Optional token program ID, defaults to normal SPL token program
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Withdraw native instruction
+ */
+export async function getWithdrawNativeIx(
+  user: PublicKey,
+  userAccount: PublicKey,
+  poolId: number,
+  bankId: number,
+  amount: number,
+  userBankId: number[],
+  mint: PublicKey = NATIVE_MINT,
+  tokenProgram: TokenProgram = TokenProgram.TOKEN_PROGRAM,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const bank = getBankPublicKey(poolId, bankId, programId);
+  const bankTokenAccount = getBankTokenAccountPublicKey(bank, programId);
+  const pool = getPoolPublicKey(poolId, programId);
+  const admin = getAdminPublicKey(programId);
+
+  const remainingAccounts: AccountMeta[] = userBankId.map((id) => ({
+    pubkey: getBankPublicKey(poolId, id, programId),
+    isWritable: false,
+    isSigner: false,
+  }));
+
+  return await program.methods
+    .withdrawNative(new BN(amount))
+    .accountsPartial({
+      user,
+      temporaryTokenAccount: getNativeTokenAccountPublicKey(
+        userAccount,
+        programId
+      ),
+      bankTokenAccount,
+      userAccount,
+      bank,
+      pool,
+      admin,
+      mint,
       tokenProgram: getTokenProgramId(tokenProgram),
     })
     .remainingAccounts(remainingAccounts)
@@ -164,6 +270,8 @@ export async function getWithdrawIx(
  * @param collateralBankId The bank ID for collateral token
  * @param liabilityBankId The bank ID for liability token
  * @param liabilityAmount The amount of liability token to repay
+ * @param collateralMint Mint of the collateral token, required for transfer_checked
+ * @param liabilityMint Mint of the liability token, required for transfer_checked
  * @param programId The program ID, defaults to PROGRAM_ID
  * @returns The liquidate instruction
  */
@@ -177,9 +285,10 @@ export async function getLiquidateIx(
   liquidatorCollateralTokenAccount: PublicKey,
   liquidatorLiabilityTokenAccount: PublicKey,
   userBankId: number[],
+  collateralMint: PublicKey,
+  liabilityMint: PublicKey,
   collateralTokenProgramId: TokenProgram = TokenProgram.TOKEN_PROGRAM,
   liabilityTokenProgramId: TokenProgram = TokenProgram.TOKEN_PROGRAM,
-  mints?: PublicKey[],
   programId: PublicKey = PROGRAM_ID
 ): Promise<TransactionInstruction> {
   // Get bank PDAs
@@ -198,6 +307,8 @@ export async function getLiquidateIx(
 
   // Get unhealthy user account PDA
   const userAccount = unhealthyUser;
+  const pool = getPoolPublicKey(poolId, programId);
+  const admin = getAdminPublicKey(programId);
 
   let remainingAccounts: AccountMeta[] = [];
 
@@ -218,16 +329,6 @@ export async function getLiquidateIx(
     });
   }
 
-  if (mints) {
-    for (const mint of mints) {
-      remainingAccounts.push({
-        pubkey: mint,
-        isWritable: false,
-        isSigner: false,
-      });
-    }
-  }
-
   return await program.methods
     .liquidate(new BN(liabilityAmount))
     .accountsPartial({
@@ -239,8 +340,346 @@ export async function getLiquidateIx(
       liabilityBank,
       bankCollateralToken,
       bankLiabilityToken,
+      pool,
+      collateralMint,
+      liabilityMint,
+      admin,
       tokenProgram: getTokenProgramId(collateralTokenProgramId),
     })
     .remainingAccounts(remainingAccounts)
     .instruction();
 }
+
+/**
+ * Create instruction to flash borrow tokens from a bank vault
+ * A flash repay instruction for the amount plus the bank's fee must follow in the same transaction
+ * @param user Borrower's wallet
+ * @param poolId Pool ID of the bank
+ * @param bankId Bank ID to borrow from
+ * @param amount Amount of tokens to borrow
+ * @param userTokenAccount Borrower's token account to receive tokens
+ * @param mint Mint of the bank token, required for transfer_checked
+ * @param tokenProgram Optional token program ID, defaults to normal SPL token program
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Flash borrow instruction
+ */
+export async function getFlashBorrowIx(
+  user: PublicKey,
+  poolId: number,
+  bankId: number,
+  amount: number,
+  userTokenAccount: PublicKey,
+  mint: PublicKey,
+  tokenProgram: TokenProgram = TokenProgram.TOKEN_PROGRAM,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const bank = getBankPublicKey(poolId, bankId, programId);
+  const bankTokenAccount = getBankTokenAccountPublicKey(bank, programId);
+  const pool = getPoolPublicKey(poolId, programId);
+  const admin = getAdminPublicKey(programId);
+
+  return await program.methods
+    .flashBorrow(new BN(amount))
+    .accountsPartial({
+      user,
+      userTokenAccount,
+      bankTokenAccount,
+      bank,
+      pool,
+      admin,
+      mint,
+      tokenProgram: getTokenProgramId(tokenProgram),
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to repay a flash loan
+ * @param user Wallet repaying the loan
+ * @param poolId Pool ID of the bank
+ * @param bankId Bank ID the loan was taken from
+ * @param amount Borrowed amount plus the bank's flash loan fee
+ * @param userTokenAccount Token account to repay from
+ * @param mint Mint of the bank token, required for transfer_checked
+ * @param tokenProgram Optional token program ID, defaults to normal SPL token program
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Flash repay instruction
+ */
+export async function getFlashRepayIx(
+  user: PublicKey,
+  poolId: number,
+  bankId: number,
+  amount: number,
+  userTokenAccount: PublicKey,
+  mint: PublicKey,
+  tokenProgram: TokenProgram = TokenProgram.TOKEN_PROGRAM,
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const bank = getBankPublicKey(poolId, bankId, programId);
+  const bankTokenAccount = getBankTokenAccountPublicKey(bank, programId);
+
+  return await program.methods
+    .flashRepay(new BN(amount))
+    .accountsPartial({
+      user,
+      userTokenAccount,
+      bankTokenAccount,
+      bank,
+      mint,
+      tokenProgram: getTokenProgramId(tokenProgram),
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to put a user account in flashloan mode
+ * Health checks are deferred until an end flashloan instruction for the same user account,
+ * which must follow in the same transaction
+ * @param authority User account authority
+ * @param userAccount User account PDA
+ * @returns Begin flashloan instruction
+ */
+export async function getBeginFlashloanIx(
+  authority: PublicKey,
+  userAccount: PublicKey
+): Promise<TransactionInstruction> {
+  return await program.methods
+    .beginFlashloan()
+    .accountsPartial({
+      userAccount,
+      authority,
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to leave flashloan mode once the position passes the initial health check
+ * @param authority User account authority
+ * @param userAccount User account PDA
+ * @param poolId Pool ID of the user account
+ * @param userBankId Bank IDs of the user's positions at the end of the flashloan
+ * @param programId Program ID, defaults to the main program ID
+ * @returns End flashloan instruction
+ */
+export async function getEndFlashloanIx(
+  authority: PublicKey,
+  userAccount: PublicKey,
+  poolId: number,
+  userBankId: number[],
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const remainingAccounts: AccountMeta[] = userBankId.map((id) => ({
+    pubkey: getBankPublicKey(poolId, id, programId),
+    isWritable: false,
+    isSigner: false,
+  }));
+
+  return await program.methods
+    .endFlashloan()
+    .accountsPartial({
+      userAccount,
+      authority,
+    })
+    .remainingAccounts(remainingAccounts)
+    .instruction();
+}
+
+/**
+ * Create instruction to propose a new authority for a user account
+ * @param authority Current user account authority
+ * @param userAccount User account PDA to transfer
+ * @param newAuthority Authority proposed to take over the user account
+ * @returns Propose user authority instruction
+ */
+export async function getProposeUserAuthorityIx(
+  authority: PublicKey,
+  userAccount: PublicKey,
+  newAuthority: PublicKey
+): Promise<TransactionInstruction> {
+  return await program.methods
+    .proposeUserAuthority()
+    .accountsPartial({
+      userAccount,
+      authority,
+      newAuthority,
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to accept a pending user account authority transfer
+ * @param newAuthority Proposed authority accepting the transfer
+ * @param userAccount User account PDA being transferred
+ * @returns Accept user authority instruction
+ */
+export async function getAcceptUserAuthorityIx(
+  newAuthority: PublicKey,
+  userAccount: PublicKey
+): Promise<TransactionInstruction> {
+  return await program.methods
+    .acceptUserAuthority()
+    .accountsPartial({
+      userAccount,
+      newAuthority,
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to grow a user account created before the pending
+ * authority was appended to the layout
+ * @param payer Account paying the extra rent
+ * @param userAccount User account PDA to migrate
+ * @returns Migrate user instruction
+ */
+export async function getMigrateUserIx(
+  payer: PublicKey,
+  userAccount: PublicKey
+): Promise<TransactionInstruction> {
+  return await program.methods
+    .migrateUser()
+    .accountsPartial({
+      userAccount,
+      payer,
+    })
+    .instruction();
+}
+
+/**
+ * Create instruction to query the health of a user position
+ * Simulate it and decode the return data with UserHealth.decode
+ * @param userAccount User account PDA to value
+ * @param poolId Pool ID of the user account
+ * @param userBankId Bank IDs of the user's open positions
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Get user health instruction
+ */
+export async function getUserHealthIx(
+  userAccount: PublicKey,
+  poolId: number,
+  userBankId: number[],
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const remainingAccounts: AccountMeta[] = userBankId.map((id) => ({
+    pubkey: getBankPublicKey(poolId, id, programId),
+    isWritable: false,
+    isSigner: false,
+  }));
+
+  return await program.methods
+    .getUserHealth()
+    .accountsPartial({
+      userAccount,
+    })
+    .remainingAccounts(remainingAccounts)
+    .instruction();
+}
+
+/**
+ * Create instruction to query the largest collateral withdrawal from a bank
+ * Simulate it and decode the return data as a little-endian u64
+ * @param userAccount User account PDA to value
+ * @param poolId Pool ID of the user account
+ * @param bankId Bank ID to withdraw from
+ * @param userBankId Bank IDs of the user's open positions
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Get max withdraw instruction
+ */
+export async function getMaxWithdrawIx(
+  userAccount: PublicKey,
+  poolId: number,
+  bankId: number,
+  userBankId: number[],
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const bank = getBankPublicKey(poolId, bankId, programId);
+  const remainingAccounts: AccountMeta[] = [
+    ...new Set([...userBankId, bankId]),
+  ].map((id) => ({
+    pubkey: getBankPublicKey(poolId, id, programId),
+    isWritable: false,
+    isSigner: false,
+  }));
+
+  return await program.methods
+    .getMaxWithdraw()
+    .accountsPartial({
+      userAccount,
+      bank,
+    })
+    .remainingAccounts(remainingAccounts)
+    .instruction();
+}
+
+/**
+ * Create instruction to query the largest borrow from a bank
+ * Simulate it and decode the return data as a little-endian u64
+ * @param userAccount User account PDA to value
+ * @param poolId Pool ID of the user account
+ * @param bankId Bank ID to borrow from
+ * @param userBankId Bank IDs of the user's open positions
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Get max borrow instruction
+ */
+export async function getMaxBorrowIx(
+  userAccount: PublicKey,
+  poolId: number,
+  bankId: number,
+  userBankId: number[],
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const bank = getBankPublicKey(poolId, bankId, programId);
+  const remainingAccounts: AccountMeta[] = [
+    ...new Set([...userBankId, bankId]),
+  ].map((id) => ({
+    pubkey: getBankPublicKey(poolId, id, programId),
+    isWritable: false,
+    isSigner: false,
+  }));
+
+  return await program.methods
+    .getMaxBorrow()
+    .accountsPartial({
+      userAccount,
+      bank,
+    })
+    .remainingAccounts(remainingAccounts)
+    .instruction();
+}
+
+/**
+ * Create instruction to query the price of a bank's token at which a user becomes liquidatable
+ * Simulate it and decode the return data as a Borsh option of a little-endian u64,
+ * in the bank's price feed units and exponent
+ * @param userAccount User account PDA to value
+ * @param poolId Pool ID of the user account
+ * @param bankId Bank ID whose token price is solved for
+ * @param userBankId Bank IDs of the user's open positions
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Get liquidation price instruction
+ */
+export async function getLiquidationPriceIx(
+  userAccount: PublicKey,
+  poolId: number,
+  bankId: number,
+  userBankId: number[],
+  programId: PublicKey = PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const bank = getBankPublicKey(poolId, bankId, programId);
+  const remainingAccounts: AccountMeta[] = [
+    ...new Set([...userBankId, bankId]),
+  ].map((id) => ({
+    pubkey: getBankPublicKey(poolId, id, programId),
+    isWritable: false,
+    isSigner: false,
+  }));
+
+  return await program.methods
+    .getLiquidationPrice()
+    .accountsPartial({
+      userAccount,
+      bank,
+    })
+    .remainingAccounts(remainingAccounts)
+    .instruction();
+}
@@ -7,6 +7,7 @@ export const POOL_SEED = "pool";
 export const BANK_SEED = "bank";
 export const TOKEN_ACCOUNT_SEED = "token_account";
 export const USER_SEED = "user";
+export const NATIVE_TOKEN_ACCOUNT_SEED = "native_token_account";
 
 /**
  * Derive the admin PDA address and bump
@@ -162,3 +163,32 @@ export function getUserPublicKey(
 ): PublicKey {
   return getUserPublicKeyAndNonce(poolId, userId, authority, programId)[0];
 }
+
+/**
+ * Derive the temporary wrapped SOL account PDA address and bump
+ * @param userAccount User account PDA the native transfer is for
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Tuple of [address, bump]
+ */
+export function getNativeTokenAccountPublicKeyAndNonce(
+  userAccount: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(NATIVE_TOKEN_ACCOUNT_SEED), userAccount.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derive the temporary wrapped SOL account PDA address
+ * @param userAccount User account PDA the native transfer is for
+ * @param programId Program ID, defaults to the main program ID
+ * @returns Temporary wrapped SOL account PDA
+ */
+export function getNativeTokenAccountPublicKey(
+  userAccount: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+): PublicKey {
+  return getNativeTokenAccountPublicKeyAndNonce(userAccount, programId)[0];
+}
@@ -5,14 +5,68 @@ import { AnchorLendingExample } from "../types/anchor_lending_example";
 
 const program = new Program<AnchorLendingExample>(idl as AnchorLendingExample);
 
+/**
+ * Operations that can be paused protocol-wide or per pool, combined as bit flags
+ */
+export enum PauseFlag {
+  /** Deposits are paused */
+  Deposit = 1 << 0,
+  /** Withdrawals are paused */
+  Withdraw = 1 << 1,
+  /** Liquidations are paused */
+  Liquidate = 1 << 2,
+}
+
+/**
+ * All pause flags combined
+ */
+export const PAUSE_ALL =
+  PauseFlag.Deposit | PauseFlag.Withdraw | PauseFlag.Liquidate;
+
+/**
+ * Roles that can be delegated by the admin authority
+ */
+export enum AdminRole {
+  /** Manages bank risk settings and status */
+  RiskManager = 0,
+  /** Lists new banks */
+  ListingManager = 1,
+  /** Pauses, freezes or sets banks to reduce-only during incidents */
+  EmergencyGuardian = 2,
+  /** Updates bank prices */
+  OracleKeeper = 3,
+}
+
 export class AdminAccount {
   authority: PublicKey;
+  pendingAuthority: PublicKey;
+  riskManager: PublicKey;
+  listingManager: PublicKey;
+  emergencyGuardian: PublicKey;
+  oracleKeeper: PublicKey;
   poolCount: number;
+  pauseFlags: number;
   bump: number;
 
-  constructor(args: { authority: PublicKey; poolCount: number; bump: number }) {
+  constructor(args: {
+    authority: PublicKey;
+    pendingAuthority: PublicKey;
+    riskManager: PublicKey;
+    listingManager: PublicKey;
+    emergencyGuardian: PublicKey;
+    oracleKeeper: PublicKey;
+    poolCount: number;
+    pauseFlags: number;
+    bump: number;
+  }) {
     this.authority = args.authority;
+    this.pendingAuthority = args.pendingAuthority;
+    this.riskManager = args.riskManager;
+    this.listingManager = args.listingManager;
+    this.emergencyGuardian = args.emergencyGuardian;
+    this.oracleKeeper = args.oracleKeeper;
     this.poolCount = args.poolCount;
+    this.pauseFlags = args.pauseFlags;
     this.bump = args.bump;
   }
 
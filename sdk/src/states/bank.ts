@@ -15,8 +15,37 @@ export enum BankStatus {
   Active = 1,
   /** Bank is in reduce-only mode - no new positions allowed */
   ReduceOnly = 2,
+  /** Bank only accepts deposits and repayments, no funds can leave */
+  DepositOnly = 3,
+  /** Bank is frozen during an incident, every operation is blocked */
+  Frozen = 4,
+  /** Bank is being delisted, positions can only be closed */
+  Sunset = 5,
 }
 
+/**
+ * Token-2022 mint extensions rejected at listing unless allowlisted, combined as bit flags
+ */
+export enum MintExtension {
+  /** A delegate can move or burn tokens out of the vault */
+  PermanentDelegate = 1 << 0,
+  /** Tokens cannot be transferred out of the vault */
+  NonTransferable = 1 << 1,
+  /** Balances can move through confidential transfers the bank cannot see */
+  ConfidentialTransfer = 1 << 2,
+  /** Transfers invoke an external program */
+  TransferHook = 1 << 3,
+  /** New token accounts, including the vault, may start frozen */
+  DefaultAccountState = 1 << 4,
+  /** The mint can be closed and recreated once supply reaches zero */
+  MintCloseAuthority = 1 << 5,
+}
+
+/**
+ * Seconds a bank must stay frozen before its status can change again
+ */
+export const FROZEN_TIMELOCK_SECONDS = 24 * 60 * 60;
+
 /**
  * Represents price feed data from oracle
  */
@@ -53,6 +82,22 @@ export class BankAccount {
   decimals: number;
   /** Padding for memory alignment */
   padding: number[];
+  /** Restricted mint extensions allowlisted by the admin authority */
+  allowedExtensions: number;
+  /** Flash loan fee in basis points of the borrowed amount */
+  flashLoanFee: number;
+  /** Unix timestamp of the last status change */
+  statusUpdatedAt: BN;
+  /** Unix timestamp after which a sunset bank can be force settled */
+  sunsetDeadline: BN;
+  /** Total collateral deposited by users */
+  totalDeposits: BN;
+  /** Total liabilities owed by users */
+  totalLiabilities: BN;
+  /** Flash loan repayment owed including the fee, only non-zero within a transaction */
+  flashLoanOutstanding: BN;
+  /** Flash loan fees accrued to the vault */
+  flashLoanFees: BN;
   /** The token mint address */
   mint: PublicKey;
   /** Current price feed data */
@@ -65,6 +110,14 @@ export class BankAccount {
     status: BankStatus;
     decimals: number;
     padding: number[];
+    allowedExtensions: number;
+    flashLoanFee: number;
+    statusUpdatedAt: BN;
+    sunsetDeadline: BN;
+    totalDeposits: BN;
+    totalLiabilities: BN;
+    flashLoanOutstanding: BN;
+    flashLoanFees: BN;
     mint: PublicKey;
     priceMessage: PriceFeedMessage;
   }) {
@@ -74,6 +127,14 @@ export class BankAccount {
     this.status = args.status;
     this.decimals = args.decimals;
     this.padding = args.padding;
+    this.allowedExtensions = args.allowedExtensions;
+    this.flashLoanFee = args.flashLoanFee;
+    this.statusUpdatedAt = args.statusUpdatedAt;
+    this.sunsetDeadline = args.sunsetDeadline;
+    this.totalDeposits = args.totalDeposits;
+    this.totalLiabilities = args.totalLiabilities;
+    this.flashLoanOutstanding = args.flashLoanOutstanding;
+    this.flashLoanFees = args.flashLoanFees;
     this.mint = args.mint;
     this.priceMessage = args.priceMessage;
   }
@@ -98,43 +159,21 @@ export class BankAccount {
 
   /**
    * Calculate USD value of token amount using bank price feed
-   * Returns the USD value scaled to 6 decimal places
+   * Returns the USD value scaled to 6 decimal places, rounding down like the program
    *
    * @param amount - The token amount as BN
-   * @returns Calculated USD value with 6 decimals or error if:
-   * - Amount too large
-   * - Price feed invalid
-   * - Math overflow
+   * @returns Calculated USD value with 6 decimals or error if the price is zero
    */
   calculateUsdValue(amount: BN): BN {
-    // Convert to u128 equivalent
-    const amountU128 = amount.toNumber();
-    const priceU128 = this.priceMessage.price.toNumber();
-
-    // Check for overflow
-    if (amountU128 > Number.MAX_SAFE_INTEGER / priceU128) {
-      throw new Error("Amount too large for calculation");
+    if (this.priceMessage.price.isZero()) {
+      throw new Error("Price must be positive");
     }
 
-    // 1. Adjust price by exponent
-    const actualPrice =
-      priceU128 * Math.pow(10, Math.abs(this.priceMessage.exponent));
-
-    // 2. Calculate base value
-    const baseValue = amountU128 * actualPrice;
-
-    // 3. Adjust for token decimals
-    const decimalAdjusted = baseValue / Math.pow(10, this.decimals);
-
-    // 4. Adjust to get 6 decimals output
-    const expAdjustment = 6 - this.priceMessage.exponent;
-    const adjFactor = Math.pow(10, Math.abs(expAdjustment));
-
-    const finalValue =
-      expAdjustment > 0
-        ? decimalAdjusted * adjFactor
-        : decimalAdjusted / adjFactor;
+    // amount * price * 10^(6 + exponent - decimals), dividing only once at the end
+    const baseValue = amount.mul(this.priceMessage.price);
+    const scale = 6 + this.priceMessage.exponent - this.decimals;
+    const scaleFactor = new BN(10).pow(new BN(Math.abs(scale)));
 
-    return new BN(Math.floor(finalValue));
+    return scale >= 0 ? baseValue.mul(scaleFactor) : baseValue.div(scaleFactor);
   }
 }
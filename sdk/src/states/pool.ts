@@ -1,5 +1,6 @@
 import { Buffer } from "buffer";
 import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
 import idl from "../idl/anchor_lending_example.json";
 import { AnchorLendingExample } from "../types/anchor_lending_example";
 
@@ -7,14 +8,55 @@ const program = new anchor.Program<AnchorLendingExample>(
   idl as AnchorLendingExample
 );
 
+/**
+ * Pool operational status
+ */
+export enum PoolStatus {
+  /** Pool is inactive, no new users or deposits allowed */
+  Inactive = 0,
+  /** Pool is active and can be used for all operations */
+  Active = 1,
+}
+
 /**
  * Represents the on-chain Pool account state
  */
 export class PoolAccount {
+  /** The authority allowed to manage this pool */
+  authority: PublicKey;
+  /** Pool name, UTF-8 encoded and zero padded */
+  name: number[];
+  /** Number of banks initialized in this pool */
   bankCount: number;
+  /** Current operational status */
+  status: PoolStatus;
+  /** Maximum percentage of a liability that can be repaid in a single liquidation */
+  closeFactor: number;
+  /** Maximum liquidation bonus percentage paid to liquidators */
+  maxLiquidationBonus: number;
+  /** Maximum number of token positions a user can hold in this pool */
+  maxPositions: number;
+  /** Paused operations in this pool, see PauseFlag */
+  pauseFlags: number;
 
-  constructor(args: { bankCount: number }) {
+  constructor(args: {
+    authority: PublicKey;
+    name: number[];
+    bankCount: number;
+    status: PoolStatus;
+    closeFactor: number;
+    maxLiquidationBonus: number;
+    maxPositions: number;
+    pauseFlags: number;
+  }) {
+    this.authority = args.authority;
+    this.name = args.name;
     this.bankCount = args.bankCount;
+    this.status = args.status;
+    this.closeFactor = args.closeFactor;
+    this.maxLiquidationBonus = args.maxLiquidationBonus;
+    this.maxPositions = args.maxPositions;
+    this.pauseFlags = args.pauseFlags;
   }
 
   /**
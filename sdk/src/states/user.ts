@@ -44,7 +44,7 @@ export interface TokenBalance {
  */
 export class UserAccount {
   /** Size of the user account for space allocation */
-  static readonly LEN = 8 + (32 + 2 + 1 + 1 + 4 + 16 * (8 + 1 + 1 + 6));
+  static readonly LEN = 8 + (32 + 2 + 1 + 1 + 1 + 3 + 16 * (8 + 1 + 1 + 6) + 32);
 
   /** The user's authority (usually their wallet address) */
   authority: PublicKey;
@@ -54,23 +54,30 @@ export class UserAccount {
   poolId: number;
   /** Bump seed for PDA validation - u8 */
   bump: number;
-  /** Padding for memory alignment - 4 bytes */
+  /** Non-zero while in flashloan mode, health checks are deferred to end flashloan - u8 */
+  flashloan: number;
+  /** Padding for memory alignment - 3 bytes */
   padding: number[];
   /** Token balances array with exactly 16 different tokens */
   tokenBalances: TokenBalance[];
+  /** Authority proposed to take over this account, default if none pending */
+  pendingAuthority: PublicKey;
 
   constructor(args: {
     authority: PublicKey;
     id: number;
     poolId: number;
     bump: number;
+    flashloan: number;
     padding: number[];
     tokenBalances: TokenBalance[];
+    pendingAuthority: PublicKey;
   }) {
     this.authority = args.authority;
     this.id = args.id;
     this.poolId = args.poolId;
     this.bump = args.bump;
+    this.flashloan = args.flashloan;
     this.padding = args.padding;
     // Ensure exactly 16 token balances
     this.tokenBalances = Array(16)
@@ -84,6 +91,7 @@ export class UserAccount {
             padding: new Array(6).fill(0),
           }
       );
+    this.pendingAuthority = args.pendingAuthority;
   }
 
   /**
@@ -104,3 +112,35 @@ export class UserAccount {
     return await program.coder.accounts.encode("user", user);
   }
 }
+
+/**
+ * Health of a user position returned by the get_user_health instruction
+ * Values are in USD with 6 decimal places precision
+ */
+export class UserHealth {
+  /** Collateral value without weights */
+  totalCollateral: BN;
+  /** Liability value without weights */
+  totalLiability: BN;
+  /** Collateral value with initial asset weights */
+  weightedCollateral: BN;
+  /** Liability value with initial liability weights */
+  weightedLiability: BN;
+  /** Collateral value with maintenance asset weights */
+  maintenanceCollateral: BN;
+  /** Liability value with maintenance liability weights */
+  maintenanceLiability: BN;
+  /** Maintenance collateral to liability ratio in basis points, u64 max without liabilities */
+  healthRatio: BN;
+  /** Whether the position is below its maintenance requirement */
+  liquidatable: boolean;
+
+  /**
+   * Decode instruction return data into a UserHealth instance
+   * @param data Return data of a simulated get_user_health instruction
+   * @returns Decoded UserHealth instance
+   */
+  static decode(data: Buffer): UserHealth {
+    return program.coder.types.decode("UserHealth", data);
+  }
+}
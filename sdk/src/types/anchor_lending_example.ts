@@ -14,88 +14,94 @@ export type AnchorLendingExample = {
   };
   instructions: [
     {
-      name: "deposit";
+      name: "acceptAuthority";
       docs: [
-        "Deposit tokens into a bank",
-        "User must sign the transaction and provide token account with sufficient balance"
+        "Accept a pending admin authority transfer",
+        "Must be signed by the proposed authority"
       ];
-      discriminator: [242, 35, 198, 137, 82, 225, 242, 182];
+      discriminator: [107, 86, 198, 91, 33, 12, 107, 160];
       accounts: [
         {
-          name: "user";
-          docs: ["User's wallet that must sign"];
-          writable: true;
-          signer: true;
-        },
-        {
-          name: "userTokenAccount";
-          docs: ["User's token account to deposit from"];
-          writable: true;
-        },
-        {
-          name: "bankTokenAccount";
-          docs: ["Bank's token account to deposit to"];
+          name: "admin";
+          docs: ["The admin account to update"];
           writable: true;
           pda: {
             seeds: [
               {
                 kind: "const";
-                value: [
-                  116,
-                  111,
-                  107,
-                  101,
-                  110,
-                  95,
-                  97,
-                  99,
-                  99,
-                  111,
-                  117,
-                  110,
-                  116
-                ];
-              },
-              {
-                kind: "account";
-                path: "bank";
+                value: [97, 100, 109, 105, 110];
               }
             ];
           };
         },
+        {
+          name: "newAuthority";
+          docs: ["The proposed authority that must sign to accept"];
+          signer: true;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "acceptUserAuthority";
+      docs: [
+        "Accept a pending user account authority transfer",
+        "Must be signed by the proposed authority"
+      ];
+      discriminator: [250, 240, 123, 212, 197, 190, 121, 37];
+      accounts: [
         {
           name: "userAccount";
-          docs: ["User account to update balance"];
+          docs: ["The user account to transfer"];
           writable: true;
         },
         {
-          name: "bank";
-          docs: ["Bank account to validate status"];
+          name: "newAuthority";
+          docs: [
+            "The proposed authority that must sign to accept the transfer"
+          ];
+          signer: true;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "beginFlashloan";
+      docs: [
+        "Put a user account in flashloan mode, deferring health checks",
+        "An end_flashloan for the same user account must follow in the same transaction"
+      ];
+      discriminator: [222, 201, 11, 76, 109, 92, 152, 88];
+      accounts: [
+        {
+          name: "userAccount";
+          docs: ["The user account to defer health checks for"];
           writable: true;
         },
         {
-          name: "tokenProgram";
-          docs: ["Token program, either Token or Token2022"];
-        }
-      ];
-      args: [
+          name: "authority";
+          docs: ["The user authority that must sign"];
+          signer: true;
+        },
         {
-          name: "amount";
-          type: "u64";
+          name: "instructions";
+          docs: ["Instructions sysvar used to find the closing end_flashloan"];
+          address: "Sysvar1nstructions1111111111111111111111111";
         }
       ];
+      args: [];
     },
     {
-      name: "initialize";
+      name: "cancelAuthority";
       docs: [
-        "Initialize the lending program by creating an admin account",
-        "The admin account will have authority over certain program functions"
+        "Cancel a pending admin authority transfer",
+        "Can only be called by the current authority"
       ];
-      discriminator: [175, 175, 109, 31, 13, 152, 155, 237];
+      discriminator: [9, 60, 251, 100, 254, 226, 62, 110];
       accounts: [
         {
           name: "admin";
-          docs: ["The admin account to be initialized"];
+          docs: ["The admin account to update"];
           writable: true;
           pda: {
             seeds: [
@@ -108,54 +114,45 @@ export type AnchorLendingExample = {
         },
         {
           name: "authority";
-          docs: ["The authority that will have admin privileges"];
-          writable: true;
+          docs: ["The current authority that must sign"];
           signer: true;
-        },
-        {
-          name: "systemProgram";
-          address: "11111111111111111111111111111111";
+          relations: ["admin"];
         }
       ];
       args: [];
     },
     {
-      name: "initializeBank";
+      name: "closeBank";
       docs: [
-        "Initialize configuration for a new bank",
-        "Can only be called by the admin authority"
+        "Close a sunset bank and its vault once every position is settled",
+        "Permissionless, rent and remaining vault tokens go to the pool authority"
       ];
-      discriminator: [217, 55, 77, 45, 245, 197, 75, 140];
+      discriminator: [5, 98, 148, 223, 249, 112, 102, 111];
       accounts: [
         {
-          name: "admin";
-          docs: ["The admin account"];
-          pda: {
-            seeds: [
-              {
-                kind: "const";
-                value: [97, 100, 109, 105, 110];
-              }
-            ];
-          };
+          name: "caller";
+          docs: ["Anyone may close the bank once every position is settled"];
+          signer: true;
         },
         {
           name: "pool";
-          docs: ["The pool account"];
-          writable: true;
+          docs: ["The pool the bank belongs to"];
         },
         {
-          name: "bank";
-          docs: ["The bank account to initialize"];
+          name: "poolAuthority";
+          docs: [
+            "The pool authority receiving the rent of the closed accounts"
+          ];
           writable: true;
         },
         {
-          name: "mint";
-          docs: ["The token mint"];
+          name: "bank";
+          docs: ["The sunset bank to close"];
+          writable: true;
         },
         {
-          name: "tokenAccount";
-          docs: ["The token account owned by bank PDA"];
+          name: "bankTokenAccount";
+          docs: ["Bank's token account to close"];
           writable: true;
           pda: {
             seeds: [
@@ -185,119 +182,91 @@ export type AnchorLendingExample = {
           };
         },
         {
-          name: "authority";
-          docs: ["The authority that must sign to initialize bank"];
+          name: "receiverTokenAccount";
+          docs: [
+            "Pool authority token account receiving any remaining vault tokens"
+          ];
           writable: true;
-          signer: true;
-          relations: ["admin"];
-        },
-        {
-          name: "tokenProgram";
-          docs: ["Token program"];
         },
         {
-          name: "systemProgram";
-          address: "11111111111111111111111111111111";
-        }
-      ];
-      args: [
-        {
-          name: "poolId";
-          type: "u8";
+          name: "mint";
+          docs: ["Mint of the bank token, required for transfer_checked"];
         },
         {
-          name: "params";
-          type: {
-            defined: {
-              name: "bankConfigParams";
-            };
-          };
+          name: "tokenProgram";
+          docs: ["Token program, either Token or Token2022"];
         }
       ];
+      args: [];
     },
     {
-      name: "initializePool";
+      name: "deposit";
       docs: [
-        "Initialize a new pool",
-        "Can only be called by the admin authority"
+        "Deposit tokens into a bank",
+        "User must sign the transaction and provide token account with sufficient balance",
+        "With `all` set, the amount is ignored and the whole liability is repaid"
       ];
-      discriminator: [95, 180, 10, 172, 84, 174, 232, 40];
+      discriminator: [242, 35, 198, 137, 82, 225, 242, 182];
       accounts: [
         {
-          name: "admin";
-          docs: ["The admin account to be updated"];
-          writable: true;
-          pda: {
-            seeds: [
-              {
-                kind: "const";
-                value: [97, 100, 109, 105, 110];
-              }
-            ];
-          };
-        },
-        {
-          name: "pool";
-          docs: ["The pool account to initialize"];
+          name: "user";
+          docs: ["User's wallet that must sign"];
           writable: true;
+          signer: true;
         },
         {
-          name: "authority";
-          docs: ["The authority that must sign this transaction"];
+          name: "userTokenAccount";
+          docs: ["User's token account to deposit from"];
           writable: true;
-          signer: true;
-          relations: ["admin"];
         },
         {
-          name: "systemProgram";
-          docs: ["System program for CPI"];
-          address: "11111111111111111111111111111111";
-        }
-      ];
-      args: [];
-    },
-    {
-      name: "initializeUser";
-      docs: [
-        "Initialize a new user account with unique ID in a specific token group",
-        "This creates a PDA account for the user that will hold their lending protocol state"
-      ];
-      discriminator: [111, 17, 185, 250, 60, 122, 38, 254];
-      accounts: [
-        {
-          name: "user";
-          docs: ["The new user account to be created"];
+          name: "bankTokenAccount";
+          docs: ["Bank's token account to deposit to"];
           writable: true;
           pda: {
             seeds: [
               {
                 kind: "const";
-                value: [117, 115, 101, 114];
-              },
-              {
-                kind: "arg";
-                path: "poolId";
-              },
-              {
-                kind: "arg";
-                path: "userId";
+                value: [
+                  116,
+                  111,
+                  107,
+                  101,
+                  110,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ];
               },
               {
                 kind: "account";
-                path: "authority";
+                path: "bank";
               }
             ];
           };
         },
         {
-          name: "authority";
-          docs: ["The authority (owner) of the user account"];
+          name: "userAccount";
+          docs: ["User account to update balance"];
           writable: true;
-          signer: true;
+        },
+        {
+          name: "bank";
+          docs: ["Bank account to validate status"];
+          writable: true;
+        },
+        {
+          name: "pool";
+          docs: ["Pool the bank belongs to"];
         },
         {
           name: "admin";
-          docs: ["The admin account for the protocol"];
+          docs: ["Admin account holding protocol-wide pause flags"];
           pda: {
             seeds: [
               {
@@ -308,70 +277,57 @@ export type AnchorLendingExample = {
           };
         },
         {
-          name: "systemProgram";
-          docs: ["System program for CPI"];
-          address: "11111111111111111111111111111111";
+          name: "mint";
+          docs: ["Mint of the bank token, required for transfer_checked"];
+        },
+        {
+          name: "tokenProgram";
+          docs: ["Token program, either Token or Token2022"];
         }
       ];
       args: [
         {
-          name: "poolId";
-          type: "u8";
+          name: "amount";
+          type: "u64";
         },
         {
-          name: "userId";
-          type: "u16";
+          name: "all";
+          type: "bool";
         }
       ];
     },
     {
-      name: "liquidate";
+      name: "depositNative";
       docs: [
-        "Liquidate an unhealthy position",
-        "Liquidator must provide sufficient tokens to repay the liability"
+        "Deposit native SOL into a wrapped SOL bank",
+        "Lamports are wrapped through a temporary account closed back to the user"
       ];
-      discriminator: [223, 179, 226, 125, 48, 46, 39, 74];
+      discriminator: [13, 158, 13, 223, 95, 213, 28, 6];
       accounts: [
         {
-          name: "liquidator";
-          docs: ["Liquidator that must sign"];
+          name: "user";
+          docs: ["User's wallet that must sign and pays the lamports"];
           writable: true;
           signer: true;
         },
         {
-          name: "liquidatorCollateralToken";
-          docs: ["Liquidator's token account to receive collateral"];
-          writable: true;
-        },
-        {
-          name: "liquidatorLiabilityToken";
-          docs: ["Liquidator's token account to repay liability"];
-          writable: true;
-        },
-        {
-          name: "userAccount";
-          docs: ["Unhealthy user's account to liquidate"];
-          writable: true;
-        },
-        {
-          name: "collateralBank";
-          docs: ["Bank account for collateral token"];
-          writable: true;
-        },
-        {
-          name: "liabilityBank";
-          docs: ["Bank account for liability token"];
-          writable: true;
-        },
-        {
-          name: "bankCollateralToken";
-          docs: ["Bank's token account for collateral"];
+          name: "temporaryTokenAccount";
+          docs: [
+            "Temporary wrapped SOL account, closed back to the user after the deposit"
+          ];
           writable: true;
           pda: {
             seeds: [
               {
                 kind: "const";
                 value: [
+                  110,
+                  97,
+                  116,
+                  105,
+                  118,
+                  101,
+                  95,
                   116,
                   111,
                   107,
@@ -389,14 +345,14 @@ export type AnchorLendingExample = {
               },
               {
                 kind: "account";
-                path: "collateralBank";
+                path: "userAccount";
               }
             ];
           };
         },
         {
-          name: "bankLiabilityToken";
-          docs: ["Bank's token account for liability"];
+          name: "bankTokenAccount";
+          docs: ["Bank's token account to deposit to"];
           writable: true;
           pda: {
             seeds: [
@@ -420,14 +376,49 @@ export type AnchorLendingExample = {
               },
               {
                 kind: "account";
-                path: "liabilityBank";
+                path: "bank";
+              }
+            ];
+          };
+        },
+        {
+          name: "userAccount";
+          docs: ["User account to update balance"];
+          writable: true;
+        },
+        {
+          name: "bank";
+          docs: ["Bank account to validate status"];
+          writable: true;
+        },
+        {
+          name: "pool";
+          docs: ["Pool the bank belongs to"];
+        },
+        {
+          name: "admin";
+          docs: ["Admin account holding protocol-wide pause flags"];
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
               }
             ];
           };
         },
+        {
+          name: "mint";
+          docs: ["Native mint of the bank, required for transfer_checked"];
+        },
         {
           name: "tokenProgram";
-          docs: ["Token program"];
+          docs: ["Token program, either Token or Token2022"];
+        },
+        {
+          name: "systemProgram";
+          docs: ["System program to fund the temporary account"];
+          address: "11111111111111111111111111111111";
         }
       ];
       args: [
@@ -438,50 +429,87 @@ export type AnchorLendingExample = {
       ];
     },
     {
-      name: "updateAuthority";
+      name: "endFlashloan";
       docs: [
-        "Update the admin authority to a new account",
-        "Can only be called by the current authority"
+        "Leave flashloan mode once the position passes the initial health check"
       ];
-      discriminator: [32, 46, 64, 28, 149, 75, 243, 88];
+      discriminator: [146, 218, 65, 153, 210, 53, 177, 134];
       accounts: [
         {
-          name: "admin";
-          docs: ["The admin account to update"];
+          name: "userAccount";
+          docs: ["The user account in flashloan mode"];
           writable: true;
-          pda: {
-            seeds: [
-              {
-                kind: "const";
-                value: [97, 100, 109, 105, 110];
-              }
-            ];
-          };
         },
         {
           name: "authority";
-          docs: ["The current authority that must sign"];
+          docs: ["The user authority that must sign"];
           signer: true;
-          relations: ["admin"];
-        },
-        {
-          name: "newAuthority";
-          docs: ["The new authority to be set"];
         }
       ];
       args: [];
     },
     {
-      name: "updateBankStatus";
+      name: "flashBorrow";
       docs: [
-        "Update the operational status of a bank",
-        "Can only be called by the admin authority"
+        "Flash borrow tokens from a bank vault",
+        "A flash_repay of the amount plus the bank's fee must follow in the same transaction"
       ];
-      discriminator: [75, 255, 49, 191, 115, 239, 30, 148];
+      discriminator: [166, 221, 220, 25, 61, 73, 127, 240];
       accounts: [
+        {
+          name: "user";
+          docs: ["Borrower's wallet that must sign"];
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "userTokenAccount";
+          docs: ["Borrower's token account to receive the loan"];
+          writable: true;
+        },
+        {
+          name: "bankTokenAccount";
+          docs: ["Bank's token account to lend from"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  116,
+                  111,
+                  107,
+                  101,
+                  110,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ];
+              },
+              {
+                kind: "account";
+                path: "bank";
+              }
+            ];
+          };
+        },
+        {
+          name: "bank";
+          docs: ["Bank account to validate status and sign token transfer"];
+          writable: true;
+        },
+        {
+          name: "pool";
+          docs: ["Pool the bank belongs to"];
+        },
         {
           name: "admin";
-          docs: ["The admin account"];
+          docs: ["Admin account holding protocol-wide pause flags"];
           pda: {
             seeds: [
               {
@@ -492,80 +520,115 @@ export type AnchorLendingExample = {
           };
         },
         {
-          name: "authority";
-          docs: ["The authority that must sign"];
-          signer: true;
-          relations: ["admin"];
+          name: "mint";
+          docs: ["Mint of the bank token, required for transfer_checked"];
         },
         {
-          name: "bank";
-          docs: ["The bank account to update"];
-          writable: true;
+          name: "tokenProgram";
+          docs: ["Token program, either Token or Token2022"];
+        },
+        {
+          name: "instructions";
+          address: "Sysvar1nstructions1111111111111111111111111";
         }
       ];
       args: [
         {
-          name: "newStatus";
-          type: "u8";
+          name: "amount";
+          type: "u64";
         }
       ];
     },
     {
-      name: "updatePrice";
-      docs: [
-        "Update the price feed for a bank",
-        "Can only be called by the admin authority"
-      ];
-      discriminator: [61, 34, 117, 155, 75, 34, 123, 208];
+      name: "flashRepay";
+      docs: ["Repay a flash loan with the borrowed amount plus the bank's fee"];
+      discriminator: [182, 143, 19, 23, 39, 221, 184, 78];
       accounts: [
         {
-          name: "admin";
-          docs: ["The admin account containing authority info"];
+          name: "user";
+          docs: ["Wallet repaying the loan that must sign"];
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "userTokenAccount";
+          docs: ["Token account to repay from"];
+          writable: true;
+        },
+        {
+          name: "bankTokenAccount";
+          docs: ["Bank's token account receiving the repayment"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  116,
+                  111,
+                  107,
+                  101,
+                  110,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ];
+              },
+              {
+                kind: "account";
+                path: "bank";
+              }
+            ];
+          };
         },
         {
           name: "bank";
-          docs: ["The bank account to update price for"];
+          docs: ["Bank the loan was taken from"];
           writable: true;
         },
         {
-          name: "authority";
-          docs: ["The authority that can update prices"];
-          signer: true;
+          name: "mint";
+          docs: ["Mint of the bank token, required for transfer_checked"];
+        },
+        {
+          name: "tokenProgram";
+          docs: ["Token program, either Token or Token2022"];
         }
       ];
       args: [
         {
-          name: "params";
-          type: {
-            defined: {
-              name: "updatePriceParams";
-            };
-          };
+          name: "amount";
+          type: "u64";
         }
       ];
     },
     {
-      name: "withdraw";
+      name: "forceSettle";
       docs: [
-        "Withdraw tokens from a bank",
-        "User must sign the transaction and have sufficient balance in their user account"
+        "Settle a user position in a sunset bank after its deadline",
+        "Permissionless, collateral is paid out pro rata once liabilities are settled, and",
+        "liabilities are only written off once the user has no collateral left"
       ];
-      discriminator: [183, 18, 70, 156, 148, 109, 161, 34];
+      discriminator: [201, 10, 239, 245, 47, 198, 225, 204];
       accounts: [
         {
-          name: "user";
-          docs: ["User's wallet that must sign"];
-          writable: true;
+          name: "caller";
+          docs: ["Anyone may settle positions once the deadline has passed"];
           signer: true;
         },
         {
-          name: "userTokenAccount";
-          docs: ["User's token account to withdraw to"];
+          name: "bank";
+          docs: ["The sunset bank"];
           writable: true;
         },
         {
           name: "bankTokenAccount";
-          docs: ["Bank's token account to withdraw from"];
+          docs: ["Bank's token account paying out collateral"];
           writable: true;
           pda: {
             seeds: [
@@ -596,218 +659,2235 @@ export type AnchorLendingExample = {
         },
         {
           name: "userAccount";
-          docs: ["User account to update balance"];
+          docs: ["User account holding the position"];
           writable: true;
         },
         {
-          name: "bank";
-          docs: ["Bank account to validate status and sign token transfer"];
+          name: "userTokenAccount";
+          docs: ["Token account of the user authority receiving collateral"];
           writable: true;
         },
+        {
+          name: "mint";
+          docs: ["Mint of the bank token, required for transfer_checked"];
+        },
         {
           name: "tokenProgram";
           docs: ["Token program, either Token or Token2022"];
         }
       ];
-      args: [
-        {
-          name: "amount";
-          type: "u64";
-        }
-      ];
-    }
-  ];
-  accounts: [
-    {
-      name: "admin";
-      discriminator: [244, 158, 220, 65, 8, 73, 4, 65];
-    },
-    {
-      name: "bank";
-      discriminator: [142, 49, 166, 242, 50, 66, 97, 188];
-    },
-    {
-      name: "pool";
-      discriminator: [241, 154, 109, 4, 17, 177, 109, 188];
+      args: [];
     },
     {
-      name: "user";
-      discriminator: [159, 117, 95, 227, 239, 151, 58, 236];
-    }
-  ];
-  events: [
-    {
-      name: "adminAuthorityUpdated";
-      discriminator: [123, 175, 81, 239, 14, 93, 204, 151];
-    },
-    {
-      name: "adminInitialized";
-      discriminator: [237, 223, 71, 11, 140, 218, 196, 171];
-    },
-    {
-      name: "bankInitialized";
-      discriminator: [12, 70, 239, 83, 166, 159, 112, 156];
-    },
-    {
-      name: "bankStatusUpdated";
-      discriminator: [20, 241, 184, 46, 202, 162, 62, 230];
-    },
-    {
-      name: "priceUpdateEvent";
-      discriminator: [176, 152, 211, 252, 92, 105, 194, 103];
+      name: "forceSettleLiability";
+      docs: [
+        "Settle a liability in a sunset bank after its deadline against the user's collateral",
+        "Permissionless, the caller repays the liability and receives discounted collateral"
+      ];
+      discriminator: [36, 119, 96, 110, 6, 162, 67, 135];
+      accounts: [
+        {
+          name: "caller";
+          docs: ["Anyone may settle liabilities once the deadline has passed"];
+          signer: true;
+        },
+        {
+          name: "callerLiabilityToken";
+          docs: ["Caller's token account repaying the liability"];
+          writable: true;
+        },
+        {
+          name: "callerCollateralToken";
+          docs: ["Caller's token account receiving collateral"];
+          writable: true;
+        },
+        {
+          name: "userAccount";
+          docs: ["User account holding the liability"];
+          writable: true;
+        },
+        {
+          name: "bank";
+          docs: ["The sunset bank holding the liability"];
+          writable: true;
+        },
+        {
+          name: "collateralBank";
+          docs: [
+            "Bank holding the collateral the liability is settled against"
+          ];
+          writable: true;
+        },
+        {
+          name: "bankTokenAccount";
+          docs: ["Sunset bank's token account receiving the repayment"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  116,
+                  111,
+                  107,
+                  101,
+                  110,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ];
+              },
+              {
+                kind: "account";
+                path: "bank";
+              }
+            ];
+          };
+        },
+        {
+          name: "bankCollateralToken";
+          docs: ["Collateral bank's token account paying out collateral"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  116,
+                  111,
+                  107,
+                  101,
+                  110,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ];
+              },
+              {
+                kind: "account";
+                path: "collateralBank";
+              }
+            ];
+          };
+        },
+        {
+          name: "pool";
+          docs: ["Pool the banks belong to"];
+        },
+        {
+          name: "mint";
+          docs: [
+            "Mint of the sunset bank token, required for transfer_checked"
+          ];
+        },
+        {
+          name: "collateralMint";
+          docs: ["Mint of the collateral token, required for transfer_checked"];
+        },
+        {
+          name: "tokenProgram";
+          docs: ["Token program"];
+        }
+      ];
+      args: [];
     },
     {
-      name: "userBalanceUpdated";
-      discriminator: [229, 61, 41, 151, 217, 169, 131, 105];
+      name: "getLiquidationPrice";
+      docs: [
+        "Get the price of a bank's token at which a user becomes liquidatable",
+        "Read-only, the price is returned through return data for simulations"
+      ];
+      discriminator: [73, 174, 119, 65, 149, 5, 73, 239];
+      accounts: [
+        {
+          name: "userAccount";
+          docs: ["The user account to value"];
+        },
+        {
+          name: "bank";
+          docs: ["Bank whose token price is solved for"];
+        }
+      ];
+      args: [];
+      returns: {
+        option: "u64";
+      };
     },
     {
-      name: "userInitialized";
-      discriminator: [66, 195, 5, 223, 42, 84, 135, 60];
+      name: "getMaxBorrow";
+      docs: [
+        "Get the largest liability a user can open in a bank under initial weights",
+        "Read-only, the amount is returned through return data for simulations"
+      ];
+      discriminator: [223, 81, 133, 133, 138, 42, 89, 216];
+      accounts: [
+        {
+          name: "userAccount";
+          docs: ["The user account to value"];
+        },
+        {
+          name: "bank";
+          docs: ["Bank to borrow from"];
+        }
+      ];
+      args: [];
+      returns: "u64";
     },
     {
-      name: "anchor_lending_example::protocol::event::PoolInitialized";
-      discriminator: [100, 118, 173, 87, 12, 198, 254, 229];
+      name: "getMaxWithdraw";
+      docs: [
+        "Get the largest amount of collateral withdrawable from a bank without borrowing",
+        "Read-only, the amount is returned through return data for simulations"
+      ];
+      discriminator: [191, 64, 201, 192, 118, 18, 126, 162];
+      accounts: [
+        {
+          name: "userAccount";
+          docs: ["The user account to value"];
+        },
+        {
+          name: "bank";
+          docs: ["Bank to withdraw from"];
+        }
+      ];
+      args: [];
+      returns: "u64";
     },
     {
-      name: "anchor_lending_example::protocol::instructions::initialize_pool::PoolInitialized";
-      discriminator: [100, 118, 173, 87, 12, 198, 254, 229];
-    }
-  ];
-  errors: [
-    {
-      code: 6000;
-      name: "alreadyInitialized";
-      msg: "User account already initialized";
+      name: "getUserHealth";
+      docs: [
+        "Get the health of a user position",
+        "Read-only, the values are returned through return data for simulations"
+      ];
+      discriminator: [0, 152, 82, 184, 215, 162, 205, 140];
+      accounts: [
+        {
+          name: "userAccount";
+          docs: ["The user account to value"];
+        }
+      ];
+      args: [];
+      returns: {
+        defined: {
+          name: "userHealth";
+        };
+      };
     },
     {
-      code: 6001;
-      name: "invalidAuthority";
-      msg: "Invalid authority provided";
+      name: "grantRole";
+      docs: [
+        "Grant an admin role to an account",
+        "Can only be called by the admin authority"
+      ];
+      discriminator: [218, 234, 128, 15, 82, 33, 236, 253];
+      accounts: [
+        {
+          name: "admin";
+          docs: ["The admin account to update"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "authority";
+          docs: ["The super-admin authority that must sign"];
+          signer: true;
+          relations: ["admin"];
+        },
+        {
+          name: "grantee";
+          docs: ["The account receiving the role"];
+        }
+      ];
+      args: [
+        {
+          name: "role";
+          type: "u8";
+        }
+      ];
     },
     {
-      code: 6002;
-      name: "balanceUpdateOverflow";
-      msg: "Balance update overflow";
+      name: "initialize";
+      docs: [
+        "Initialize the lending program by creating an admin account",
+        "The admin account will have authority over certain program functions"
+      ];
+      discriminator: [175, 175, 109, 31, 13, 152, 155, 237];
+      accounts: [
+        {
+          name: "admin";
+          docs: ["The admin account to be initialized"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "authority";
+          docs: ["The authority that will have admin privileges"];
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        }
+      ];
+      args: [];
     },
     {
-      code: 6003;
-      name: "maxTokenTypes";
-      msg: "Reach max token types in a single account, no extra token types can be insert, consider create a new account.";
+      name: "initializeBank";
+      docs: [
+        "Initialize configuration for a new bank",
+        "Can only be called by the pool authority, listing manager or admin authority"
+      ];
+      discriminator: [217, 55, 77, 45, 245, 197, 75, 140];
+      accounts: [
+        {
+          name: "admin";
+          docs: ["The admin account"];
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "pool";
+          docs: ["The pool account"];
+          writable: true;
+        },
+        {
+          name: "bank";
+          docs: ["The bank account to initialize"];
+          writable: true;
+        },
+        {
+          name: "mint";
+          docs: ["The token mint"];
+        },
+        {
+          name: "tokenAccount";
+          docs: ["The token account owned by bank PDA"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  116,
+                  111,
+                  107,
+                  101,
+                  110,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ];
+              },
+              {
+                kind: "account";
+                path: "bank";
+              }
+            ];
+          };
+        },
+        {
+          name: "authority";
+          docs: [
+            "The pool authority or listing manager that must sign to initialize bank"
+          ];
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "tokenProgram";
+          docs: ["Token program"];
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        }
+      ];
+      args: [
+        {
+          name: "poolId";
+          type: "u8";
+        },
+        {
+          name: "params";
+          type: {
+            defined: {
+              name: "bankConfigParams";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "initializePool";
+      docs: [
+        "Initialize a new pool managed by the given pool authority",
+        "Can only be called by the admin authority"
+      ];
+      discriminator: [95, 180, 10, 172, 84, 174, 232, 40];
+      accounts: [
+        {
+          name: "admin";
+          docs: ["The admin account to be updated"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "pool";
+          docs: ["The pool account to initialize"];
+          writable: true;
+        },
+        {
+          name: "authority";
+          docs: ["The authority that must sign this transaction"];
+          writable: true;
+          signer: true;
+          relations: ["admin"];
+        },
+        {
+          name: "poolAuthority";
+          docs: ["The authority that will manage the new pool"];
+        },
+        {
+          name: "systemProgram";
+          docs: ["System program for CPI"];
+          address: "11111111111111111111111111111111";
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: {
+              name: "poolConfigParams";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "initializeUser";
+      docs: [
+        "Initialize a new user account with unique ID in a specific token group",
+        "This creates a PDA account for the user that will hold their lending protocol state"
+      ];
+      discriminator: [111, 17, 185, 250, 60, 122, 38, 254];
+      accounts: [
+        {
+          name: "user";
+          docs: ["The new user account to be created"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [117, 115, 101, 114];
+              },
+              {
+                kind: "arg";
+                path: "poolId";
+              },
+              {
+                kind: "arg";
+                path: "userId";
+              },
+              {
+                kind: "account";
+                path: "authority";
+              }
+            ];
+          };
+        },
+        {
+          name: "authority";
+          docs: ["The authority (owner) of the user account"];
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "pool";
+          docs: ["The pool the user joins"];
+        },
+        {
+          name: "systemProgram";
+          docs: ["System program for CPI"];
+          address: "11111111111111111111111111111111";
+        }
+      ];
+      args: [
+        {
+          name: "poolId";
+          type: "u8";
+        },
+        {
+          name: "userId";
+          type: "u16";
+        }
+      ];
+    },
+    {
+      name: "liquidate";
+      docs: [
+        "Liquidate an unhealthy position",
+        "Liquidator must provide sufficient tokens to repay the liability"
+      ];
+      discriminator: [223, 179, 226, 125, 48, 46, 39, 74];
+      accounts: [
+        {
+          name: "liquidator";
+          docs: ["Liquidator that must sign"];
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "liquidatorCollateralToken";
+          docs: ["Liquidator's token account to receive collateral"];
+          writable: true;
+        },
+        {
+          name: "liquidatorLiabilityToken";
+          docs: ["Liquidator's token account to repay liability"];
+          writable: true;
+        },
+        {
+          name: "userAccount";
+          docs: ["Unhealthy user's account to liquidate"];
+          writable: true;
+        },
+        {
+          name: "collateralBank";
+          docs: ["Bank account for collateral token"];
+          writable: true;
+        },
+        {
+          name: "liabilityBank";
+          docs: ["Bank account for liability token"];
+          writable: true;
+        },
+        {
+          name: "bankCollateralToken";
+          docs: ["Bank's token account for collateral"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  116,
+                  111,
+                  107,
+                  101,
+                  110,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ];
+              },
+              {
+                kind: "account";
+                path: "collateralBank";
+              }
+            ];
+          };
+        },
+        {
+          name: "bankLiabilityToken";
+          docs: ["Bank's token account for liability"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  116,
+                  111,
+                  107,
+                  101,
+                  110,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ];
+              },
+              {
+                kind: "account";
+                path: "liabilityBank";
+              }
+            ];
+          };
+        },
+        {
+          name: "pool";
+          docs: ["Pool the banks belong to"];
+        },
+        {
+          name: "collateralMint";
+          docs: ["Mint of the collateral token, required for transfer_checked"];
+        },
+        {
+          name: "liabilityMint";
+          docs: ["Mint of the liability token, required for transfer_checked"];
+        },
+        {
+          name: "admin";
+          docs: ["Admin account holding protocol-wide pause flags"];
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "tokenProgram";
+          docs: ["Token program"];
+        }
+      ];
+      args: [
+        {
+          name: "amount";
+          type: "u64";
+        }
+      ];
+    },
+    {
+      name: "migrateUser";
+      docs: [
+        "Grow a user account created before the pending authority was added to the layout",
+        "Permissionless, the payer funds the extra rent"
+      ];
+      discriminator: [159, 64, 64, 6, 80, 228, 54, 66];
+      accounts: [
+        {
+          name: "userAccount";
+          docs: [
+            "The user account to grow, checked by hand since the legacy size does not load"
+          ];
+          writable: true;
+        },
+        {
+          name: "payer";
+          docs: ["Pays the rent for the appended fields"];
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "systemProgram";
+          docs: ["System program for the rent transfer"];
+          address: "11111111111111111111111111111111";
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "proposeAuthority";
+      docs: [
+        "Propose a new admin authority",
+        "Can only be called by the current authority"
+      ];
+      discriminator: [20, 148, 236, 198, 76, 119, 99, 142];
+      accounts: [
+        {
+          name: "admin";
+          docs: ["The admin account to update"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "authority";
+          docs: ["The current authority that must sign"];
+          signer: true;
+          relations: ["admin"];
+        },
+        {
+          name: "newAuthority";
+          docs: ["The authority proposed to take over admin privileges"];
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "proposeUserAuthority";
+      docs: [
+        "Propose a new authority for a user account",
+        "Can only be called by the current user authority"
+      ];
+      discriminator: [67, 248, 188, 106, 138, 52, 255, 242];
+      accounts: [
+        {
+          name: "userAccount";
+          docs: ["The user account to transfer"];
+          writable: true;
+        },
+        {
+          name: "authority";
+          docs: ["The current authority that must sign"];
+          signer: true;
+        },
+        {
+          name: "newAuthority";
+          docs: ["The authority proposed to take over the user account"];
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "revokeRole";
+      docs: [
+        "Revoke an admin role from its holder",
+        "Can only be called by the admin authority"
+      ];
+      discriminator: [179, 232, 2, 180, 48, 227, 82, 7];
+      accounts: [
+        {
+          name: "admin";
+          docs: ["The admin account to update"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "authority";
+          docs: ["The super-admin authority that must sign"];
+          signer: true;
+          relations: ["admin"];
+        }
+      ];
+      args: [
+        {
+          name: "role";
+          type: "u8";
+        }
+      ];
+    },
+    {
+      name: "setPoolPause";
+      docs: [
+        "Replace the pause flags of a pool for deposits, withdrawals and liquidations",
+        "Can be called by the pool authority, emergency guardian or admin authority"
+      ];
+      discriminator: [52, 171, 212, 208, 28, 209, 90, 15];
+      accounts: [
+        {
+          name: "admin";
+          docs: ["The admin account"];
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "pool";
+          docs: ["The pool account holding the pause flags"];
+          writable: true;
+        },
+        {
+          name: "authority";
+          docs: [
+            "The pool authority, emergency guardian or admin authority that must sign"
+          ];
+          signer: true;
+        }
+      ];
+      args: [
+        {
+          name: "poolId";
+          type: "u8";
+        },
+        {
+          name: "flags";
+          type: "u8";
+        }
+      ];
+    },
+    {
+      name: "setProtocolPause";
+      docs: [
+        "Replace the protocol-wide pause flags for deposits, withdrawals and liquidations",
+        "Can only be called by the emergency guardian or admin authority"
+      ];
+      discriminator: [19, 235, 135, 250, 184, 114, 209, 89];
+      accounts: [
+        {
+          name: "admin";
+          docs: ["The admin account holding the protocol pause flags"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "authority";
+          docs: ["The emergency guardian or admin authority that must sign"];
+          signer: true;
+        }
+      ];
+      args: [
+        {
+          name: "flags";
+          type: "u8";
+        }
+      ];
+    },
+    {
+      name: "sunsetBank";
+      docs: [
+        "Start delisting a bank, its asset weights decay to zero by the deadline",
+        "Can only be called by the pool authority or risk manager"
+      ];
+      discriminator: [166, 46, 101, 55, 195, 101, 38, 45];
+      accounts: [
+        {
+          name: "admin";
+          docs: ["The admin account"];
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "pool";
+          docs: ["The pool the bank belongs to"];
+        },
+        {
+          name: "authority";
+          docs: ["The pool authority or risk manager that must sign"];
+          signer: true;
+        },
+        {
+          name: "bank";
+          docs: ["The bank account to sunset"];
+          writable: true;
+        }
+      ];
+      args: [
+        {
+          name: "deadline";
+          type: "i64";
+        }
+      ];
+    },
+    {
+      name: "updateBankConfig";
+      docs: [
+        "Update the risk weights of a bank",
+        "Can be called by the pool authority or risk manager"
+      ];
+      discriminator: [98, 195, 67, 76, 187, 143, 229, 92];
+      accounts: [
+        {
+          name: "admin";
+          docs: ["The admin account"];
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "pool";
+          docs: ["The pool the bank belongs to"];
+        },
+        {
+          name: "authority";
+          docs: ["The pool authority or risk manager that must sign"];
+          signer: true;
+        },
+        {
+          name: "bank";
+          docs: ["The bank account to update"];
+          writable: true;
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: {
+              name: "updateBankConfigParams";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "updateBankStatus";
+      docs: [
+        "Update the operational status of a bank",
+        "Can be called by the pool authority or risk manager,",
+        "or by the emergency guardian to pause, reduce or freeze"
+      ];
+      discriminator: [75, 255, 49, 191, 115, 239, 30, 148];
+      accounts: [
+        {
+          name: "admin";
+          docs: ["The admin account"];
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "pool";
+          docs: ["The pool the bank belongs to"];
+        },
+        {
+          name: "authority";
+          docs: [
+            "The pool authority, risk manager or emergency guardian that must sign"
+          ];
+          signer: true;
+        },
+        {
+          name: "bank";
+          docs: ["The bank account to update"];
+          writable: true;
+        }
+      ];
+      args: [
+        {
+          name: "newStatus";
+          type: "u8";
+        }
+      ];
+    },
+    {
+      name: "updateFlashLoanFee";
+      docs: [
+        "Update the flash loan fee of a bank in basis points",
+        "Can be called by the pool authority or risk manager"
+      ];
+      discriminator: [244, 251, 61, 221, 246, 231, 107, 181];
+      accounts: [
+        {
+          name: "admin";
+          docs: ["The admin account"];
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "pool";
+          docs: ["The pool the bank belongs to"];
+        },
+        {
+          name: "authority";
+          docs: ["The pool authority or risk manager that must sign"];
+          signer: true;
+        },
+        {
+          name: "bank";
+          docs: ["The bank account to update"];
+          writable: true;
+        }
+      ];
+      args: [
+        {
+          name: "fee";
+          type: "u16";
+        }
+      ];
+    },
+    {
+      name: "updatePool";
+      docs: [
+        "Update the configuration of a pool",
+        "Can only be called by the pool authority"
+      ];
+      discriminator: [239, 214, 170, 78, 36, 35, 30, 34];
+      accounts: [
+        {
+          name: "pool";
+          docs: ["The pool account to update"];
+          writable: true;
+        },
+        {
+          name: "authority";
+          docs: ["The pool authority that must sign"];
+          signer: true;
+          relations: ["pool"];
+        }
+      ];
+      args: [
+        {
+          name: "poolId";
+          type: "u8";
+        },
+        {
+          name: "params";
+          type: {
+            defined: {
+              name: "updatePoolParams";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "updatePrice";
+      docs: [
+        "Update the price feed for a bank",
+        "Can only be called by the oracle keeper or admin authority"
+      ];
+      discriminator: [61, 34, 117, 155, 75, 34, 123, 208];
+      accounts: [
+        {
+          name: "admin";
+          docs: ["The admin account containing authority info"];
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "bank";
+          docs: ["The bank account to update price for"];
+          writable: true;
+        },
+        {
+          name: "authority";
+          docs: ["The oracle keeper that can update prices"];
+          signer: true;
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: {
+              name: "updatePriceParams";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "withdraw";
+      docs: [
+        "Withdraw tokens from a bank",
+        "User must sign the transaction and have sufficient balance in their user account",
+        "With `all` set, the amount is ignored and the whole collateral is withdrawn"
+      ];
+      discriminator: [183, 18, 70, 156, 148, 109, 161, 34];
+      accounts: [
+        {
+          name: "user";
+          docs: ["User's wallet that must sign"];
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "userTokenAccount";
+          docs: ["User's token account to withdraw to"];
+          writable: true;
+        },
+        {
+          name: "bankTokenAccount";
+          docs: ["Bank's token account to withdraw from"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  116,
+                  111,
+                  107,
+                  101,
+                  110,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ];
+              },
+              {
+                kind: "account";
+                path: "bank";
+              }
+            ];
+          };
+        },
+        {
+          name: "userAccount";
+          docs: ["User account to update balance"];
+          writable: true;
+        },
+        {
+          name: "bank";
+          docs: ["Bank account to validate status and sign token transfer"];
+          writable: true;
+        },
+        {
+          name: "pool";
+          docs: ["Pool the bank belongs to"];
+        },
+        {
+          name: "admin";
+          docs: ["Admin account holding protocol-wide pause flags"];
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "mint";
+          docs: ["Mint of the bank token, required for transfer_checked"];
+        },
+        {
+          name: "tokenProgram";
+          docs: ["Token program, either Token or Token2022"];
+        }
+      ];
+      args: [
+        {
+          name: "amount";
+          type: "u64";
+        },
+        {
+          name: "all";
+          type: "bool";
+        }
+      ];
+    },
+    {
+      name: "withdrawNative";
+      docs: [
+        "Withdraw native SOL from a wrapped SOL bank",
+        "Tokens are unwrapped by closing a temporary account to the user"
+      ];
+      discriminator: [113, 227, 26, 32, 53, 66, 90, 250];
+      accounts: [
+        {
+          name: "user";
+          docs: ["User's wallet that must sign and receives the lamports"];
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "temporaryTokenAccount";
+          docs: [
+            "Temporary wrapped SOL account, closed to the user to unwrap the withdrawal"
+          ];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  110,
+                  97,
+                  116,
+                  105,
+                  118,
+                  101,
+                  95,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ];
+              },
+              {
+                kind: "account";
+                path: "userAccount";
+              }
+            ];
+          };
+        },
+        {
+          name: "bankTokenAccount";
+          docs: ["Bank's token account to withdraw from"];
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  116,
+                  111,
+                  107,
+                  101,
+                  110,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ];
+              },
+              {
+                kind: "account";
+                path: "bank";
+              }
+            ];
+          };
+        },
+        {
+          name: "userAccount";
+          docs: ["User account to update balance"];
+          writable: true;
+        },
+        {
+          name: "bank";
+          docs: ["Bank account to validate status and sign token transfer"];
+          writable: true;
+        },
+        {
+          name: "pool";
+          docs: ["Pool the bank belongs to"];
+        },
+        {
+          name: "admin";
+          docs: ["Admin account holding protocol-wide pause flags"];
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 100, 109, 105, 110];
+              }
+            ];
+          };
+        },
+        {
+          name: "mint";
+          docs: ["Native mint of the bank, required for transfer_checked"];
+        },
+        {
+          name: "tokenProgram";
+          docs: ["Token program, either Token or Token2022"];
+        },
+        {
+          name: "systemProgram";
+          docs: ["System program to create the temporary account"];
+          address: "11111111111111111111111111111111";
+        }
+      ];
+      args: [
+        {
+          name: "amount";
+          type: "u64";
+        }
+      ];
+    }
+  ];
+  accounts: [
+    {
+      name: "admin";
+      discriminator: [244, 158, 220, 65, 8, 73, 4, 65];
+    },
+    {
+      name: "bank";
+      discriminator: [142, 49, 166, 242, 50, 66, 97, 188];
+    },
+    {
+      name: "pool";
+      discriminator: [241, 154, 109, 4, 17, 177, 109, 188];
+    },
+    {
+      name: "user";
+      discriminator: [159, 117, 95, 227, 239, 151, 58, 236];
+    }
+  ];
+  events: [
+    {
+      name: "adminAuthorityProposalCancelled";
+      discriminator: [165, 173, 174, 112, 250, 132, 189, 236];
+    },
+    {
+      name: "adminAuthorityProposed";
+      discriminator: [92, 15, 139, 115, 66, 104, 85, 95];
+    },
+    {
+      name: "adminAuthorityUpdated";
+      discriminator: [123, 175, 81, 239, 14, 93, 204, 151];
+    },
+    {
+      name: "adminInitialized";
+      discriminator: [237, 223, 71, 11, 140, 218, 196, 171];
+    },
+    {
+      name: "adminRoleGranted";
+      discriminator: [100, 97, 162, 197, 214, 206, 220, 130];
+    },
+    {
+      name: "adminRoleRevoked";
+      discriminator: [47, 127, 87, 27, 141, 160, 79, 249];
+    },
+    {
+      name: "bankClosed";
+      discriminator: [31, 216, 207, 32, 4, 152, 203, 99];
+    },
+    {
+      name: "bankConfigUpdated";
+      discriminator: [129, 190, 223, 48, 70, 128, 22, 203];
+    },
+    {
+      name: "bankInitialized";
+      discriminator: [12, 70, 239, 83, 166, 159, 112, 156];
+    },
+    {
+      name: "bankStatusUpdated";
+      discriminator: [20, 241, 184, 46, 202, 162, 62, 230];
+    },
+    {
+      name: "bankSunsetStarted";
+      discriminator: [61, 74, 51, 45, 1, 248, 214, 32];
+    },
+    {
+      name: "flashLoanBorrowed";
+      discriminator: [37, 86, 25, 222, 83, 163, 251, 242];
+    },
+    {
+      name: "flashLoanFeeUpdated";
+      discriminator: [199, 190, 187, 217, 99, 111, 202, 186];
+    },
+    {
+      name: "flashLoanRepaid";
+      discriminator: [9, 204, 105, 115, 70, 7, 25, 198];
+    },
+    {
+      name: "flashloanEnded";
+      discriminator: [183, 155, 36, 232, 208, 58, 98, 81];
+    },
+    {
+      name: "flashloanStarted";
+      discriminator: [186, 91, 237, 160, 4, 190, 169, 247];
+    },
+    {
+      name: "poolPauseUpdated";
+      discriminator: [162, 210, 176, 177, 206, 48, 117, 184];
+    },
+    {
+      name: "poolUpdated";
+      discriminator: [218, 43, 210, 231, 127, 214, 72, 245];
+    },
+    {
+      name: "positionForceSettled";
+      discriminator: [158, 105, 150, 181, 34, 132, 151, 13];
+    },
+    {
+      name: "priceUpdateEvent";
+      discriminator: [176, 152, 211, 252, 92, 105, 194, 103];
+    },
+    {
+      name: "protocolPauseUpdated";
+      discriminator: [18, 112, 97, 19, 182, 70, 162, 226];
+    },
+    {
+      name: "sunsetLiabilitySettled";
+      discriminator: [115, 185, 225, 153, 225, 74, 21, 26];
+    },
+    {
+      name: "userAuthorityProposed";
+      discriminator: [252, 246, 186, 231, 140, 108, 72, 249];
+    },
+    {
+      name: "userAuthorityTransferred";
+      discriminator: [98, 2, 164, 205, 172, 99, 216, 195];
+    },
+    {
+      name: "userBalanceUpdated";
+      discriminator: [229, 61, 41, 151, 217, 169, 131, 105];
+    },
+    {
+      name: "userInitialized";
+      discriminator: [66, 195, 5, 223, 42, 84, 135, 60];
+    },
+    {
+      name: "anchor_lending_example::protocol::event::PoolInitialized";
+      discriminator: [100, 118, 173, 87, 12, 198, 254, 229];
+    },
+    {
+      name: "anchor_lending_example::protocol::instructions::initialize_pool::PoolInitialized";
+      discriminator: [100, 118, 173, 87, 12, 198, 254, 229];
+    }
+  ];
+  errors: [
+    {
+      code: 6000;
+      name: "alreadyInitialized";
+      msg: "User account already initialized";
+    },
+    {
+      code: 6001;
+      name: "invalidAuthority";
+      msg: "Invalid authority provided";
+    },
+    {
+      code: 6002;
+      name: "balanceUpdateOverflow";
+      msg: "Balance update overflow";
+    },
+    {
+      code: 6003;
+      name: "maxTokenTypes";
+      msg: "Reach max token types in a single account, no extra token types can be insert, consider create a new account.";
+    },
+    {
+      code: 6004;
+      name: "poolNotFound";
+      msg: "Pool not found";
+    },
+    {
+      code: 6005;
+      name: "invalidCollateralBalance";
+      msg: "Invalid collateral balance";
+    },
+    {
+      code: 6006;
+      name: "insufficientCollateral";
+      msg: "Insufficient collateral";
+    },
+    {
+      code: 6007;
+      name: "positionHealthy";
+      msg: "User account is healthy, no need to liquidate";
+    },
+    {
+      code: 6008;
+      name: "mathOverflow";
+      msg: "Math operation overflow";
+    },
+    {
+      code: 6009;
+      name: "poolInactive";
+      msg: "Pool is inactive";
+    },
+    {
+      code: 6010;
+      name: "maxPositions";
+      msg: "Reach max positions allowed by the pool";
+    },
+    {
+      code: 6011;
+      name: "closeFactorExceeded";
+      msg: "Liquidation amount exceeds pool close factor";
+    },
+    {
+      code: 6012;
+      name: "protocolPaused";
+      msg: "Operation is paused protocol-wide";
+    },
+    {
+      code: 6013;
+      name: "poolPaused";
+      msg: "Operation is paused for this pool";
+    },
+    {
+      code: 6014;
+      name: "notNativeMint";
+      msg: "Bank mint is not the native SOL mint";
+    },
+    {
+      code: 6015;
+      name: "liquidationHealthNotImproved";
+      msg: "Liquidation must raise the position health ratio";
+    },
+    {
+      code: 6016;
+      name: "liquidationOvershoot";
+      msg: "Liquidation leaves the position healthier than the liquidation target";
+    },
+    {
+      code: 6017;
+      name: "noPositionToClear";
+      msg: "No position in this bank to clear";
+    },
+    {
+      code: 6018;
+      name: "inFlashloan";
+      msg: "User account is in flashloan mode";
+    },
+    {
+      code: 6019;
+      name: "notInFlashloan";
+      msg: "User account is not in flashloan mode";
+    },
+    {
+      code: 6020;
+      name: "endFlashloanMissing";
+      msg: "Transaction must call end_flashloan for this user account";
+    },
+    {
+      code: 6021;
+      name: "noLiabilityToRepay";
+      msg: "User has no liability in the liability bank";
+    },
+    {
+      code: 6022;
+      name: "alreadyMigrated";
+      msg: "User account already has the current layout";
+    }
+  ];
+  types: [
+    {
+      name: "admin";
+      docs: ["Admin account data"];
+      serialization: "bytemuck";
+      repr: {
+        kind: "c";
+      };
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "authority";
+            docs: ["The authority pubkey that has admin privileges"];
+            type: "pubkey";
+          },
+          {
+            name: "pendingAuthority";
+            docs: [
+              "The authority proposed to take over admin privileges, default if none pending"
+            ];
+            type: "pubkey";
+          },
+          {
+            name: "riskManager";
+            docs: ["Account allowed to manage bank risk settings"];
+            type: "pubkey";
+          },
+          {
+            name: "listingManager";
+            docs: ["Account allowed to list new banks"];
+            type: "pubkey";
+          },
+          {
+            name: "emergencyGuardian";
+            docs: ["Account allowed to pause banks or set them to reduce-only"];
+            type: "pubkey";
+          },
+          {
+            name: "oracleKeeper";
+            docs: ["Account allowed to update bank prices"];
+            type: "pubkey";
+          },
+          {
+            name: "poolCount";
+            docs: ["Number of pools that have been initialized"];
+            type: "u8";
+          },
+          {
+            name: "pauseFlags";
+            docs: ["Protocol-wide paused operations, see `PauseFlag`"];
+            type: "u8";
+          }
+        ];
+      };
+    },
+    {
+      name: "adminAuthorityProposalCancelled";
+      docs: [
+        "Event emitted when a pending admin authority proposal is cancelled"
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "admin";
+            docs: ["The admin account address"];
+            type: "pubkey";
+          },
+          {
+            name: "authority";
+            docs: ["The current authority"];
+            type: "pubkey";
+          },
+          {
+            name: "cancelledAuthority";
+            docs: ["The proposed authority that was cancelled"];
+            type: "pubkey";
+          }
+        ];
+      };
+    },
+    {
+      name: "adminAuthorityProposed";
+      docs: ["Event emitted when a new admin authority is proposed"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "admin";
+            docs: ["The admin account address"];
+            type: "pubkey";
+          },
+          {
+            name: "authority";
+            docs: ["The current authority"];
+            type: "pubkey";
+          },
+          {
+            name: "pendingAuthority";
+            docs: ["The proposed authority"];
+            type: "pubkey";
+          }
+        ];
+      };
+    },
+    {
+      name: "adminAuthorityUpdated";
+      docs: ["Event emitted when admin authority is updated"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "admin";
+            docs: ["The admin account address"];
+            type: "pubkey";
+          },
+          {
+            name: "oldAuthority";
+            docs: ["The old authority"];
+            type: "pubkey";
+          },
+          {
+            name: "newAuthority";
+            docs: ["The new authority"];
+            type: "pubkey";
+          }
+        ];
+      };
+    },
+    {
+      name: "adminInitialized";
+      docs: ["Event emitted when admin is initialized"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "admin";
+            docs: ["The admin account address"];
+            type: "pubkey";
+          },
+          {
+            name: "authority";
+            docs: ["The authority set for this admin"];
+            type: "pubkey";
+          }
+        ];
+      };
+    },
+    {
+      name: "adminRoleGranted";
+      docs: ["Event emitted when an admin role is granted"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "admin";
+            docs: ["The admin account address"];
+            type: "pubkey";
+          },
+          {
+            name: "role";
+            docs: ["The role granted"];
+            type: "u8";
+          },
+          {
+            name: "oldHolder";
+            docs: ["The previous role holder"];
+            type: "pubkey";
+          },
+          {
+            name: "newHolder";
+            docs: ["The new role holder"];
+            type: "pubkey";
+          }
+        ];
+      };
+    },
+    {
+      name: "adminRoleRevoked";
+      docs: ["Event emitted when an admin role is revoked"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "admin";
+            docs: ["The admin account address"];
+            type: "pubkey";
+          },
+          {
+            name: "role";
+            docs: ["The role revoked"];
+            type: "u8";
+          },
+          {
+            name: "oldHolder";
+            docs: ["The role holder that was removed"];
+            type: "pubkey";
+          }
+        ];
+      };
+    },
+    {
+      name: "balanceType";
+      docs: ["Type of balance for a token position"];
+      repr: {
+        kind: "rust";
+      };
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "collateral";
+          },
+          {
+            name: "liability";
+          }
+        ];
+      };
+    },
+    {
+      name: "bank";
+      docs: ["Bank account data"];
+      serialization: "bytemuck";
+      repr: {
+        kind: "c";
+      };
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "bankId";
+            docs: ["The bank ID within the pool"];
+            type: "u8";
+          },
+          {
+            name: "poolId";
+            docs: ["The pool ID"];
+            type: "u8";
+          },
+          {
+            name: "status";
+            docs: ["Current operational status"];
+            type: "u8";
+          },
+          {
+            name: "decimals";
+            docs: ["The decimal places of the token mint"];
+            type: "u8";
+          },
+          {
+            name: "initialAssetWeight";
+            docs: [
+              "Weight applied to assets for initial collateral ratio calculations"
+            ];
+            type: "u8";
+          },
+          {
+            name: "maintenanceAssetWeight";
+            docs: [
+              "Weight applied to assets for maintenance collateral ratio calculations"
+            ];
+            type: "u8";
+          },
+          {
+            name: "initialLiabilityWeight";
+            docs: [
+              "Weight applied to liabilities for initial borrowing limits"
+            ];
+            type: "u8";
+          },
+          {
+            name: "maintenanceLiabilityWeight";
+            docs: [
+              "Weight applied to liabilities for maintenance requirements"
+            ];
+            type: "u8";
+          },
+          {
+            name: "allowedExtensions";
+            docs: [
+              "Restricted mint extensions allowlisted by the admin authority, see `MintExtension`"
+            ];
+            type: "u16";
+          },
+          {
+            name: "flashLoanFee";
+            docs: ["Flash loan fee in basis points of the borrowed amount"];
+            type: "u16";
+          },
+          {
+            name: "padding";
+            type: {
+              array: ["u8", 4];
+            };
+          },
+          {
+            name: "statusUpdatedAt";
+            docs: ["Unix timestamp of the last status change"];
+            type: "i64";
+          },
+          {
+            name: "sunsetDeadline";
+            docs: [
+              "Unix timestamp after which a sunset bank can be force settled"
+            ];
+            type: "i64";
+          },
+          {
+            name: "totalDeposits";
+            docs: ["Total collateral deposited by users"];
+            type: "u64";
+          },
+          {
+            name: "totalLiabilities";
+            docs: ["Total liabilities owed by users"];
+            type: "u64";
+          },
+          {
+            name: "flashLoanOutstanding";
+            docs: [
+              "Flash loan repayment owed to the vault including the fee, only non-zero within a transaction"
+            ];
+            type: "u64";
+          },
+          {
+            name: "flashLoanFees";
+            docs: ["Flash loan fees accrued to the vault"];
+            type: "u64";
+          },
+          {
+            name: "mint";
+            docs: ["The token mint address"];
+            type: "pubkey";
+          },
+          {
+            name: "priceMessage";
+            docs: ["The price message"];
+            type: {
+              defined: {
+                name: "priceFeedMessage";
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "bankClosed";
+      docs: ["Event emitted when a sunset bank and its vault are closed"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "mint";
+            docs: ["The token mint address"];
+            type: "pubkey";
+          },
+          {
+            name: "poolId";
+            docs: ["The pool ID"];
+            type: "u8";
+          },
+          {
+            name: "bankId";
+            docs: ["The bank ID within the pool"];
+            type: "u8";
+          },
+          {
+            name: "sweptAmount";
+            docs: ["Remaining vault tokens swept to the pool authority"];
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "bankConfigParams";
+      docs: ["Parameters for initializing a new bank"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "initialAssetWeight";
+            docs: [
+              "Weight applied to assets for initial collateral ratio calculations"
+            ];
+            type: "u8";
+          },
+          {
+            name: "maintenanceAssetWeight";
+            docs: [
+              "Weight applied to assets for maintenance collateral ratio calculations"
+            ];
+            type: "u8";
+          },
+          {
+            name: "initialLiabilityWeight";
+            docs: [
+              "Weight applied to liabilities for initial borrowing limits"
+            ];
+            type: "u8";
+          },
+          {
+            name: "maintenanceLiabilityWeight";
+            docs: [
+              "Weight applied to liabilities for maintenance requirements"
+            ];
+            type: "u8";
+          },
+          {
+            name: "allowedExtensions";
+            docs: [
+              "Restricted mint extensions to accept, see `MintExtension`",
+              "Only the admin authority may allowlist extensions"
+            ];
+            type: "u16";
+          },
+          {
+            name: "flashLoanFee";
+            docs: ["Flash loan fee in basis points of the borrowed amount"];
+            type: "u16";
+          }
+        ];
+      };
     },
     {
-      code: 6004;
-      name: "poolNotFound";
-      msg: "Pool not found";
+      name: "bankConfigUpdated";
+      docs: ["Event emitted when a bank's risk weights are updated"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "mint";
+            docs: ["The token mint address"];
+            type: "pubkey";
+          },
+          {
+            name: "initialAssetWeight";
+            docs: ["New initial asset weight"];
+            type: "u8";
+          },
+          {
+            name: "maintenanceAssetWeight";
+            docs: ["New maintenance asset weight"];
+            type: "u8";
+          },
+          {
+            name: "initialLiabilityWeight";
+            docs: ["New initial liability weight"];
+            type: "u8";
+          },
+          {
+            name: "maintenanceLiabilityWeight";
+            docs: ["New maintenance liability weight"];
+            type: "u8";
+          }
+        ];
+      };
     },
     {
-      code: 6005;
-      name: "invalidCollateralBalance";
-      msg: "Invalid collateral balance";
+      name: "bankInitialized";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "mint";
+            type: "pubkey";
+          },
+          {
+            name: "groupId";
+            type: "u8";
+          },
+          {
+            name: "status";
+            type: "u8";
+          },
+          {
+            name: "tokenAccount";
+            type: "pubkey";
+          }
+        ];
+      };
     },
     {
-      code: 6006;
-      name: "insufficientCollateral";
-      msg: "Insufficient collateral";
+      name: "bankStatusUpdated";
+      docs: ["Event emitted when bank status is updated"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "mint";
+            docs: ["The token mint address"];
+            type: "pubkey";
+          },
+          {
+            name: "oldStatus";
+            docs: ["Previous bank status"];
+            type: "u8";
+          },
+          {
+            name: "newStatus";
+            docs: ["New bank status"];
+            type: "u8";
+          }
+        ];
+      };
     },
     {
-      code: 6007;
-      name: "positionHealthy";
-      msg: "User account is healthy, no need to liquidate";
+      name: "bankSunsetStarted";
+      docs: ["Event emitted when a bank enters sunset"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "mint";
+            docs: ["The token mint address"];
+            type: "pubkey";
+          },
+          {
+            name: "oldStatus";
+            docs: ["Previous bank status"];
+            type: "u8";
+          },
+          {
+            name: "deadline";
+            docs: ["Unix timestamp after which positions can be force settled"];
+            type: "i64";
+          }
+        ];
+      };
     },
     {
-      code: 6008;
-      name: "mathOverflow";
-      msg: "Math operation overflow";
-    }
-  ];
-  types: [
-    {
-      name: "admin";
-      docs: ["Admin account data"];
-      serialization: "bytemuck";
-      repr: {
-        kind: "c";
-      };
+      name: "flashLoanBorrowed";
+      docs: ["Event emitted when tokens are flash borrowed from a bank vault"];
       type: {
         kind: "struct";
         fields: [
           {
-            name: "authority";
-            docs: ["The authority pubkey that has admin privileges"];
+            name: "bank";
+            docs: ["The bank account address"];
             type: "pubkey";
           },
           {
-            name: "poolCount";
-            docs: ["Number of pools that have been initialized"];
-            type: "u8";
+            name: "borrower";
+            docs: ["The borrower's wallet"];
+            type: "pubkey";
+          },
+          {
+            name: "amount";
+            docs: ["Amount borrowed"];
+            type: "u64";
+          },
+          {
+            name: "fee";
+            docs: ["Fee owed on top of the amount"];
+            type: "u64";
           }
         ];
       };
     },
     {
-      name: "adminAuthorityUpdated";
-      docs: ["Event emitted when admin authority is updated"];
+      name: "flashLoanFeeUpdated";
+      docs: ["Event emitted when a bank's flash loan fee is updated"];
       type: {
         kind: "struct";
         fields: [
           {
-            name: "admin";
-            docs: ["The admin account address"];
+            name: "mint";
+            docs: ["The token mint address"];
             type: "pubkey";
           },
           {
-            name: "oldAuthority";
-            docs: ["The old authority"];
+            name: "oldFee";
+            docs: ["Previous fee in basis points"];
+            type: "u16";
+          },
+          {
+            name: "newFee";
+            docs: ["New fee in basis points"];
+            type: "u16";
+          }
+        ];
+      };
+    },
+    {
+      name: "flashLoanRepaid";
+      docs: ["Event emitted when a flash loan is repaid to a bank vault"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "bank";
+            docs: ["The bank account address"];
             type: "pubkey";
           },
           {
-            name: "newAuthority";
-            docs: ["The new authority"];
+            name: "borrower";
+            docs: ["The repaying wallet"];
             type: "pubkey";
+          },
+          {
+            name: "amount";
+            docs: ["Amount repaid including the fee"];
+            type: "u64";
           }
         ];
       };
     },
     {
-      name: "adminInitialized";
-      docs: ["Event emitted when admin is initialized"];
+      name: "flashloanEnded";
+      docs: [
+        "Event emitted when a user account leaves flashloan mode with a healthy position"
+      ];
       type: {
         kind: "struct";
         fields: [
           {
-            name: "admin";
-            docs: ["The admin account address"];
+            name: "user";
+            docs: ["The user account address"];
             type: "pubkey";
           },
           {
             name: "authority";
-            docs: ["The authority set for this admin"];
+            docs: ["The authority that ended the flashloan"];
             type: "pubkey";
           }
         ];
       };
     },
     {
-      name: "balanceType";
-      docs: ["Type of balance for a token position"];
-      repr: {
-        kind: "rust";
-      };
+      name: "flashloanStarted";
+      docs: ["Event emitted when a user account enters flashloan mode"];
       type: {
-        kind: "enum";
-        variants: [
+        kind: "struct";
+        fields: [
           {
-            name: "collateral";
+            name: "user";
+            docs: ["The user account address"];
+            type: "pubkey";
           },
           {
-            name: "liability";
+            name: "authority";
+            docs: ["The authority that started the flashloan"];
+            type: "pubkey";
           }
         ];
       };
     },
     {
-      name: "bank";
-      docs: ["Bank account data"];
+      name: "pool";
+      docs: ["Pool account data"];
       serialization: "bytemuck";
       repr: {
         kind: "c";
@@ -816,169 +2896,191 @@ export type AnchorLendingExample = {
         kind: "struct";
         fields: [
           {
-            name: "bankId";
-            docs: ["The bank ID within the pool"];
-            type: "u8";
+            name: "authority";
+            docs: ["The authority allowed to manage this pool"];
+            type: "pubkey";
           },
           {
-            name: "poolId";
-            docs: ["The pool ID"];
-            type: "u8";
+            name: "name";
+            docs: ["Pool name, UTF-8 encoded and zero padded"];
+            type: {
+              array: ["u8", 32];
+            };
           },
           {
-            name: "status";
-            docs: ["Current operational status"];
+            name: "bankCount";
+            docs: ["Number of banks initialized in this pool"];
             type: "u8";
           },
           {
-            name: "decimals";
-            docs: ["The decimal places of the token mint"];
+            name: "status";
+            docs: ["Current operational status"];
             type: "u8";
           },
           {
-            name: "initialAssetWeight";
+            name: "closeFactor";
             docs: [
-              "Weight applied to assets for initial collateral ratio calculations"
+              "Maximum percentage of a liability that can be repaid in a single liquidation"
             ];
             type: "u8";
           },
           {
-            name: "maintenanceAssetWeight";
-            docs: [
-              "Weight applied to assets for maintenance collateral ratio calculations"
-            ];
+            name: "maxLiquidationBonus";
+            docs: ["Maximum liquidation bonus percentage paid to liquidators"];
             type: "u8";
           },
           {
-            name: "initialLiabilityWeight";
+            name: "maxPositions";
             docs: [
-              "Weight applied to liabilities for initial borrowing limits"
+              "Maximum number of token positions a user can hold in this pool"
             ];
             type: "u8";
           },
           {
-            name: "maintenanceLiabilityWeight";
-            docs: [
-              "Weight applied to liabilities for maintenance requirements"
-            ];
+            name: "pauseFlags";
+            docs: ["Paused operations for this pool, see `PauseFlag`"];
             type: "u8";
-          },
-          {
-            name: "mint";
-            docs: ["The token mint address"];
-            type: "pubkey";
-          },
-          {
-            name: "priceMessage";
-            docs: ["The price message"];
-            type: {
-              defined: {
-                name: "priceFeedMessage";
-              };
-            };
           }
         ];
       };
     },
     {
-      name: "bankConfigParams";
-      docs: ["Parameters for initializing a new bank"];
+      name: "poolConfigParams";
+      docs: ["Parameters for initializing a new pool"];
       type: {
         kind: "struct";
         fields: [
           {
-            name: "initialAssetWeight";
+            name: "name";
+            docs: ["Pool name, UTF-8 encoded and zero padded"];
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "closeFactor";
             docs: [
-              "Weight applied to assets for initial collateral ratio calculations"
+              "Maximum percentage of a liability that can be repaid in a single liquidation"
             ];
             type: "u8";
           },
           {
-            name: "maintenanceAssetWeight";
-            docs: [
-              "Weight applied to assets for maintenance collateral ratio calculations"
-            ];
+            name: "maxLiquidationBonus";
+            docs: ["Maximum liquidation bonus percentage paid to liquidators"];
             type: "u8";
           },
           {
-            name: "initialLiabilityWeight";
+            name: "maxPositions";
             docs: [
-              "Weight applied to liabilities for initial borrowing limits"
+              "Maximum number of token positions a user can hold in this pool"
             ];
             type: "u8";
+          }
+        ];
+      };
+    },
+    {
+      name: "poolPauseUpdated";
+      docs: ["Event emitted when pool pause flags are updated"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "poolId";
+            docs: ["The pool ID"];
+            type: "u8";
+          },
+          {
+            name: "authority";
+            docs: ["The signer that updated the flags"];
+            type: "pubkey";
+          },
+          {
+            name: "oldFlags";
+            docs: ["Previous pause flags"];
+            type: "u8";
           },
           {
-            name: "maintenanceLiabilityWeight";
-            docs: [
-              "Weight applied to liabilities for maintenance requirements"
-            ];
+            name: "newFlags";
+            docs: ["New pause flags"];
             type: "u8";
           }
         ];
       };
     },
     {
-      name: "bankInitialized";
+      name: "poolUpdated";
+      docs: ["Event emitted when pool configuration is updated"];
       type: {
         kind: "struct";
         fields: [
           {
-            name: "mint";
+            name: "poolId";
+            docs: ["The pool ID that was updated"];
+            type: "u8";
+          },
+          {
+            name: "authority";
+            docs: ["The pool authority"];
             type: "pubkey";
           },
           {
-            name: "groupId";
+            name: "status";
+            docs: ["New pool status"];
             type: "u8";
           },
           {
-            name: "status";
+            name: "closeFactor";
+            docs: ["New close factor"];
             type: "u8";
           },
           {
-            name: "tokenAccount";
-            type: "pubkey";
+            name: "maxLiquidationBonus";
+            docs: ["New maximum liquidation bonus"];
+            type: "u8";
+          },
+          {
+            name: "maxPositions";
+            docs: ["New maximum positions per user"];
+            type: "u8";
           }
         ];
       };
     },
     {
-      name: "bankStatusUpdated";
-      docs: ["Event emitted when bank status is updated"];
+      name: "positionForceSettled";
+      docs: ["Event emitted when a position in a sunset bank is force settled"];
       type: {
         kind: "struct";
         fields: [
+          {
+            name: "user";
+            docs: ["The user account that was settled"];
+            type: "pubkey";
+          },
           {
             name: "mint";
             docs: ["The token mint address"];
             type: "pubkey";
           },
           {
-            name: "oldStatus";
-            docs: ["Previous bank status"];
-            type: "u8";
+            name: "previousBalance";
+            docs: [
+              "Signed balance before settlement, liabilities are negative"
+            ];
+            type: "i64";
           },
           {
-            name: "newStatus";
-            docs: ["New bank status"];
-            type: "u8";
-          }
-        ];
-      };
-    },
-    {
-      name: "pool";
-      docs: ["Pool account data"];
-      serialization: "bytemuck";
-      repr: {
-        kind: "c";
-      };
-      type: {
-        kind: "struct";
-        fields: [
+            name: "settledAmount";
+            docs: ["Amount of collateral paid out from the vault"];
+            type: "u64";
+          },
           {
-            name: "bankCount";
-            docs: ["Number of banks initialized in this pool"];
-            type: "u8";
+            name: "writtenOff";
+            docs: [
+              "Amount of collateral or liability that could not be settled and was written off"
+            ];
+            type: "u64";
           }
         ];
       };
@@ -1062,6 +3164,71 @@ export type AnchorLendingExample = {
         ];
       };
     },
+    {
+      name: "protocolPauseUpdated";
+      docs: ["Event emitted when protocol-wide pause flags are updated"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "admin";
+            docs: ["The admin account address"];
+            type: "pubkey";
+          },
+          {
+            name: "authority";
+            docs: ["The signer that updated the flags"];
+            type: "pubkey";
+          },
+          {
+            name: "oldFlags";
+            docs: ["Previous pause flags"];
+            type: "u8";
+          },
+          {
+            name: "newFlags";
+            docs: ["New pause flags"];
+            type: "u8";
+          }
+        ];
+      };
+    },
+    {
+      name: "sunsetLiabilitySettled";
+      docs: [
+        "Event emitted when a liability in a sunset bank is settled against collateral"
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "user";
+            docs: ["The user account that was settled"];
+            type: "pubkey";
+          },
+          {
+            name: "mint";
+            docs: ["The sunset bank token mint"];
+            type: "pubkey";
+          },
+          {
+            name: "collateralMint";
+            docs: ["The collateral token mint"];
+            type: "pubkey";
+          },
+          {
+            name: "repaidAmount";
+            docs: ["Amount of liability repaid by the caller"];
+            type: "u64";
+          },
+          {
+            name: "seizedAmount";
+            docs: ["Amount of collateral paid to the caller"];
+            type: "u64";
+          }
+        ];
+      };
+    },
     {
       name: "tokenBalance";
       docs: ["Represents a single token balance entry"];
@@ -1100,6 +3267,76 @@ export type AnchorLendingExample = {
         ];
       };
     },
+    {
+      name: "updateBankConfigParams";
+      docs: ["Parameters for updating the risk weights of a listed bank"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "initialAssetWeight";
+            docs: [
+              "Weight applied to assets for initial collateral ratio calculations"
+            ];
+            type: "u8";
+          },
+          {
+            name: "maintenanceAssetWeight";
+            docs: [
+              "Weight applied to assets for maintenance collateral ratio calculations"
+            ];
+            type: "u8";
+          },
+          {
+            name: "initialLiabilityWeight";
+            docs: [
+              "Weight applied to liabilities for initial borrowing limits"
+            ];
+            type: "u8";
+          },
+          {
+            name: "maintenanceLiabilityWeight";
+            docs: [
+              "Weight applied to liabilities for maintenance requirements"
+            ];
+            type: "u8";
+          }
+        ];
+      };
+    },
+    {
+      name: "updatePoolParams";
+      docs: ["Parameters for updating pool configuration"];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "status";
+            docs: ["New operational status"];
+            type: "u8";
+          },
+          {
+            name: "closeFactor";
+            docs: [
+              "Maximum percentage of a liability that can be repaid in a single liquidation"
+            ];
+            type: "u8";
+          },
+          {
+            name: "maxLiquidationBonus";
+            docs: ["Maximum liquidation bonus percentage paid to liquidators"];
+            type: "u8";
+          },
+          {
+            name: "maxPositions";
+            docs: [
+              "Maximum number of token positions a user can hold in this pool"
+            ];
+            type: "u8";
+          }
+        ];
+      };
+    },
     {
       name: "updatePriceParams";
       docs: ["Parameters for updating price information"];
@@ -1169,11 +3406,18 @@ export type AnchorLendingExample = {
             docs: ["Bump seed for PDA validation"];
             type: "u8";
           },
+          {
+            name: "flashloan";
+            docs: [
+              "Non-zero while in flashloan mode, health checks are deferred to end_flashloan"
+            ];
+            type: "u8";
+          },
           {
             name: "padding";
             docs: ["Padding for memory alignment"];
             type: {
-              array: ["u8", 4];
+              array: ["u8", 3];
             };
           },
           {
@@ -1192,6 +3436,66 @@ export type AnchorLendingExample = {
                 16
               ];
             };
+          },
+          {
+            name: "pendingAuthority";
+            docs: [
+              "Authority proposed to take over this account, default if none pending",
+              "Appended after the balances so accounts created before it keep their layout"
+            ];
+            type: "pubkey";
+          }
+        ];
+      };
+    },
+    {
+      name: "userAuthorityProposed";
+      docs: [
+        "Event emitted when a new authority is proposed for a user account"
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "user";
+            docs: ["The user account address"];
+            type: "pubkey";
+          },
+          {
+            name: "authority";
+            docs: ["The current authority"];
+            type: "pubkey";
+          },
+          {
+            name: "pendingAuthority";
+            docs: ["The proposed authority"];
+            type: "pubkey";
+          }
+        ];
+      };
+    },
+    {
+      name: "userAuthorityTransferred";
+      docs: [
+        "Event emitted when a user account is transferred to a new authority"
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "user";
+            docs: ["The user account address"];
+            type: "pubkey";
+          },
+          {
+            name: "oldAuthority";
+            docs: ["The old authority"];
+            type: "pubkey";
+          },
+          {
+            name: "newAuthority";
+            docs: ["The new authority"];
+            type: "pubkey";
           }
         ];
       };
@@ -1248,6 +3552,61 @@ export type AnchorLendingExample = {
         ];
       };
     },
+    {
+      name: "userHealth";
+      docs: [
+        "Health of a user position, returned to clients through return data",
+        "Values are in USD with 6 decimal places precision"
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "totalCollateral";
+            docs: ["Collateral value without weights"];
+            type: "u128";
+          },
+          {
+            name: "totalLiability";
+            docs: ["Liability value without weights"];
+            type: "u128";
+          },
+          {
+            name: "weightedCollateral";
+            docs: ["Collateral value with initial asset weights"];
+            type: "u128";
+          },
+          {
+            name: "weightedLiability";
+            docs: ["Liability value with initial liability weights"];
+            type: "u128";
+          },
+          {
+            name: "maintenanceCollateral";
+            docs: ["Collateral value with maintenance asset weights"];
+            type: "u128";
+          },
+          {
+            name: "maintenanceLiability";
+            docs: ["Liability value with maintenance liability weights"];
+            type: "u128";
+          },
+          {
+            name: "healthRatio";
+            docs: [
+              "Maintenance collateral to liability ratio in basis points",
+              "u64::MAX when the position has no liabilities"
+            ];
+            type: "u64";
+          },
+          {
+            name: "liquidatable";
+            docs: ["Whether the position is below its maintenance requirement"];
+            type: "bool";
+          }
+        ];
+      };
+    },
     {
       name: "userInitialized";
       docs: ["Event emitted when a new user account is initialized"];
@@ -1308,6 +3667,10 @@ export type AnchorLendingExample = {
           {
             name: "poolId";
             type: "u8";
+          },
+          {
+            name: "authority";
+            type: "pubkey";
           }
         ];
       };
@@ -27,6 +27,16 @@ export function hexToBytes32(hex: string): string {
   return "0x" + hex;
 }
 
+/**
+ * Calculate the flash loan fee owed on a borrowed amount, rounded up
+ * @param amount Amount borrowed
+ * @param feeBps Bank's flash loan fee in basis points
+ * @returns Fee owed on top of the amount
+ */
+export function calculateFlashLoanFee(amount: number, feeBps: number): number {
+  return Math.ceil((amount * feeBps) / 10_000);
+}
+
 export const PROGRAM_ID = new PublicKey(
   "HKViZ7i7fEpfqcpCpDWAfmZpuVZ6WSRXST85nf1w227q"
 );
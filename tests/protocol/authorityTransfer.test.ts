@@ -0,0 +1,179 @@
+import { BankrunContextWrapper, PROGRAM_ID, sendTransaction } from "@/helpers";
+import {
+  getAdminPublicKey,
+  AdminAccount,
+  getInitializeIx,
+  getProposeAuthorityIx,
+  getAcceptAuthorityIx,
+  getCancelAuthorityIx,
+} from "@/sdk";
+import * as anchor from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { BanksClient, ProgramTestContext, startAnchor } from "solana-bankrun";
+import { PublicKey, Keypair, Connection, SystemProgram } from "@solana/web3.js";
+
+describe("Admin Authority Transfer", () => {
+  let context: ProgramTestContext;
+  let client: BanksClient;
+  let provider: BankrunProvider;
+  let connection: Connection;
+  let bankrunContextWrapper: BankrunContextWrapper;
+
+  let authority: Keypair;
+  let newAuthority: Keypair;
+  let adminKey: PublicKey;
+
+  /**
+   * Fetch and decode the admin account
+   * @returns Decoded admin account
+   */
+  async function fetchAdmin(): Promise<AdminAccount> {
+    const adminInfo = await connection.getAccountInfo(adminKey);
+    return AdminAccount.decode(adminInfo.data);
+  }
+
+  /**
+   * Propose the new authority using the current authority
+   */
+  async function propose() {
+    const ix = await getProposeAuthorityIx(
+      authority.publicKey,
+      newAuthority.publicKey
+    );
+    await sendTransaction([ix], connection, authority);
+  }
+
+  beforeEach(async () => {
+    // Set up fresh testing environment
+    context = await startAnchor("", [], []);
+    client = context.banksClient;
+    authority = context.payer;
+
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+
+    bankrunContextWrapper = new BankrunContextWrapper(context);
+    connection = bankrunContextWrapper.connection.toConnection();
+
+    newAuthority = Keypair.generate();
+    adminKey = getAdminPublicKey(PROGRAM_ID);
+
+    // Initialize admin account using SDK
+    const initIx = await getInitializeIx(authority.publicKey);
+    await sendTransaction([initIx], connection, authority);
+
+    // Fund the new authority so it can pay for its own transactions
+    const fundIx = SystemProgram.transfer({
+      fromPubkey: authority.publicKey,
+      toPubkey: newAuthority.publicKey,
+      lamports: 1_000_000_000,
+    });
+    await sendTransaction([fundIx], connection, authority);
+  });
+
+  /**
+   * Test: Propose Authority
+   * Flow:
+   * 1. Propose new authority
+   * 2. Verify pending authority is stored and authority unchanged
+   * Expected: Only the pending authority should change
+   */
+  it("should store pending authority on proposal", async () => {
+    await propose();
+
+    const adminAccount = await fetchAdmin();
+    expect(adminAccount.authority.equals(authority.publicKey)).toBe(true);
+    expect(adminAccount.pendingAuthority.equals(newAuthority.publicKey)).toBe(
+      true
+    );
+  });
+
+  /**
+   * Test: Accept Authority
+   * Flow:
+   * 1. Propose new authority
+   * 2. Accept with the new authority
+   * Expected: Authority updated and pending authority cleared
+   */
+  it("should update authority after acceptance", async () => {
+    await propose();
+
+    const acceptIx = await getAcceptAuthorityIx(newAuthority.publicKey);
+    await sendTransaction([acceptIx], connection, newAuthority);
+
+    const adminAccount = await fetchAdmin();
+    expect(adminAccount.authority.equals(newAuthority.publicKey)).toBe(true);
+    expect(adminAccount.pendingAuthority.equals(PublicKey.default)).toBe(true);
+  });
+
+  /**
+   * Test: Accept Without Proposal
+   * Flow:
+   * 1. Try to accept without a pending proposal
+   * Expected: Transaction should fail and authority unchanged
+   */
+  it("should fail to accept without pending proposal", async () => {
+    const acceptIx = await getAcceptAuthorityIx(newAuthority.publicKey);
+    await expect(
+      sendTransaction([acceptIx], connection, newAuthority)
+    ).rejects.toThrow();
+
+    const adminAccount = await fetchAdmin();
+    expect(adminAccount.authority.equals(authority.publicKey)).toBe(true);
+  });
+
+  /**
+   * Test: Cancel Proposal
+   * Flow:
+   * 1. Propose new authority
+   * 2. Cancel the proposal
+   * 3. Try to accept the cancelled proposal
+   * Expected: Pending authority cleared and acceptance fails
+   */
+  it("should cancel pending authority", async () => {
+    await propose();
+
+    const cancelIx = await getCancelAuthorityIx(authority.publicKey);
+    await sendTransaction([cancelIx], connection, authority);
+
+    const adminAccount = await fetchAdmin();
+    expect(adminAccount.pendingAuthority.equals(PublicKey.default)).toBe(true);
+
+    const acceptIx = await getAcceptAuthorityIx(newAuthority.publicKey);
+    await expect(
+      sendTransaction([acceptIx], connection, newAuthority)
+    ).rejects.toThrow();
+  });
+
+  /**
+   * Test: Cancel Without Proposal
+   * Flow:
+   * 1. Try to cancel when nothing is pending
+   * Expected: Transaction should fail with NoPendingAuthority error
+   */
+  it("should fail to cancel without pending proposal", async () => {
+    const cancelIx = await getCancelAuthorityIx(authority.publicKey);
+    await expect(
+      sendTransaction([cancelIx], connection, authority)
+    ).rejects.toThrow();
+  });
+
+  /**
+   * Test: Unauthorized Proposal
+   * Flow:
+   * 1. Try to propose authority using non-authority signer
+   * Expected: Transaction should fail and nothing is pending
+   */
+  it("should fail when proposed by unauthorized signer", async () => {
+    const ix = await getProposeAuthorityIx(
+      newAuthority.publicKey,
+      newAuthority.publicKey
+    );
+    await expect(
+      sendTransaction([ix], connection, newAuthority)
+    ).rejects.toThrow();
+
+    const adminAccount = await fetchAdmin();
+    expect(adminAccount.pendingAuthority.equals(PublicKey.default)).toBe(true);
+  });
+});
@@ -5,7 +5,10 @@ import {
   AdminAccount,
   getInitializePoolIx,
   getPoolPublicKey,
+  encodePoolName,
+  DEFAULT_POOL_CONFIG,
   PoolAccount,
+  PoolStatus,
 } from "@/sdk";
 import * as anchor from "@coral-xyz/anchor";
 import { BankrunProvider } from "anchor-bankrun";
@@ -72,6 +75,65 @@ describe("Initialize Pool", () => {
     const poolInfo = await connection.getAccountInfo(poolKey);
     const pool = PoolAccount.decode(poolInfo.data);
     expect(pool.bankCount).toBe(0);
+    expect(pool.authority).toEqual(authority.publicKey);
+    expect(pool.status).toBe(PoolStatus.Active);
+    expect(pool.closeFactor).toBe(DEFAULT_POOL_CONFIG.closeFactor);
+    expect(pool.maxLiquidationBonus).toBe(
+      DEFAULT_POOL_CONFIG.maxLiquidationBonus
+    );
+    expect(pool.maxPositions).toBe(DEFAULT_POOL_CONFIG.maxPositions);
+  });
+
+  /**
+   * Test: Initialize Pool With Custom Authority
+   * Flow:
+   * 1. Initialize pool with a separate pool authority and name
+   * 2. Verify pool stores the authority and name
+   * Expected: Pool should be managed by the given authority
+   */
+  it("should initialize pool with custom authority and name", async () => {
+    const poolAuthority = Keypair.generate();
+    const params = {
+      ...DEFAULT_POOL_CONFIG,
+      name: encodePoolName("Main Pool"),
+    };
+
+    const ix = await getInitializePoolIx(
+      authority.publicKey,
+      0,
+      params,
+      poolAuthority.publicKey
+    );
+    await sendTransaction([ix], connection, authority);
+
+    poolKey = getPoolPublicKey(0, PROGRAM_ID);
+    const poolInfo = await connection.getAccountInfo(poolKey);
+    const pool = PoolAccount.decode(poolInfo.data);
+    expect(pool.authority).toEqual(poolAuthority.publicKey);
+    expect(pool.name).toEqual(encodePoolName("Main Pool"));
+  });
+
+  /**
+   * Test: Invalid Pool Parameters
+   * Flow:
+   * 1. Try to initialize pools with out of range parameters
+   * Expected: Each transaction should fail with invalid input error
+   */
+  it("should fail with invalid pool parameters", async () => {
+    const invalidParams = [
+      { ...DEFAULT_POOL_CONFIG, closeFactor: 0 },
+      { ...DEFAULT_POOL_CONFIG, closeFactor: 101 },
+      { ...DEFAULT_POOL_CONFIG, maxLiquidationBonus: 51 },
+      { ...DEFAULT_POOL_CONFIG, maxPositions: 0 },
+      { ...DEFAULT_POOL_CONFIG, maxPositions: 17 },
+    ];
+
+    for (const params of invalidParams) {
+      const ix = await getInitializePoolIx(authority.publicKey, 0, params);
+      await expect(
+        sendTransaction([ix], connection, authority)
+      ).rejects.toThrow(/0x1772/);
+    }
   });
 
   /**
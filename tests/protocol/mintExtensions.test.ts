@@ -0,0 +1,192 @@
+import { BankrunContextWrapper, PROGRAM_ID, sendTransaction } from "@/helpers";
+import {
+  getBankPublicKey,
+  getInitializeIx,
+  getInitializePoolIx,
+  getInitializeBankIx,
+  BankAccount,
+  MintExtension,
+  TokenProgram,
+  DEFAULT_BANK_CONFIG,
+  DEFAULT_POOL_CONFIG,
+} from "@/sdk";
+import * as anchor from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { BanksClient, ProgramTestContext, startAnchor } from "solana-bankrun";
+import {
+  Keypair,
+  Connection,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createInitializePermanentDelegateInstruction,
+  getMintLen,
+} from "@solana/spl-token";
+
+describe("Mint Extension Listing", () => {
+  let context: ProgramTestContext;
+  let client: BanksClient;
+  let provider: BankrunProvider;
+  let connection: Connection;
+  let bankrunContextWrapper: BankrunContextWrapper;
+
+  let authority: Keypair;
+  let poolAuthority: Keypair;
+  let mint: Keypair;
+
+  const adminPoolId = 0;
+  const delegatedPoolId = 1;
+  const bankId = 0;
+
+  /**
+   * Create a Token-2022 mint with a permanent delegate
+   */
+  async function createPermanentDelegateMint() {
+    const mintLen = getMintLen([ExtensionType.PermanentDelegate]);
+    const lamports = await connection.getMinimumBalanceForRentExemption(
+      mintLen
+    );
+
+    const tx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: authority.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space: mintLen,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializePermanentDelegateInstruction(
+        mint.publicKey,
+        authority.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMintInstruction(
+        mint.publicKey,
+        6,
+        authority.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+    tx.recentBlockhash = context.lastBlockhash;
+    tx.feePayer = authority.publicKey;
+    tx.sign(authority, mint);
+    await client.processTransaction(tx);
+  }
+
+  beforeEach(async () => {
+    // Set up testing environment
+    context = await startAnchor("", [], []);
+    client = context.banksClient;
+    authority = context.payer;
+    poolAuthority = Keypair.generate();
+    mint = Keypair.generate();
+
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+
+    bankrunContextWrapper = new BankrunContextWrapper(context);
+    connection = bankrunContextWrapper.connection.toConnection();
+
+    await createPermanentDelegateMint();
+
+    // Initialize admin, a pool run by the admin and one run by a separate authority
+    const initIx = await getInitializeIx(authority.publicKey);
+    const adminPoolIx = await getInitializePoolIx(
+      authority.publicKey,
+      adminPoolId
+    );
+    const delegatedPoolIx = await getInitializePoolIx(
+      authority.publicKey,
+      delegatedPoolId,
+      DEFAULT_POOL_CONFIG,
+      poolAuthority.publicKey
+    );
+    const fundIx = SystemProgram.transfer({
+      fromPubkey: authority.publicKey,
+      toPubkey: poolAuthority.publicKey,
+      lamports: 1_000_000_000,
+    });
+    await sendTransaction(
+      [initIx, adminPoolIx, delegatedPoolIx, fundIx],
+      connection,
+      authority
+    );
+  });
+
+  /**
+   * Test: Restricted Extension Rejected By Default
+   * Flow:
+   * 1. List a permanent delegate mint without an allowlist
+   * Expected: Transaction should fail and no bank is created
+   */
+  it("should reject a permanent delegate mint by default", async () => {
+    const ix = await getInitializeBankIx(
+      authority.publicKey,
+      mint.publicKey,
+      adminPoolId,
+      bankId,
+      TokenProgram.TOKEN_2022_PROGRAM
+    );
+    await expect(
+      sendTransaction([ix], connection, authority)
+    ).rejects.toThrow();
+
+    const bankKey = getBankPublicKey(adminPoolId, bankId, PROGRAM_ID);
+    expect(await connection.getAccountInfo(bankKey)).toBeNull();
+  });
+
+  /**
+   * Test: Admin Authority Allowlists Extension
+   * Flow:
+   * 1. Admin authority lists the mint allowlisting PermanentDelegate
+   * Expected: Bank is created and records the allowlist
+   */
+  it("should accept an extension allowlisted by the admin authority", async () => {
+    const ix = await getInitializeBankIx(
+      authority.publicKey,
+      mint.publicKey,
+      adminPoolId,
+      bankId,
+      TokenProgram.TOKEN_2022_PROGRAM,
+      {
+        ...DEFAULT_BANK_CONFIG,
+        allowedExtensions: MintExtension.PermanentDelegate,
+      }
+    );
+    await sendTransaction([ix], connection, authority);
+
+    const bankKey = getBankPublicKey(adminPoolId, bankId, PROGRAM_ID);
+    const bank = BankAccount.decode(
+      (await connection.getAccountInfo(bankKey)).data
+    );
+    expect(bank.allowedExtensions).toBe(MintExtension.PermanentDelegate);
+  });
+
+  /**
+   * Test: Pool Authority Cannot Allowlist
+   * Flow:
+   * 1. Pool authority lists the mint allowlisting PermanentDelegate
+   * Expected: Transaction should fail with unauthorized error
+   */
+  it("should fail when the pool authority allowlists an extension", async () => {
+    const ix = await getInitializeBankIx(
+      poolAuthority.publicKey,
+      mint.publicKey,
+      delegatedPoolId,
+      bankId,
+      TokenProgram.TOKEN_2022_PROGRAM,
+      {
+        ...DEFAULT_BANK_CONFIG,
+        allowedExtensions: MintExtension.PermanentDelegate,
+      }
+    );
+    await expect(
+      sendTransaction([ix], connection, poolAuthority)
+    ).rejects.toThrow();
+  });
+});
@@ -0,0 +1,194 @@
+import { BankrunContextWrapper, PROGRAM_ID, sendTransaction } from "@/helpers";
+import {
+  getAdminPublicKey,
+  getPoolPublicKey,
+  getInitializeIx,
+  getInitializePoolIx,
+  getGrantRoleIx,
+  getSetProtocolPauseIx,
+  getSetPoolPauseIx,
+  AdminAccount,
+  AdminRole,
+  PoolAccount,
+  PauseFlag,
+  PAUSE_ALL,
+  DEFAULT_POOL_CONFIG,
+} from "@/sdk";
+import * as anchor from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { BanksClient, ProgramTestContext, startAnchor } from "solana-bankrun";
+import { PublicKey, Keypair, Connection, SystemProgram } from "@solana/web3.js";
+
+describe("Emergency Pause", () => {
+  let context: ProgramTestContext;
+  let client: BanksClient;
+  let provider: BankrunProvider;
+  let connection: Connection;
+  let bankrunContextWrapper: BankrunContextWrapper;
+
+  let authority: Keypair;
+  let guardian: Keypair;
+  let poolAuthority: Keypair;
+  let adminKey: PublicKey;
+  let poolKey: PublicKey;
+
+  const poolId = 0;
+
+  /**
+   * Fetch and decode the admin account
+   * @returns Decoded admin account
+   */
+  async function fetchAdmin(): Promise<AdminAccount> {
+    const adminInfo = await connection.getAccountInfo(adminKey);
+    return AdminAccount.decode(adminInfo.data);
+  }
+
+  /**
+   * Fetch and decode the pool account
+   * @returns Decoded pool account
+   */
+  async function fetchPool(): Promise<PoolAccount> {
+    const poolInfo = await connection.getAccountInfo(poolKey);
+    return PoolAccount.decode(poolInfo.data);
+  }
+
+  /**
+   * Create a transfer funding a keypair so it can pay for its own transactions
+   * @param recipient Keypair to fund
+   * @returns System transfer instruction
+   */
+  function getFundIx(recipient: Keypair) {
+    return SystemProgram.transfer({
+      fromPubkey: authority.publicKey,
+      toPubkey: recipient.publicKey,
+      lamports: 1_000_000_000,
+    });
+  }
+
+  beforeEach(async () => {
+    // Set up testing environment
+    context = await startAnchor("", [], []);
+    client = context.banksClient;
+    authority = context.payer;
+    guardian = Keypair.generate();
+    poolAuthority = Keypair.generate();
+
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+
+    bankrunContextWrapper = new BankrunContextWrapper(context);
+    connection = bankrunContextWrapper.connection.toConnection();
+
+    adminKey = getAdminPublicKey(PROGRAM_ID);
+    poolKey = getPoolPublicKey(poolId, PROGRAM_ID);
+
+    // Initialize admin, a guardian and a pool managed by a separate authority
+    const initIx = await getInitializeIx(authority.publicKey);
+    const grantIx = await getGrantRoleIx(
+      authority.publicKey,
+      AdminRole.EmergencyGuardian,
+      guardian.publicKey
+    );
+    const initPoolIx = await getInitializePoolIx(
+      authority.publicKey,
+      poolId,
+      DEFAULT_POOL_CONFIG,
+      poolAuthority.publicKey
+    );
+    await sendTransaction(
+      [
+        initIx,
+        grantIx,
+        initPoolIx,
+        getFundIx(guardian),
+        getFundIx(poolAuthority),
+      ],
+      connection,
+      authority
+    );
+  });
+
+  /**
+   * Test: Guardian Pauses Protocol
+   * Flow:
+   * 1. Guardian pauses deposits and withdrawals protocol-wide
+   * 2. Guardian clears all flags
+   * Expected: Admin pause flags follow each update
+   */
+  it("should set protocol pause flags as guardian", async () => {
+    const flags = PauseFlag.Deposit | PauseFlag.Withdraw;
+    const pauseIx = await getSetProtocolPauseIx(guardian.publicKey, flags);
+    await sendTransaction([pauseIx], connection, guardian);
+    expect((await fetchAdmin()).pauseFlags).toBe(flags);
+
+    const unpauseIx = await getSetProtocolPauseIx(guardian.publicKey, 0);
+    await sendTransaction([unpauseIx], connection, guardian);
+    expect((await fetchAdmin()).pauseFlags).toBe(0);
+  });
+
+  /**
+   * Test: Unauthorized Protocol Pause
+   * Flow:
+   * 1. Pool authority tries to pause the whole protocol
+   * Expected: Transaction should fail and flags unchanged
+   */
+  it("should fail to pause protocol without guardian role", async () => {
+    const ix = await getSetProtocolPauseIx(poolAuthority.publicKey, PAUSE_ALL);
+    await expect(
+      sendTransaction([ix], connection, poolAuthority)
+    ).rejects.toThrow();
+    expect((await fetchAdmin()).pauseFlags).toBe(0);
+  });
+
+  /**
+   * Test: Unknown Pause Flags
+   * Flow:
+   * 1. Try to set a flag outside PAUSE_ALL
+   * Expected: Transaction should fail with invalid input error
+   */
+  it("should fail with unknown pause flags", async () => {
+    const ix = await getSetProtocolPauseIx(guardian.publicKey, PAUSE_ALL + 1);
+    await expect(sendTransaction([ix], connection, guardian)).rejects.toThrow();
+  });
+
+  /**
+   * Test: Pool Authority And Guardian Pause Pool
+   * Flow:
+   * 1. Pool authority pauses liquidations in its pool
+   * 2. Guardian pauses every operation in the pool
+   * Expected: Pool pause flags follow each update, protocol flags untouched
+   */
+  it("should set pool pause flags as pool authority or guardian", async () => {
+    const poolIx = await getSetPoolPauseIx(
+      poolAuthority.publicKey,
+      poolId,
+      PauseFlag.Liquidate
+    );
+    await sendTransaction([poolIx], connection, poolAuthority);
+    expect((await fetchPool()).pauseFlags).toBe(PauseFlag.Liquidate);
+
+    const guardianIx = await getSetPoolPauseIx(
+      guardian.publicKey,
+      poolId,
+      PAUSE_ALL
+    );
+    await sendTransaction([guardianIx], connection, guardian);
+    expect((await fetchPool()).pauseFlags).toBe(PAUSE_ALL);
+    expect((await fetchAdmin()).pauseFlags).toBe(0);
+  });
+
+  /**
+   * Test: Unauthorized Pool Pause
+   * Flow:
+   * 1. Unrelated account tries to pause the pool
+   * Expected: Transaction should fail and flags unchanged
+   */
+  it("should fail to pause pool when unauthorized", async () => {
+    const other = Keypair.generate();
+    await sendTransaction([getFundIx(other)], connection, authority);
+
+    const ix = await getSetPoolPauseIx(other.publicKey, poolId, PAUSE_ALL);
+    await expect(sendTransaction([ix], connection, other)).rejects.toThrow();
+    expect((await fetchPool()).pauseFlags).toBe(0);
+  });
+});
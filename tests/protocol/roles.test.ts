@@ -0,0 +1,149 @@
+import { BankrunContextWrapper, PROGRAM_ID, sendTransaction } from "@/helpers";
+import {
+  getAdminPublicKey,
+  getInitializeIx,
+  getGrantRoleIx,
+  getRevokeRoleIx,
+  AdminAccount,
+  AdminRole,
+} from "@/sdk";
+import * as anchor from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { BanksClient, ProgramTestContext, startAnchor } from "solana-bankrun";
+import { PublicKey, Keypair, Connection, SystemProgram } from "@solana/web3.js";
+
+describe("Admin Roles", () => {
+  let context: ProgramTestContext;
+  let client: BanksClient;
+  let provider: BankrunProvider;
+  let connection: Connection;
+  let bankrunContextWrapper: BankrunContextWrapper;
+
+  let authority: Keypair;
+  let grantee: Keypair;
+  let adminKey: PublicKey;
+
+  /**
+   * Fetch and decode the admin account
+   * @returns Decoded admin account
+   */
+  async function fetchAdmin(): Promise<AdminAccount> {
+    const adminInfo = await connection.getAccountInfo(adminKey);
+    return AdminAccount.decode(adminInfo.data);
+  }
+
+  /**
+   * Read the holder of a role from the admin account
+   * @param admin Decoded admin account
+   * @param role Role to read
+   * @returns The role holder
+   */
+  function roleHolder(admin: AdminAccount, role: AdminRole): PublicKey {
+    switch (role) {
+      case AdminRole.RiskManager:
+        return admin.riskManager;
+      case AdminRole.ListingManager:
+        return admin.listingManager;
+      case AdminRole.EmergencyGuardian:
+        return admin.emergencyGuardian;
+      case AdminRole.OracleKeeper:
+        return admin.oracleKeeper;
+    }
+  }
+
+  beforeEach(async () => {
+    // Set up fresh testing environment
+    context = await startAnchor("", [], []);
+    client = context.banksClient;
+    authority = context.payer;
+    grantee = Keypair.generate();
+
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+
+    bankrunContextWrapper = new BankrunContextWrapper(context);
+    connection = bankrunContextWrapper.connection.toConnection();
+
+    adminKey = getAdminPublicKey(PROGRAM_ID);
+
+    const initIx = await getInitializeIx(authority.publicKey);
+    await sendTransaction([initIx], connection, authority);
+  });
+
+  /**
+   * Test: Grant And Revoke Every Role
+   * Flow:
+   * 1. Grant each role to the grantee
+   * 2. Revoke each role
+   * Expected: Role holder is set then cleared
+   */
+  it("should grant and revoke every role", async () => {
+    const roles = [
+      AdminRole.RiskManager,
+      AdminRole.ListingManager,
+      AdminRole.EmergencyGuardian,
+      AdminRole.OracleKeeper,
+    ];
+
+    for (const role of roles) {
+      const grantIx = await getGrantRoleIx(
+        authority.publicKey,
+        role,
+        grantee.publicKey
+      );
+      await sendTransaction([grantIx], connection, authority);
+      expect(roleHolder(await fetchAdmin(), role)).toEqual(grantee.publicKey);
+
+      const revokeIx = await getRevokeRoleIx(authority.publicKey, role);
+      await sendTransaction([revokeIx], connection, authority);
+      expect(roleHolder(await fetchAdmin(), role)).toEqual(PublicKey.default);
+    }
+  });
+
+  /**
+   * Test: Invalid Role
+   * Flow:
+   * 1. Try to grant a role value outside the enum
+   * Expected: Transaction should fail with invalid input error
+   */
+  it("should fail with invalid role", async () => {
+    const ix = await getGrantRoleIx(
+      authority.publicKey,
+      4 as AdminRole,
+      grantee.publicKey
+    );
+    await expect(
+      sendTransaction([ix], connection, authority)
+    ).rejects.toThrow();
+  });
+
+  /**
+   * Test: Role Holder Cannot Grant Roles
+   * Flow:
+   * 1. Grant risk manager role to grantee
+   * 2. Grantee tries to grant itself the listing manager role
+   * Expected: Transaction should fail, only the admin authority can grant
+   */
+  it("should fail when role holder grants roles", async () => {
+    const fundIx = SystemProgram.transfer({
+      fromPubkey: authority.publicKey,
+      toPubkey: grantee.publicKey,
+      lamports: 1_000_000_000,
+    });
+    const grantIx = await getGrantRoleIx(
+      authority.publicKey,
+      AdminRole.RiskManager,
+      grantee.publicKey
+    );
+    await sendTransaction([fundIx, grantIx], connection, authority);
+
+    const ix = await getGrantRoleIx(
+      grantee.publicKey,
+      AdminRole.ListingManager,
+      grantee.publicKey
+    );
+    await expect(sendTransaction([ix], connection, grantee)).rejects.toThrow();
+
+    expect((await fetchAdmin()).listingManager).toEqual(PublicKey.default);
+  });
+});
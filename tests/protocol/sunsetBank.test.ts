@@ -0,0 +1,221 @@
+import {
+  BankrunContextWrapper,
+  constructMint,
+  constructTokenAccount,
+  PROGRAM_ID,
+  sendTransaction,
+  USDC_MINT,
+} from "@/helpers";
+import {
+  getBankPublicKey,
+  getBankTokenAccountPublicKey,
+  getInitializeIx,
+  getInitializePoolIx,
+  getInitializeBankIx,
+  getSunsetBankIx,
+  getCloseBankIx,
+  getUpdateBankStatusIx,
+  BankAccount,
+  BankStatus,
+  TokenProgram,
+} from "@/sdk";
+import * as anchor from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { BanksClient, ProgramTestContext, startAnchor } from "solana-bankrun";
+import { PublicKey, Keypair, Connection } from "@solana/web3.js";
+
+describe("Bank Sunset", () => {
+  let context: ProgramTestContext;
+  let client: BanksClient;
+  let provider: BankrunProvider;
+  let connection: Connection;
+  let bankrunContextWrapper: BankrunContextWrapper;
+
+  let authority: Keypair;
+  let bankKey: PublicKey;
+  let receiverTokenAccount: PublicKey;
+
+  const poolId = 0;
+  const bankId = 0;
+  const sunsetPeriod = 7 * 24 * 60 * 60;
+
+  /**
+   * Get the current bankrun clock timestamp
+   * @returns Unix timestamp in seconds
+   */
+  async function now(): Promise<number> {
+    return Number((await client.getClock()).unixTimestamp);
+  }
+
+  /**
+   * Sunset the bank with a deadline relative to the current time
+   * @param offset Seconds from now until the deadline
+   */
+  async function sunset(offset: number) {
+    const ix = await getSunsetBankIx(
+      authority.publicKey,
+      poolId,
+      bankId,
+      (await now()) + offset
+    );
+    await sendTransaction([ix], connection, authority);
+  }
+
+  /**
+   * Create instruction to close the bank using the admin authority as caller and receiver
+   * @returns Close bank instruction
+   */
+  async function getCloseIx() {
+    return await getCloseBankIx(
+      authority.publicKey,
+      poolId,
+      bankId,
+      authority.publicKey,
+      receiverTokenAccount,
+      USDC_MINT
+    );
+  }
+
+  beforeEach(async () => {
+    // Set up testing environment
+    context = await startAnchor(
+      "",
+      [],
+      [
+        constructMint(
+          USDC_MINT,
+          0,
+          PublicKey.default,
+          0,
+          6,
+          0,
+          PublicKey.default
+        ),
+      ]
+    );
+    client = context.banksClient;
+    authority = context.payer;
+
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+
+    bankrunContextWrapper = new BankrunContextWrapper(context);
+    connection = bankrunContextWrapper.connection.toConnection();
+
+    bankKey = getBankPublicKey(poolId, bankId, PROGRAM_ID);
+
+    // Receiver token account owned by the pool authority
+    const receiverAccount = constructTokenAccount(
+      USDC_MINT,
+      authority.publicKey,
+      0
+    );
+    receiverTokenAccount = receiverAccount.address;
+    context.setAccount(receiverAccount.address, receiverAccount.info);
+
+    // Initialize admin, pool and an active bank
+    const initIx = await getInitializeIx(authority.publicKey);
+    const initPoolIx = await getInitializePoolIx(authority.publicKey, poolId);
+    await sendTransaction([initIx, initPoolIx], connection, authority);
+
+    const initBankIx = await getInitializeBankIx(
+      authority.publicKey,
+      USDC_MINT,
+      poolId,
+      bankId,
+      TokenProgram.TOKEN_PROGRAM
+    );
+    const activateIx = await getUpdateBankStatusIx(
+      authority.publicKey,
+      BankStatus.Active,
+      poolId,
+      bankId
+    );
+    await sendTransaction([initBankIx, activateIx], connection, authority);
+  });
+
+  /**
+   * Test: Sunset Bank
+   * Flow:
+   * 1. Sunset the bank with a deadline one week out
+   * Expected: Bank is in Sunset with the deadline stored
+   */
+  it("should sunset bank with deadline", async () => {
+    const deadline = (await now()) + sunsetPeriod;
+    const ix = await getSunsetBankIx(
+      authority.publicKey,
+      poolId,
+      bankId,
+      deadline
+    );
+    await sendTransaction([ix], connection, authority);
+
+    const bankInfo = await connection.getAccountInfo(bankKey);
+    const bank = BankAccount.decode(bankInfo.data);
+    expect(bank.status).toBe(BankStatus.Sunset);
+    expect(bank.sunsetDeadline.toNumber()).toBe(deadline);
+  });
+
+  /**
+   * Test: Deadline In The Past
+   * Flow:
+   * 1. Try to sunset the bank with a deadline that has already passed
+   * Expected: Transaction should fail with invalid input error
+   */
+  it("should fail with deadline in the past", async () => {
+    await expect(sunset(-1)).rejects.toThrow();
+  });
+
+  /**
+   * Test: Sunset Requires Deadline
+   * Flow:
+   * 1. Try to set Sunset through update_bank_status
+   * Expected: Transaction should fail, sunset must be started with a deadline
+   */
+  it("should not sunset through bank status update", async () => {
+    const ix = await getUpdateBankStatusIx(
+      authority.publicKey,
+      BankStatus.Sunset,
+      poolId,
+      bankId
+    );
+    await expect(
+      sendTransaction([ix], connection, authority)
+    ).rejects.toThrow();
+  });
+
+  /**
+   * Test: Close Before Deadline
+   * Flow:
+   * 1. Sunset the bank
+   * 2. Try to close it before the deadline
+   * Expected: Transaction should fail and the bank remains open
+   */
+  it("should fail to close bank before deadline", async () => {
+    await sunset(sunsetPeriod);
+
+    await expect(
+      sendTransaction([await getCloseIx()], connection, authority)
+    ).rejects.toThrow();
+    expect(await connection.getAccountInfo(bankKey)).not.toBeNull();
+  });
+
+  /**
+   * Test: Close After Deadline
+   * Flow:
+   * 1. Sunset the bank
+   * 2. Move time past the deadline
+   * 3. Close the bank
+   * Expected: Bank and vault accounts are closed
+   */
+  it("should close bank and vault after deadline", async () => {
+    await sunset(sunsetPeriod);
+    await bankrunContextWrapper.moveTimeForward(sunsetPeriod);
+
+    await sendTransaction([await getCloseIx()], connection, authority);
+
+    const vault = getBankTokenAccountPublicKey(bankKey, PROGRAM_ID);
+    expect(await connection.getAccountInfo(bankKey)).toBeNull();
+    expect(await connection.getAccountInfo(vault)).toBeNull();
+  });
+});
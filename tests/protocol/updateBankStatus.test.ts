@@ -12,8 +12,10 @@ import {
   getInitializePoolIx,
   getInitializeBankIx,
   getUpdateBankStatusIx,
+  getSunsetBankIx,
   BankAccount,
   BankStatus,
+  FROZEN_TIMELOCK_SECONDS,
 } from "@/sdk";
 import * as anchor from "@coral-xyz/anchor";
 import { BankrunProvider } from "anchor-bankrun";
@@ -37,6 +39,29 @@ describe("Update Bank Status", () => {
   const poolId = 0;
   const bankId = 0;
 
+  /**
+   * Set the bank status using the admin authority
+   * @param status New bank status
+   */
+  async function setStatus(status: BankStatus) {
+    const ix = await getUpdateBankStatusIx(
+      authority.publicKey,
+      status,
+      poolId,
+      bankId
+    );
+    await sendTransaction([ix], connection, authority);
+  }
+
+  /**
+   * Fetch the current bank status
+   * @returns Current bank status
+   */
+  async function fetchStatus(): Promise<BankStatus> {
+    const bankInfo = await connection.getAccountInfo(bankKey);
+    return BankAccount.decode(bankInfo.data).status;
+  }
+
   beforeEach(async () => {
     // Set up testing environment
     context = await startAnchor(
@@ -158,7 +183,7 @@ describe("Update Bank Status", () => {
    * Expected: Function should throw error during parameter validation
    */
   it("should fail with invalid status value", () => {
-    const invalidStatus = 3; // Beyond ReduceOnly(2)
+    const invalidStatus = 6; // Beyond Sunset(5)
 
     expect(() =>
       getUpdateBankStatusIx(
@@ -234,4 +259,60 @@ describe("Update Bank Status", () => {
     bank = BankAccount.decode(bankInfo.data);
     expect(bank.status).toBe(BankStatus.Inactive);
   });
+
+  /**
+   * Test: Sunset Is Terminal
+   * Flow:
+   * 1. Inactive -> Active -> Sunset
+   * 2. Try to reactivate the bank
+   * Expected: Leaving Sunset should fail with invalid transition error
+   */
+  it("should not allow leaving Sunset", async () => {
+    await setStatus(BankStatus.Active);
+    const clock = await client.getClock();
+    const sunsetIx = await getSunsetBankIx(
+      authority.publicKey,
+      poolId,
+      bankId,
+      Number(clock.unixTimestamp) + 60
+    );
+    await sendTransaction([sunsetIx], connection, authority);
+
+    await expect(setStatus(BankStatus.Active)).rejects.toThrow();
+    expect(await fetchStatus()).toBe(BankStatus.Sunset);
+  });
+
+  /**
+   * Test: Frozen Timelock
+   * Flow:
+   * 1. Freeze the bank
+   * 2. Try to reopen it before the timelock elapses
+   * 3. Move time past the timelock and reopen in DepositOnly
+   * Expected: Reopen fails before the timelock and succeeds after it
+   */
+  it("should enforce timelock when leaving Frozen", async () => {
+    await setStatus(BankStatus.Frozen);
+
+    await expect(setStatus(BankStatus.DepositOnly)).rejects.toThrow();
+    expect(await fetchStatus()).toBe(BankStatus.Frozen);
+
+    await bankrunContextWrapper.moveTimeForward(FROZEN_TIMELOCK_SECONDS);
+    await setStatus(BankStatus.DepositOnly);
+    expect(await fetchStatus()).toBe(BankStatus.DepositOnly);
+  });
+
+  /**
+   * Test: Frozen Cannot Reopen Directly
+   * Flow:
+   * 1. Freeze the bank and wait for the timelock
+   * 2. Try to set the bank straight back to Active
+   * Expected: Transaction should fail, frozen banks reopen through a restricted mode
+   */
+  it("should not allow Frozen to go directly to Active", async () => {
+    await setStatus(BankStatus.Frozen);
+    await bankrunContextWrapper.moveTimeForward(FROZEN_TIMELOCK_SECONDS);
+
+    await expect(setStatus(BankStatus.Active)).rejects.toThrow();
+    expect(await fetchStatus()).toBe(BankStatus.Frozen);
+  });
 });
@@ -0,0 +1,118 @@
+import { BankrunContextWrapper, PROGRAM_ID, sendTransaction } from "@/helpers";
+import {
+  getInitializeIx,
+  getInitializePoolIx,
+  getUpdatePoolIx,
+  getPoolPublicKey,
+  DEFAULT_POOL_CONFIG,
+  PoolAccount,
+  PoolStatus,
+  UpdatePoolParams,
+} from "@/sdk";
+import * as anchor from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { BanksClient, ProgramTestContext, startAnchor } from "solana-bankrun";
+import { PublicKey, Keypair, Connection, SystemProgram } from "@solana/web3.js";
+
+describe("Update Pool", () => {
+  let context: ProgramTestContext;
+  let client: BanksClient;
+  let provider: BankrunProvider;
+  let connection: Connection;
+  let bankrunContextWrapper: BankrunContextWrapper;
+
+  let authority: Keypair;
+  let poolAuthority: Keypair;
+  let poolKey: PublicKey;
+
+  const poolId = 0;
+  const updateParams: UpdatePoolParams = {
+    status: PoolStatus.Inactive,
+    closeFactor: 100,
+    maxLiquidationBonus: 10,
+    maxPositions: 4,
+  };
+
+  beforeEach(async () => {
+    // Set up testing environment
+    context = await startAnchor("", [], []);
+    client = context.banksClient;
+    authority = context.payer;
+    poolAuthority = Keypair.generate();
+
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+
+    bankrunContextWrapper = new BankrunContextWrapper(context);
+    connection = bankrunContextWrapper.connection.toConnection();
+
+    poolKey = getPoolPublicKey(poolId, PROGRAM_ID);
+
+    // Initialize admin and a pool managed by a separate pool authority
+    const initIx = await getInitializeIx(authority.publicKey);
+    const initPoolIx = await getInitializePoolIx(
+      authority.publicKey,
+      poolId,
+      DEFAULT_POOL_CONFIG,
+      poolAuthority.publicKey
+    );
+    const fundIx = SystemProgram.transfer({
+      fromPubkey: authority.publicKey,
+      toPubkey: poolAuthority.publicKey,
+      lamports: 1_000_000_000,
+    });
+    await sendTransaction([initIx, initPoolIx, fundIx], connection, authority);
+  });
+
+  /**
+   * Test: Update Pool Successfully
+   * Flow:
+   * 1. Pool authority updates pool configuration
+   * 2. Verify pool account reflects new configuration
+   * Expected: Pool configuration updated
+   */
+  it("should update pool configuration", async () => {
+    const ix = await getUpdatePoolIx(
+      poolAuthority.publicKey,
+      poolId,
+      updateParams
+    );
+    await sendTransaction([ix], connection, poolAuthority);
+
+    const poolInfo = await connection.getAccountInfo(poolKey);
+    const pool = PoolAccount.decode(poolInfo.data);
+    expect(pool.status).toBe(PoolStatus.Inactive);
+    expect(pool.closeFactor).toBe(updateParams.closeFactor);
+    expect(pool.maxLiquidationBonus).toBe(updateParams.maxLiquidationBonus);
+    expect(pool.maxPositions).toBe(updateParams.maxPositions);
+  });
+
+  /**
+   * Test: Global Admin Cannot Update Pool
+   * Flow:
+   * 1. Admin authority tries to update a pool it does not manage
+   * Expected: Transaction should fail with unauthorized error
+   */
+  it("should fail when signed by non pool authority", async () => {
+    const ix = await getUpdatePoolIx(authority.publicKey, poolId, updateParams);
+    await expect(
+      sendTransaction([ix], connection, authority)
+    ).rejects.toThrow();
+  });
+
+  /**
+   * Test: Invalid Pool Status
+   * Flow:
+   * 1. Try to update pool with status beyond Active
+   * Expected: Transaction should fail with invalid input error
+   */
+  it("should fail with invalid status", async () => {
+    const ix = await getUpdatePoolIx(poolAuthority.publicKey, poolId, {
+      ...updateParams,
+      status: 2 as PoolStatus,
+    });
+    await expect(
+      sendTransaction([ix], connection, poolAuthority)
+    ).rejects.toThrow(/0x1772/);
+  });
+});
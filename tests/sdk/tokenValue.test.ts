@@ -0,0 +1,91 @@
+import { BankAccount, BankStatus } from "@/sdk";
+import { PublicKey } from "@solana/web3.js";
+import { BN } from "bn.js";
+
+/**
+ * Builds an in-memory bank quoting `price` scaled by 10^`exponent`
+ */
+function bank(decimals: number, price: BN, exponent: number): BankAccount {
+  return new BankAccount({
+    bankId: 0,
+    poolId: 0,
+    bump: 0,
+    status: BankStatus.Active,
+    decimals,
+    padding: [],
+    allowedExtensions: 0,
+    flashLoanFee: 0,
+    statusUpdatedAt: new BN(0),
+    sunsetDeadline: new BN(0),
+    totalDeposits: new BN(0),
+    totalLiabilities: new BN(0),
+    flashLoanOutstanding: new BN(0),
+    flashLoanFees: new BN(0),
+    mint: PublicKey.default,
+    priceMessage: {
+      emaPrice: price,
+      emaConf: new BN(0),
+      price,
+      conf: new BN(0),
+      exponent,
+      padding: 0,
+      publishTime: 0,
+    },
+  });
+}
+
+describe("Token Value", () => {
+  /**
+   * Test: Value Tokens With Signed Exponents
+   * Flow:
+   * 1. Value amounts against prices with negative, zero and positive exponents
+   * Expected: Values match the program's calculate_token_value, rounding down
+   */
+  it("should scale values by the signed price exponent", () => {
+    // [amount, decimals, price, exponent, expected value]
+    const cases: [string, number, string, number, string][] = [
+      // 2 SOL at a Pyth-style 150.00000000 USD
+      ["2000000000", 9, "15000000000", -8, "300000000"],
+      // 1 token at 3 * 10^2 USD
+      ["1000000", 6, "3", 2, "300000000"],
+      // Half a token at 1 USD keeps its sub-dollar value
+      ["500000", 6, "1", 0, "500000"],
+      // 1 token at 0.00000001 USD rounds down to zero
+      ["1000000", 6, "1", -8, "0"],
+      // A token with no decimals at 1.5 USD
+      ["7", 0, "15", -1, "10500000"],
+    ];
+    for (const [amount, decimals, price, exponent, expected] of cases) {
+      const value = bank(decimals, new BN(price), exponent).calculateUsdValue(
+        new BN(amount)
+      );
+      expect(value.toString()).toBe(expected);
+    }
+  });
+
+  /**
+   * Test: Value Amounts Beyond Safe Integers
+   * Flow:
+   * 1. Value u64::MAX tokens at a Pyth-style price
+   * Expected: The value is exact instead of losing precision to floating point
+   */
+  it("should value amounts beyond safe integers exactly", () => {
+    const amount = new BN("18446744073709551615");
+    const value = bank(9, new BN("15000000000"), -8).calculateUsdValue(amount);
+    expect(value.toString()).toBe(
+      amount.mul(new BN(150)).div(new BN(1000)).toString()
+    );
+  });
+
+  /**
+   * Test: Value Tokens At A Zero Price
+   * Flow:
+   * 1. Value tokens against a bank whose price is zero
+   * Expected: Valuation throws like the program's InvalidPrice error
+   */
+  it("should reject a zero price", () => {
+    expect(() =>
+      bank(6, new BN(0), -8).calculateUsdValue(new BN(1_000_000))
+    ).toThrow("Price must be positive");
+  });
+});
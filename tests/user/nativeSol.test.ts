@@ -0,0 +1,227 @@
+import {
+  BankrunContextWrapper,
+  PROGRAM_ID,
+  constructMint,
+  sendTransaction,
+} from "@/helpers";
+import {
+  getBankPublicKey,
+  getBankTokenAccountPublicKey,
+  getNativeTokenAccountPublicKey,
+  getInitializeIx,
+  getInitializePoolIx,
+  getInitializeBankIx,
+  getUpdateBankStatusIx,
+  getUpdatePriceIx,
+  getInitializeUserIx,
+  getDepositNativeIx,
+  getWithdrawNativeIx,
+  getUserPublicKey,
+  BankAccount,
+  BankStatus,
+  TokenProgram,
+  UserAccount,
+} from "@/sdk";
+import * as anchor from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { BanksClient, ProgramTestContext, startAnchor } from "solana-bankrun";
+import { PublicKey, Keypair, Connection } from "@solana/web3.js";
+import { NATIVE_MINT, getAccount } from "@solana/spl-token";
+import { BN } from "bn.js";
+
+describe("Native SOL", () => {
+  let context: ProgramTestContext;
+  let client: BanksClient;
+  let provider: BankrunProvider;
+  let connection: Connection;
+  let bankrunContextWrapper: BankrunContextWrapper;
+
+  let authority: Keypair;
+  let bankKey: PublicKey;
+  let userKey: PublicKey;
+
+  const poolId = 0;
+  const bankId = 0;
+  const userId = 0;
+  const depositAmount = 2_000_000_000;
+
+  /**
+   * Fetch the user's balance in the wrapped SOL bank
+   * @returns Signed balance of the bank position
+   */
+  async function fetchUserBalance(): Promise<number> {
+    const user = UserAccount.decode(
+      (await connection.getAccountInfo(userKey)).data
+    );
+    return user.tokenBalances[0].balance.toNumber();
+  }
+
+  /**
+   * Fetch the wrapped SOL held by the bank vault
+   * @returns Vault token amount
+   */
+  async function fetchVaultAmount(): Promise<number> {
+    const vault = await getAccount(
+      connection,
+      getBankTokenAccountPublicKey(bankKey, PROGRAM_ID)
+    );
+    return Number(vault.amount);
+  }
+
+  beforeEach(async () => {
+    // Set up testing environment with the native mint
+    context = await startAnchor(
+      "",
+      [],
+      [
+        constructMint(
+          NATIVE_MINT,
+          0,
+          PublicKey.default,
+          0,
+          9,
+          0,
+          PublicKey.default
+        ),
+      ]
+    );
+    client = context.banksClient;
+    authority = context.payer;
+
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+
+    bankrunContextWrapper = new BankrunContextWrapper(context);
+    connection = bankrunContextWrapper.connection.toConnection();
+
+    bankKey = getBankPublicKey(poolId, bankId, PROGRAM_ID);
+    userKey = getUserPublicKey(poolId, userId, authority.publicKey, PROGRAM_ID);
+
+    // Initialize admin, pool, an active priced wrapped SOL bank and a user
+    const initIx = await getInitializeIx(authority.publicKey);
+    const initPoolIx = await getInitializePoolIx(authority.publicKey, poolId);
+    await sendTransaction([initIx, initPoolIx], connection, authority);
+
+    const initBankIx = await getInitializeBankIx(
+      authority.publicKey,
+      NATIVE_MINT,
+      poolId,
+      bankId,
+      TokenProgram.TOKEN_PROGRAM
+    );
+    const activateIx = await getUpdateBankStatusIx(
+      authority.publicKey,
+      BankStatus.Active,
+      poolId,
+      bankId
+    );
+    const priceIx = await getUpdatePriceIx(
+      authority.publicKey,
+      {
+        emaPrice: new BN(150),
+        emaConf: new BN(0),
+        price: new BN(150),
+        conf: new BN(0),
+        exponent: 0,
+        publishTime: new BN(0),
+      },
+      poolId,
+      bankId
+    );
+    const initUserIx = await getInitializeUserIx(
+      authority.publicKey,
+      poolId,
+      userId
+    );
+    await sendTransaction(
+      [initBankIx, activateIx, priceIx, initUserIx],
+      connection,
+      authority
+    );
+  });
+
+  /**
+   * Test: Deposit Native SOL
+   * Flow:
+   * 1. Deposit lamports into the wrapped SOL bank
+   * 2. Check user balance, vault, wallet lamports and temporary account
+   * Expected: Lamports are wrapped into the vault and the temporary account is closed
+   */
+  it("should wrap lamports on deposit", async () => {
+    const lamportsBefore = await connection.getBalance(authority.publicKey);
+
+    const ix = await getDepositNativeIx(
+      authority.publicKey,
+      userKey,
+      poolId,
+      bankId,
+      depositAmount
+    );
+    await sendTransaction([ix], connection, authority);
+
+    expect(await fetchUserBalance()).toBe(depositAmount);
+    expect(await fetchVaultAmount()).toBe(depositAmount);
+
+    // Only the amount and transaction fees leave the wallet, temporary rent is refunded
+    const lamportsAfter = await connection.getBalance(authority.publicKey);
+    const spent = lamportsBefore - lamportsAfter;
+    expect(spent).toBeGreaterThanOrEqual(depositAmount);
+    expect(spent).toBeLessThan(depositAmount + 100_000);
+
+    const temporaryAccount = getNativeTokenAccountPublicKey(
+      userKey,
+      PROGRAM_ID
+    );
+    expect(await connection.getAccountInfo(temporaryAccount)).toBeNull();
+
+    const bank = BankAccount.decode(
+      (await connection.getAccountInfo(bankKey)).data
+    );
+    expect(bank.totalDeposits.toNumber()).toBe(depositAmount);
+  });
+
+  /**
+   * Test: Withdraw Native SOL
+   * Flow:
+   * 1. Deposit lamports into the wrapped SOL bank
+   * 2. Withdraw part of the deposit as native SOL
+   * Expected: Wallet receives lamports and user balance decreases
+   */
+  it("should unwrap lamports on withdrawal", async () => {
+    const depositIx = await getDepositNativeIx(
+      authority.publicKey,
+      userKey,
+      poolId,
+      bankId,
+      depositAmount
+    );
+    await sendTransaction([depositIx], connection, authority);
+
+    const withdrawAmount = depositAmount / 2;
+    const lamportsBefore = await connection.getBalance(authority.publicKey);
+
+    const withdrawIx = await getWithdrawNativeIx(
+      authority.publicKey,
+      userKey,
+      poolId,
+      bankId,
+      withdrawAmount,
+      [bankId]
+    );
+    await sendTransaction([withdrawIx], connection, authority);
+
+    expect(await fetchUserBalance()).toBe(depositAmount - withdrawAmount);
+    expect(await fetchVaultAmount()).toBe(depositAmount - withdrawAmount);
+
+    const lamportsAfter = await connection.getBalance(authority.publicKey);
+    expect(lamportsAfter - lamportsBefore).toBeGreaterThan(
+      withdrawAmount - 100_000
+    );
+
+    const temporaryAccount = getNativeTokenAccountPublicKey(
+      userKey,
+      PROGRAM_ID
+    );
+    expect(await connection.getAccountInfo(temporaryAccount)).toBeNull();
+  });
+});
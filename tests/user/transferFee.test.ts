@@ -0,0 +1,213 @@
+import { BankrunContextWrapper, PROGRAM_ID, sendTransaction } from "@/helpers";
+import {
+  getBankPublicKey,
+  getBankTokenAccountPublicKey,
+  getInitializeIx,
+  getInitializePoolIx,
+  getInitializeBankIx,
+  getUpdateBankStatusIx,
+  getInitializeUserIx,
+  getDepositIx,
+  getUserPublicKey,
+  BankAccount,
+  BankStatus,
+  TokenProgram,
+  UserAccount,
+} from "@/sdk";
+import * as anchor from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { BanksClient, ProgramTestContext, startAnchor } from "solana-bankrun";
+import {
+  PublicKey,
+  Keypair,
+  Connection,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMintToInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMintLen,
+} from "@solana/spl-token";
+
+describe("Transfer Fee Deposits", () => {
+  let context: ProgramTestContext;
+  let client: BanksClient;
+  let provider: BankrunProvider;
+  let connection: Connection;
+  let bankrunContextWrapper: BankrunContextWrapper;
+
+  let authority: Keypair;
+  let mint: Keypair;
+  let userTokenAccount: PublicKey;
+
+  const poolId = 0;
+  const bankId = 0;
+  const userId = 0;
+  const decimals = 6;
+  const feeBasisPoints = 100; // 1%
+  const maxFee = BigInt(1_000_000_000);
+  const mintedAmount = 1_000_000_000;
+
+  /**
+   * Create a Token-2022 mint with a transfer fee and fund an authority token account
+   */
+  async function createFeeMint() {
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const lamports = await connection.getMinimumBalanceForRentExemption(
+      mintLen
+    );
+    userTokenAccount = getAssociatedTokenAddressSync(
+      mint.publicKey,
+      authority.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const tx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: authority.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space: mintLen,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(
+        mint.publicKey,
+        authority.publicKey,
+        authority.publicKey,
+        feeBasisPoints,
+        maxFee,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMintInstruction(
+        mint.publicKey,
+        decimals,
+        authority.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createAssociatedTokenAccountInstruction(
+        authority.publicKey,
+        userTokenAccount,
+        authority.publicKey,
+        mint.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createMintToInstruction(
+        mint.publicKey,
+        userTokenAccount,
+        authority.publicKey,
+        mintedAmount,
+        [],
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+    tx.recentBlockhash = context.lastBlockhash;
+    tx.feePayer = authority.publicKey;
+    tx.sign(authority, mint);
+    await client.processTransaction(tx);
+  }
+
+  beforeEach(async () => {
+    // Set up testing environment
+    context = await startAnchor("", [], []);
+    client = context.banksClient;
+    authority = context.payer;
+    mint = Keypair.generate();
+
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+
+    bankrunContextWrapper = new BankrunContextWrapper(context);
+    connection = bankrunContextWrapper.connection.toConnection();
+
+    await createFeeMint();
+
+    // Initialize admin, pool, an active Token-2022 bank and a user
+    const initIx = await getInitializeIx(authority.publicKey);
+    const initPoolIx = await getInitializePoolIx(authority.publicKey, poolId);
+    await sendTransaction([initIx, initPoolIx], connection, authority);
+
+    const initBankIx = await getInitializeBankIx(
+      authority.publicKey,
+      mint.publicKey,
+      poolId,
+      bankId,
+      TokenProgram.TOKEN_2022_PROGRAM
+    );
+    const activateIx = await getUpdateBankStatusIx(
+      authority.publicKey,
+      BankStatus.Active,
+      poolId,
+      bankId
+    );
+    const initUserIx = await getInitializeUserIx(
+      authority.publicKey,
+      poolId,
+      userId
+    );
+    await sendTransaction(
+      [initBankIx, activateIx, initUserIx],
+      connection,
+      authority
+    );
+  });
+
+  /**
+   * Test: Deposit Credits Net Amount
+   * Flow:
+   * 1. Deposit into a bank whose mint charges a 1% transfer fee
+   * 2. Compare the user balance, bank totals and vault balance
+   * Expected: User is credited the amount after fees, matching the vault
+   */
+  it("should credit the amount received after transfer fees", async () => {
+    const amount = 100_000_000;
+    const expectedFee = (amount * feeBasisPoints) / 10_000;
+
+    const userKey = getUserPublicKey(
+      poolId,
+      userId,
+      authority.publicKey,
+      PROGRAM_ID
+    );
+    const ix = await getDepositIx(
+      authority.publicKey,
+      userKey,
+      poolId,
+      bankId,
+      amount,
+      userTokenAccount,
+      mint.publicKey,
+      TokenProgram.TOKEN_2022_PROGRAM
+    );
+    await sendTransaction([ix], connection, authority);
+
+    const bankKey = getBankPublicKey(poolId, bankId, PROGRAM_ID);
+    const vault = await getAccount(
+      connection,
+      getBankTokenAccountPublicKey(bankKey, PROGRAM_ID),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(Number(vault.amount)).toBe(amount - expectedFee);
+
+    const user = UserAccount.decode(
+      (await connection.getAccountInfo(userKey)).data
+    );
+    expect(user.tokenBalances[0].balance.toNumber()).toBe(
+      amount - expectedFee
+    );
+
+    const bank = BankAccount.decode(
+      (await connection.getAccountInfo(bankKey)).data
+    );
+    expect(bank.totalDeposits.toNumber()).toBe(amount - expectedFee);
+  });
+});
@@ -0,0 +1,383 @@
+import { BankrunContextWrapper, PROGRAM_ID, sendTransaction } from "@/helpers";
+import {
+  getInitializeIx,
+  getInitializePoolIx,
+  getInitializeBankIx,
+  getUpdateBankStatusIx,
+  getUpdatePriceIx,
+  getInitializeUserIx,
+  getProposeUserAuthorityIx,
+  getAcceptUserAuthorityIx,
+  getDepositIx,
+  getWithdrawIx,
+  getUserPublicKey,
+  BankStatus,
+  TokenProgram,
+  UserAccount,
+} from "@/sdk";
+import * as anchor from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { BanksClient, ProgramTestContext, startAnchor } from "solana-bankrun";
+import {
+  PublicKey,
+  Keypair,
+  Connection,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import {
+  MINT_SIZE,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createInitializeMintInstruction,
+  createMintToInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { BN } from "bn.js";
+
+describe("User Authority Transfer", () => {
+  let context: ProgramTestContext;
+  let client: BanksClient;
+  let provider: BankrunProvider;
+  let connection: Connection;
+  let bankrunContextWrapper: BankrunContextWrapper;
+
+  let authority: Keypair;
+  let newAuthority: Keypair;
+  let userKey: PublicKey;
+
+  const poolId = 0;
+  const userId = 0;
+
+  /**
+   * Fetch and decode the user account
+   * @returns Decoded user account
+   */
+  async function fetchUser(): Promise<UserAccount> {
+    const userInfo = await connection.getAccountInfo(userKey);
+    return UserAccount.decode(userInfo.data);
+  }
+
+  /**
+   * Fund a keypair so it can pay for its own transactions
+   * @param recipient Keypair to fund
+   */
+  async function fund(recipient: Keypair) {
+    const ix = SystemProgram.transfer({
+      fromPubkey: authority.publicKey,
+      toPubkey: recipient.publicKey,
+      lamports: 1_000_000_000,
+    });
+    await sendTransaction([ix], connection, authority);
+  }
+
+  /**
+   * Transfer the user account from the current authority to the new authority
+   */
+  async function transferAuthority() {
+    const proposeIx = await getProposeUserAuthorityIx(
+      authority.publicKey,
+      userKey,
+      newAuthority.publicKey
+    );
+    await sendTransaction([proposeIx], connection, authority);
+
+    const acceptIx = await getAcceptUserAuthorityIx(
+      newAuthority.publicKey,
+      userKey
+    );
+    await sendTransaction([acceptIx], connection, newAuthority);
+  }
+
+  /**
+   * List an active, priced bank for a new mint and fund a token account of each owner
+   * @param bankId Bank ID to list
+   * @param owners Wallets receiving a funded token account
+   * @param amount Amount minted to each token account
+   * @returns Mint and the token accounts, in the order of the owners
+   */
+  async function createFundedBank(
+    bankId: number,
+    owners: PublicKey[],
+    amount: number
+  ): Promise<{ mint: PublicKey; tokenAccounts: PublicKey[] }> {
+    const mint = Keypair.generate();
+    const lamports = await connection.getMinimumBalanceForRentExemption(
+      MINT_SIZE
+    );
+    const tokenAccounts = owners.map((owner) =>
+      getAssociatedTokenAddressSync(mint.publicKey, owner)
+    );
+
+    const tx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: authority.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space: MINT_SIZE,
+        lamports,
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      createInitializeMintInstruction(
+        mint.publicKey,
+        6,
+        authority.publicKey,
+        null
+      )
+    );
+    owners.forEach((owner, index) => {
+      tx.add(
+        createAssociatedTokenAccountInstruction(
+          authority.publicKey,
+          tokenAccounts[index],
+          owner,
+          mint.publicKey
+        ),
+        createMintToInstruction(
+          mint.publicKey,
+          tokenAccounts[index],
+          authority.publicKey,
+          amount
+        )
+      );
+    });
+    tx.recentBlockhash = context.lastBlockhash;
+    tx.feePayer = authority.publicKey;
+    tx.sign(authority, mint);
+    await client.processTransaction(tx);
+
+    const initBankIx = await getInitializeBankIx(
+      authority.publicKey,
+      mint.publicKey,
+      poolId,
+      bankId,
+      TokenProgram.TOKEN_PROGRAM
+    );
+    const activateIx = await getUpdateBankStatusIx(
+      authority.publicKey,
+      BankStatus.Active,
+      poolId,
+      bankId
+    );
+    const priceIx = await getUpdatePriceIx(
+      authority.publicKey,
+      {
+        emaPrice: new BN(1),
+        emaConf: new BN(0),
+        price: new BN(1),
+        conf: new BN(0),
+        exponent: 0,
+        publishTime: new BN(0),
+      },
+      poolId,
+      bankId
+    );
+    await sendTransaction(
+      [initBankIx, activateIx, priceIx],
+      connection,
+      authority
+    );
+
+    return { mint: mint.publicKey, tokenAccounts };
+  }
+
+  beforeEach(async () => {
+    // Set up testing environment
+    context = await startAnchor("", [], []);
+    client = context.banksClient;
+    authority = context.payer;
+    newAuthority = Keypair.generate();
+
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+
+    bankrunContextWrapper = new BankrunContextWrapper(context);
+    connection = bankrunContextWrapper.connection.toConnection();
+
+    userKey = getUserPublicKey(poolId, userId, authority.publicKey, PROGRAM_ID);
+
+    // Initialize admin, pool and user
+    const initIx = await getInitializeIx(authority.publicKey);
+    await sendTransaction([initIx], connection, authority);
+
+    const initPoolIx = await getInitializePoolIx(authority.publicKey, poolId);
+    await sendTransaction([initPoolIx], connection, authority);
+
+    const initUserIx = await getInitializeUserIx(
+      authority.publicKey,
+      poolId,
+      userId
+    );
+    await sendTransaction([initUserIx], connection, authority);
+
+    await fund(newAuthority);
+  });
+
+  /**
+   * Test: Propose And Accept Authority
+   * Flow:
+   * 1. Current authority proposes new authority
+   * 2. New authority accepts the proposal
+   * Expected: Authority is updated and pending authority is cleared
+   */
+  it("should transfer user authority after acceptance", async () => {
+    const proposeIx = await getProposeUserAuthorityIx(
+      authority.publicKey,
+      userKey,
+      newAuthority.publicKey
+    );
+    await sendTransaction([proposeIx], connection, authority);
+
+    let user = await fetchUser();
+    expect(user.authority).toEqual(authority.publicKey);
+    expect(user.pendingAuthority).toEqual(newAuthority.publicKey);
+
+    const acceptIx = await getAcceptUserAuthorityIx(
+      newAuthority.publicKey,
+      userKey
+    );
+    await sendTransaction([acceptIx], connection, newAuthority);
+
+    user = await fetchUser();
+    expect(user.authority).toEqual(newAuthority.publicKey);
+    expect(user.pendingAuthority).toEqual(PublicKey.default);
+  });
+
+  /**
+   * Test: Unauthorized Proposal
+   * Flow:
+   * 1. Non-authority tries to propose itself as authority
+   * Expected: Transaction should fail
+   */
+  it("should fail when proposed by non-authority", async () => {
+    const ix = await getProposeUserAuthorityIx(
+      newAuthority.publicKey,
+      userKey,
+      newAuthority.publicKey
+    );
+    await expect(
+      sendTransaction([ix], connection, newAuthority)
+    ).rejects.toThrow();
+  });
+
+  /**
+   * Test: Accept Without Proposal
+   * Flow:
+   * 1. Account tries to accept without a pending proposal
+   * Expected: Transaction should fail
+   */
+  it("should fail to accept without pending proposal", async () => {
+    const ix = await getAcceptUserAuthorityIx(newAuthority.publicKey, userKey);
+    await expect(
+      sendTransaction([ix], connection, newAuthority)
+    ).rejects.toThrow();
+  });
+
+  /**
+   * Test: Accept By Wrong Account
+   * Flow:
+   * 1. Current authority proposes new authority
+   * 2. A different account tries to accept
+   * Expected: Transaction should fail and authority remains unchanged
+   */
+  it("should fail when accepted by a different account", async () => {
+    const proposeIx = await getProposeUserAuthorityIx(
+      authority.publicKey,
+      userKey,
+      newAuthority.publicKey
+    );
+    await sendTransaction([proposeIx], connection, authority);
+
+    const other = Keypair.generate();
+    await fund(other);
+
+    const acceptIx = await getAcceptUserAuthorityIx(other.publicKey, userKey);
+    await expect(
+      sendTransaction([acceptIx], connection, other)
+    ).rejects.toThrow();
+
+    const user = await fetchUser();
+    expect(user.authority).toEqual(authority.publicKey);
+  });
+
+  /**
+   * Test: New Authority Uses Position
+   * Flow:
+   * 1. Transfer the user account to the new authority
+   * 2. New authority deposits 1,000 tokens and withdraws 400 of them
+   * 3. Old authority tries to deposit and withdraw with the same user account
+   * Expected: New authority's deposit and withdrawal succeed, the old authority is
+   * rejected and the position keeps 600 tokens
+   */
+  it("should let the new authority deposit and withdraw", async () => {
+    const bankId = 0;
+    await transferAuthority();
+    const {
+      mint,
+      tokenAccounts: [newAuthorityTokenAccount, oldAuthorityTokenAccount],
+    } = await createFundedBank(
+      bankId,
+      [newAuthority.publicKey, authority.publicKey],
+      1_000_000_000
+    );
+
+    const depositIx = await getDepositIx(
+      newAuthority.publicKey,
+      userKey,
+      poolId,
+      bankId,
+      1_000_000_000,
+      newAuthorityTokenAccount,
+      mint
+    );
+    await sendTransaction([depositIx], connection, newAuthority);
+
+    const withdrawIx = await getWithdrawIx(
+      newAuthority.publicKey,
+      userKey,
+      poolId,
+      bankId,
+      400_000_000,
+      newAuthorityTokenAccount,
+      [bankId],
+      mint
+    );
+    await sendTransaction([withdrawIx], connection, newAuthority);
+
+    let user = await fetchUser();
+    expect(user.tokenBalances[0].balance.toNumber()).toBe(600_000_000);
+    const tokenAccount = await getAccount(connection, newAuthorityTokenAccount);
+    expect(Number(tokenAccount.amount)).toBe(400_000_000);
+
+    const oldDepositIx = await getDepositIx(
+      authority.publicKey,
+      userKey,
+      poolId,
+      bankId,
+      1_000_000,
+      oldAuthorityTokenAccount,
+      mint
+    );
+    await expect(
+      sendTransaction([oldDepositIx], connection, authority)
+    ).rejects.toThrow();
+
+    const oldWithdrawIx = await getWithdrawIx(
+      authority.publicKey,
+      userKey,
+      poolId,
+      bankId,
+      1_000_000,
+      oldAuthorityTokenAccount,
+      [bankId],
+      mint
+    );
+    await expect(
+      sendTransaction([oldWithdrawIx], connection, authority)
+    ).rejects.toThrow();
+
+    user = await fetchUser();
+    expect(user.authority).toEqual(newAuthority.publicKey);
+    expect(user.tokenBalances[0].balance.toNumber()).toBe(600_000_000);
+  });
+});